@@ -4,15 +4,40 @@
 //!
 //! ## Interface
 //!
-//! -`PricesManager`: provides get_underlying_price interface.
+//! -`PriceSource`: provides get_underlying_price interface.
+//!
+//! -`PriceLocker`: locks the prices at the current feed answers. The locked prices are provided
+//! instead of the feed answers until they are unlocked.
+//!
+//! -`DataProvider`: provides chainlink prices as a data source for the prices module.
 //!
 //! ### Dispatchable Functions (extrinsics)
 //!
-//! - `enable_feeding` - Enable providing oracle prices.
+//! - `enable_feeding` - Enable providing oracle prices. Feeding can be enabled only if all feed
+//! descriptions are unique and every enabled underlying asset has a feed.
 //!
 //! - `disable_feeding` - Disable providing oracle prices.
 //! The get_underlying_price will return None.
 //!
+//! - `bind_feed` - Bind the chainlink feed to the underlying asset. Optionally sets the number
+//! of decimals of the feed answers, which are normalised to the price accuracy.
+//!
+//! - `unbind_feed` - Unbind the chainlink feed from the underlying asset.
+//!
+//! - `set_max_staleness` - Set maximum number of blocks since the last update of the feed round
+//! after which the price is considered stale. The get_underlying_price returns None for stale
+//! prices.
+//!
+//! - `set_round_initiation_period` - Set number of blocks between the new rounds initiated by
+//! the offchain worker for the feed.
+//!
+//! ### Offchain worker
+//!
+//! Every round initiation period the offchain worker initiates a new round for the feed. A new
+//! round isn't initiated while the previously initiated round hasn't collected enough oracle
+//! submissions to be answered. The round that isn't answered within `RoundAnswerTimeout` blocks
+//! is initiated again.
+//!
 //!  TODO Pallet in development.
 //!  Implement provider types Chainlink and Minterest
 
@@ -25,10 +50,14 @@ use frame_system::{
 	pallet_prelude::*,
 };
 use minterest_primitives::{currency::CurrencyType::UnderlyingAsset, currency::*, CurrencyId, OffchainErr, Price};
+use orml_traits::DataProvider;
 use pallet_chainlink_feed::{FeedInterface, FeedOracle, RoundData, RoundId};
-use pallet_traits::PricesManager;
-use sp_runtime::traits::{One, Zero};
-use sp_std::convert::TryInto;
+use pallet_traits::{PriceLocker, PriceSource};
+use sp_runtime::{
+	traits::{One, Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::{collections::btree_set::BTreeSet, convert::TryInto, vec::Vec};
 pub use weights::WeightInfo;
 
 #[cfg(test)]
@@ -36,12 +65,33 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
 pub mod weights;
 
 pub use module::*;
 
 type ChainlinkFeedPallet<T> = pallet_chainlink_feed::Pallet<T>;
 
+/// Number of decimals of the price.
+pub const PRICE_DECIMALS: u8 = 18;
+
+/// Storage version of the pallet.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum Releases {
+	/// Feeds are looked up by their descriptions.
+	V0,
+	/// Feeds are bound to the underlying assets explicitly.
+	V1,
+	/// Feeding is enabled and disabled explicitly.
+	V2,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -59,17 +109,203 @@ pub mod module {
 		/// multiple pallets send unsigned transactions.
 		type UnsignedPriority: Get<TransactionPriority>;
 
+		/// The origin which may update chainlink price manager parameters. Root or
+		/// Half Minterest Council can always do this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Number of blocks between the new rounds of the feeds without the configured round
+		/// initiation period. Must be greater than zero.
+		#[pallet::constant]
+		type DefaultRoundInitiationPeriod: Get<Self::BlockNumber>;
+
+		/// Number of blocks after which the initiated round that hasn't collected enough oracle
+		/// submissions is initiated again.
+		#[pallet::constant]
+		type RoundAnswerTimeout: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics.
 		type ChainlinkPriceManagerWeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
-	pub enum Error<T> {}
+	pub enum Error<T> {
+		/// The currency is not enabled in protocol.
+		NotValidUnderlyingAssetId,
+		/// Maximum staleness can't be zero.
+		ZeroMaxStaleness,
+		/// Feeding is already enabled.
+		FeedingAlreadyEnabled,
+		/// Feeding is already disabled.
+		FeedingAlreadyDisabled,
+		/// There are several feeds with the same description.
+		FeedDescriptionsAreNotUnique,
+		/// The feed doesn't exist or one of the enabled underlying assets has no bound feed.
+		FeedNotExists,
+		/// The feed is already bound to another underlying asset.
+		FeedAlreadyBound,
+		/// There is no feed bound to the underlying asset.
+		FeedNotBound,
+		/// The feed can't be unbound while feeding is enabled.
+		FeedingIsEnabled,
+		/// Number of decimals of the feed can't be greater than the price decimals.
+		NotValidFeedDecimals,
+		/// Round initiation period can't be zero.
+		ZeroRoundInitiationPeriod,
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(fn deposit_event)]
 	pub enum Event<T: Config> {
 		InitiateNewRound(T::FeedId, RoundId),
+		/// Maximum staleness has been successfully changed: \[currency_id, max_staleness\]
+		MaxStalenessChanged(CurrencyId, Option<T::BlockNumber>),
+		/// The feed hasn't been updated for longer than the maximum staleness:
+		/// \[currency_id, feed_id, updated_at\]
+		FeedIsStale(CurrencyId, T::FeedId, T::BlockNumber),
+		/// The stale feed has been updated: \[currency_id, feed_id\]
+		FeedIsUpdated(CurrencyId, T::FeedId),
+		/// Providing oracle prices has been enabled.
+		FeedingEnabled,
+		/// Providing oracle prices has been disabled.
+		FeedingDisabled,
+		/// The feed has been bound to the underlying asset: \[currency_id, feed_id, decimals\]
+		FeedBound(CurrencyId, T::FeedId, Option<u8>),
+		/// The feed has been unbound from the underlying asset: \[currency_id, feed_id\]
+		FeedUnbound(CurrencyId, T::FeedId),
+		/// Round initiation period has been changed: \[feed_id, round_initiation_period\]
+		RoundInitiationPeriodChanged(T::FeedId, Option<T::BlockNumber>),
+		/// Lock price. \[currency_id, locked_price\]
+		LockPrice(CurrencyId, Price),
+		/// Unlock price. \[currency_id\]
+		UnlockPrice(CurrencyId),
+	}
+
+	/// Defines whether the oracle prices are provided. While feeding is disabled
+	/// get_underlying_price returns None.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::feeding_enabled_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn feeding_enabled_storage)]
+	pub type FeedingEnabledStorage<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Chainlink feeds that provide prices of the underlying assets.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::feed_id_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn feed_id_storage)]
+	pub type FeedIdStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, T::FeedId, OptionQuery>;
+
+	/// Number of decimals of the feed answers. Answers of the feeds without a value are
+	/// considered to have the price decimals.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::feed_decimals_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn feed_decimals_storage)]
+	pub type FeedDecimalsStorage<T: Config> = StorageMap<_, Twox64Concat, T::FeedId, u8, OptionQuery>;
+
+	/// Storage version of the pallet.
+	#[pallet::storage]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	/// Maximum number of blocks since the last update of the feed round after which the price
+	/// is considered stale. No value means that the staleness is not checked.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::max_staleness_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn max_staleness_storage)]
+	pub type MaxStalenessStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, T::BlockNumber, OptionQuery>;
+
+	/// Currencies with stale feeds and the block number of the last feed round update.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::stale_feeds_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn stale_feeds_storage)]
+	pub type StaleFeedsStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, T::BlockNumber, OptionQuery>;
+
+	/// Number of blocks between the new rounds initiated by the offchain worker for the feed.
+	/// No value means that `DefaultRoundInitiationPeriod` is used.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::round_initiation_period_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn round_initiation_period_storage)]
+	pub type RoundInitiationPeriodStorage<T: Config> =
+		StorageMap<_, Twox64Concat, T::FeedId, T::BlockNumber, OptionQuery>;
+
+	/// The last round initiated by the offchain worker for the feed.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::last_initiated_round_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn last_initiated_round_storage)]
+	pub type LastInitiatedRoundStorage<T: Config> = StorageMap<_, Twox64Concat, T::FeedId, RoundId, OptionQuery>;
+
+	/// The block at which the last round for the feed was initiated.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::last_round_initiation_block_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn last_round_initiation_block_storage)]
+	pub type LastRoundInitiationBlockStorage<T: Config> =
+		StorageMap<_, Twox64Concat, T::FeedId, T::BlockNumber, OptionQuery>;
+
+	/// Prices locked at the feed answers. The locked price is provided instead of the feed
+	/// answer until it is unlocked.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=chainlink_price_manager::module::Pallet::locked_price_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT chainlink_price_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn locked_price_storage)]
+	pub type LockedPriceStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub feeding_enabled: bool,
+		pub _phantom: PhantomData<T>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig {
+				feeding_enabled: false,
+				_phantom: PhantomData,
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			if self.feeding_enabled {
+				Pallet::<T>::check_feeds().expect("feeds of the enabled currencies are not valid");
+			}
+			FeedingEnabledStorage::<T>::put(self.feeding_enabled);
+			StorageVersion::<T>::put(Releases::V2);
+		}
 	}
 
 	#[pallet::pallet]
@@ -82,6 +318,9 @@ pub mod module {
 		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
 			match call {
 				Call::initiate_new_round(feed_id, round_id) => {
+					if !Self::can_initiate_round(feed_id, *round_id, <frame_system::Pallet<T>>::block_number()) {
+						return InvalidTransaction::Stale.into();
+					}
 					ValidTransaction::with_tag_prefix("ChainlinkPriceManagerWorker")
 						.priority(T::UnsignedPriority::get())
 						.and_provides((<frame_system::Pallet<T>>::block_number(), feed_id, round_id))
@@ -96,6 +335,44 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate_to_v1::<T>().saturating_add(migrations::migrate_to_v2::<T>())
+		}
+
+		/// Emits an event when a feed goes stale or a stale feed is updated.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut checked_feeds: Weight = 0;
+			MaxStalenessStorage::<T>::iter().for_each(|(currency_id, _)| {
+				checked_feeds += 1;
+				let (feed_id, updated_at) = match Self::get_feed_id(currency_id).and_then(|feed_id| {
+					let RoundData { updated_at, .. } = <ChainlinkFeedPallet<T>>::feed(feed_id)?.latest_data();
+					Some((feed_id, updated_at))
+				}) {
+					Some(feed_data) => feed_data,
+					None => return,
+				};
+
+				let is_stale = Self::is_round_stale(currency_id, updated_at, now);
+				match (is_stale, StaleFeedsStorage::<T>::contains_key(currency_id)) {
+					(true, false) => {
+						StaleFeedsStorage::<T>::insert(currency_id, updated_at);
+						Self::deposit_event(Event::FeedIsStale(currency_id, feed_id, updated_at));
+					}
+					(false, true) => {
+						StaleFeedsStorage::<T>::remove(currency_id);
+						Self::deposit_event(Event::FeedIsUpdated(currency_id, feed_id));
+					}
+					_ => {}
+				}
+			});
+			// Feed lookup, round data and stale feed flag are read for every checked feed.
+			T::DbWeight::get().reads_writes(checked_feeds.saturating_mul(4), checked_feeds)
+		}
+
+		fn integrity_test() {
+			assert!(!T::DefaultRoundInitiationPeriod::get().is_zero());
+		}
+
 		fn offchain_worker(now: T::BlockNumber) {
 			if let Err(error) = Self::_offchain_worker(now) {
 				log::info!(
@@ -117,7 +394,7 @@ pub mod module {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Produces events to initiate a new round for oracles.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::ChainlinkPriceManagerWeightInfo::initiate_new_round())]
 		#[transactional]
 		pub fn initiate_new_round(
 			origin: OriginFor<T>,
@@ -125,25 +402,193 @@ pub mod module {
 			new_round: RoundId,
 		) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
+			LastInitiatedRoundStorage::<T>::insert(feed_id, new_round);
+			LastRoundInitiationBlockStorage::<T>::insert(feed_id, <frame_system::Pallet<T>>::block_number());
 			Self::deposit_event(Event::InitiateNewRound(feed_id, new_round));
 			Ok(().into())
 		}
 
-		/// Enables feeding. Start providing prices
-		#[pallet::weight(10_000)]
+		/// Enables feeding. Start providing prices.
+		///
+		/// Feeding can be enabled only if descriptions of all feeds are unique and every enabled
+		/// underlying asset has a feed.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT chainlink_price_manager")]
+		#[pallet::weight(T::ChainlinkPriceManagerWeightInfo::enable_feeding())]
+		#[transactional]
+		pub fn enable_feeding(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Self::feeding_enabled_storage(), Error::<T>::FeedingAlreadyEnabled);
+			Self::check_feeds()?;
+
+			FeedingEnabledStorage::<T>::put(true);
+			Self::deposit_event(Event::FeedingEnabled);
+			Ok(().into())
+		}
+
+		/// Disables feeding. Stop providing prices, get_underlying_price returns None.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT chainlink_price_manager")]
+		#[pallet::weight(T::ChainlinkPriceManagerWeightInfo::disable_feeding())]
 		#[transactional]
-		pub fn enable_feeding(_origin: OriginFor<T>) -> DispatchResult {
-			// TODO make additional checks
-			// Check is all feed description are unique
-			// Check is all enabled currencies has feed
-			Ok(())
+		pub fn disable_feeding(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Self::feeding_enabled_storage(), Error::<T>::FeedingAlreadyDisabled);
+
+			FeedingEnabledStorage::<T>::put(false);
+			Self::deposit_event(Event::FeedingDisabled);
+			Ok(().into())
 		}
 
-		// TODO. Then get_underlying_price should always return None
-		#[pallet::weight(10_000)]
+		/// Bind the chainlink feed to the underlying asset. The feed previously bound to the
+		/// underlying asset is replaced.
+		///
+		/// Parameters:
+		/// - `currency_id`: the CurrencyId for which the feed is being bound.
+		/// - `feed_id`: the chainlink feed that provides the price of the currency.
+		/// - `decimals`: number of decimals of the feed answers. `None` means that the answers
+		/// have the price decimals.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT chainlink_price_manager")]
+		#[pallet::weight(T::ChainlinkPriceManagerWeightInfo::bind_feed())]
 		#[transactional]
-		pub fn disable_feeding(_origin: OriginFor<T>) -> DispatchResult {
-			Ok(())
+		pub fn bind_feed(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			feed_id: T::FeedId,
+			decimals: Option<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				currency_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(
+				<ChainlinkFeedPallet<T>>::feed(feed_id).is_some(),
+				Error::<T>::FeedNotExists
+			);
+			ensure!(
+				decimals.map_or(true, |decimals| decimals <= PRICE_DECIMALS),
+				Error::<T>::NotValidFeedDecimals
+			);
+			ensure!(
+				!FeedIdStorage::<T>::iter().any(|(bound_currency_id, bound_feed_id)| {
+					bound_feed_id == feed_id && bound_currency_id != currency_id
+				}),
+				Error::<T>::FeedAlreadyBound
+			);
+
+			if let Some(previous_feed_id) = FeedIdStorage::<T>::get(currency_id) {
+				FeedDecimalsStorage::<T>::remove(previous_feed_id);
+			}
+			FeedIdStorage::<T>::insert(currency_id, feed_id);
+			FeedDecimalsStorage::<T>::mutate_exists(feed_id, |feed_decimals| *feed_decimals = decimals);
+			StaleFeedsStorage::<T>::remove(currency_id);
+
+			Self::deposit_event(Event::FeedBound(currency_id, feed_id, decimals));
+			Ok(().into())
+		}
+
+		/// Unbind the chainlink feed from the underlying asset. Feeds can be unbound only while
+		/// feeding is disabled.
+		///
+		/// Parameters:
+		/// - `currency_id`: the CurrencyId for which the feed is being unbound.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT chainlink_price_manager")]
+		#[pallet::weight(T::ChainlinkPriceManagerWeightInfo::unbind_feed())]
+		#[transactional]
+		pub fn unbind_feed(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Self::feeding_enabled_storage(), Error::<T>::FeedingIsEnabled);
+			let feed_id = FeedIdStorage::<T>::take(currency_id).ok_or(Error::<T>::FeedNotBound)?;
+			FeedDecimalsStorage::<T>::remove(feed_id);
+			StaleFeedsStorage::<T>::remove(currency_id);
+
+			Self::deposit_event(Event::FeedUnbound(currency_id, feed_id));
+			Ok(().into())
+		}
+
+		/// Set maximum number of blocks since the last update of the feed round after which the
+		/// price is considered stale.
+		///
+		/// Parameters:
+		/// - `currency_id`: the CurrencyId for which the parameter value is being set.
+		/// - `max_staleness`: New value of maximum staleness. `None` disables the check.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT chainlink_price_manager")]
+		#[pallet::weight(T::ChainlinkPriceManagerWeightInfo::set_max_staleness())]
+		#[transactional]
+		pub fn set_max_staleness(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_staleness: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				currency_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+
+			match max_staleness {
+				Some(max_staleness) => {
+					ensure!(!max_staleness.is_zero(), Error::<T>::ZeroMaxStaleness);
+					MaxStalenessStorage::<T>::insert(currency_id, max_staleness);
+				}
+				None => {
+					MaxStalenessStorage::<T>::remove(currency_id);
+					StaleFeedsStorage::<T>::remove(currency_id);
+				}
+			}
+
+			Self::deposit_event(Event::MaxStalenessChanged(currency_id, max_staleness));
+			Ok(().into())
+		}
+
+		/// Set number of blocks between the new rounds initiated by the offchain worker for
+		/// the feed.
+		///
+		/// Parameters:
+		/// - `feed_id`: the feed for which the parameter value is being set.
+		/// - `round_initiation_period`: New value of the round initiation period. `None` means
+		/// that `DefaultRoundInitiationPeriod` is used.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT chainlink_price_manager")]
+		#[pallet::weight(T::ChainlinkPriceManagerWeightInfo::set_round_initiation_period())]
+		#[transactional]
+		pub fn set_round_initiation_period(
+			origin: OriginFor<T>,
+			feed_id: T::FeedId,
+			round_initiation_period: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				<ChainlinkFeedPallet<T>>::feed(feed_id).is_some(),
+				Error::<T>::FeedNotExists
+			);
+
+			match round_initiation_period {
+				Some(period) => {
+					ensure!(!period.is_zero(), Error::<T>::ZeroRoundInitiationPeriod);
+					RoundInitiationPeriodStorage::<T>::insert(feed_id, period);
+				}
+				None => RoundInitiationPeriodStorage::<T>::remove(feed_id),
+			}
+
+			Self::deposit_event(Event::RoundInitiationPeriodChanged(feed_id, round_initiation_period));
+			Ok(().into())
 		}
 	}
 }
@@ -161,19 +606,29 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn _offchain_worker(now: T::BlockNumber) -> Result<(), OffchainErr> {
-		// TODO implement extrinsic to set initiate round period instead of hardcoded 3
-		let bn: T::BlockNumber = (3_u32).into();
-		if !(now % bn).is_zero() {
-			return Ok(());
-		}
-
 		for currency in CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset) {
 			let feed_id = Self::get_feed_id(currency).ok_or(OffchainErr::ChainlinkFeedNotExists)?;
+			let round_initiation_period =
+				Self::round_initiation_period_storage(feed_id).unwrap_or_else(T::DefaultRoundInitiationPeriod::get);
+			if !(now % round_initiation_period).is_zero() {
+				continue;
+			}
+
 			let new_round_id = <ChainlinkFeedPallet<T>>::feed(feed_id)
 				.ok_or(OffchainErr::FailReceivingOraclePrice)?
 				.latest_round()
 				.checked_add(One::one())
 				.ok_or(OffchainErr::NumOverflow)?;
+			// The previously initiated round isn't answered until it collects enough oracle
+			// submissions. The round is initiated again after `RoundAnswerTimeout` blocks.
+			if !Self::can_initiate_round(&feed_id, new_round_id, now) {
+				log::debug!(
+					"Round {:?} for currency {:?} has not enough submissions yet",
+					new_round_id,
+					currency
+				);
+				continue;
+			}
 			log::debug!("New round_id {:?} for currency {:?}", new_round_id, currency);
 			let call = Call::<T>::initiate_new_round(feed_id, new_round_id);
 			if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
@@ -187,9 +642,23 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	// TODO This is temporary function. We need tom move this function as method to
-	// primitives/src/currency.rs. Also, add distingiush between chainlink provider and minterest
-	fn convert_to_description(currency_id: CurrencyId) -> &'static [u8] {
+	/// Checks whether the round of the feed can be initiated at the block `now`: the round follows
+	/// the last initiated round, or it is the last initiated round that hasn't been answered within
+	/// `RoundAnswerTimeout` blocks.
+	fn can_initiate_round(feed_id: &T::FeedId, round_id: RoundId, now: T::BlockNumber) -> bool {
+		match Self::last_initiated_round_storage(feed_id) {
+			Some(last_round) if last_round == round_id => Self::last_round_initiation_block_storage(feed_id)
+				.map_or(true, |initiated_at| {
+					now >= initiated_at.saturating_add(T::RoundAnswerTimeout::get())
+				}),
+			Some(last_round) => last_round < round_id,
+			None => true,
+		}
+	}
+
+	/// Returns the description of the feeds that were looked up by description before feeds
+	/// were bound to the underlying assets explicitly. Used by the storage migration.
+	pub fn convert_to_description(currency_id: CurrencyId) -> &'static [u8] {
 		match currency_id {
 			ETH => b"MIN-ETH",
 			DOT => b"MIN-DOT",
@@ -200,26 +669,53 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	/// Looks for appropriate feed config with description and returns FeedId
+	/// Checks that descriptions of all feeds are unique and every enabled underlying asset
+	/// has a bound feed.
+	fn check_feeds() -> DispatchResult {
+		let mut descriptions = BTreeSet::<Vec<u8>>::new();
+		let descriptions_are_unique = <pallet_chainlink_feed::Feeds<T> as IterableStorageMap<
+			T::FeedId,
+			pallet_chainlink_feed::FeedConfigOf<T>,
+		>>::iter()
+		.all(|(_, v)| descriptions.insert(v.description.into_ref().as_slice().to_vec()));
+		ensure!(descriptions_are_unique, Error::<T>::FeedDescriptionsAreNotUnique);
+
+		ensure!(
+			CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+				.into_iter()
+				.all(|currency_id| Self::get_feed_id(currency_id)
+					.map_or(false, |feed_id| <ChainlinkFeedPallet<T>>::feed(feed_id).is_some())),
+			Error::<T>::FeedNotExists
+		);
+		Ok(())
+	}
+
+	/// Checks if the feed round updated at block `updated_at` is too old to be used at block `now`.
+	fn is_round_stale(currency_id: CurrencyId, updated_at: T::BlockNumber, now: T::BlockNumber) -> bool {
+		Self::max_staleness_storage(currency_id)
+			.map_or(false, |max_staleness| now.saturating_sub(updated_at) > max_staleness)
+	}
+
+	/// Returns the FeedId bound to the underlying asset.
 	pub fn get_feed_id(currency_id: CurrencyId) -> Option<T::FeedId> {
-		Some(
-			<pallet_chainlink_feed::Feeds<T> as IterableStorageMap<
-				T::FeedId,
-				pallet_chainlink_feed::FeedConfigOf<T>,
-			>>::iter()
-			.find(|(_, v)| v.description.into_ref().as_slice() == Self::convert_to_description(currency_id))?
-			.0,
-		)
+		Self::feed_id_storage(currency_id)
+	}
+
+	/// Converts the feed answer to the price accuracy according to the feed decimals.
+	fn normalize_answer(feed_id: T::FeedId, answer: u128) -> Option<u128> {
+		match Self::feed_decimals_storage(feed_id) {
+			Some(decimals) => answer.checked_mul(10_u128.checked_pow(PRICE_DECIMALS.checked_sub(decimals)?.into())?),
+			None => Some(answer),
+		}
 	}
 }
 
-impl<T: Config> PricesManager<CurrencyId> for Pallet<T> {
-	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
-		// TODO check is feeding enabled
+impl<T: Config> Pallet<T> {
+	/// Returns the price from the latest answer of the feed bound to the currency.
+	fn get_feed_price(currency_id: CurrencyId) -> Option<Price> {
 		let feed_id = Self::get_feed_id(currency_id)?;
 		let feed_result = <ChainlinkFeedPallet<T>>::feed(feed_id)?;
-		// TODO handle updated_at
-		let RoundData { answer, .. } = feed_result.latest_data();
+		let RoundData { answer, updated_at, .. } = feed_result.latest_data();
 
 		// There is an issue that pallet-chainlink-feed can return Some(0)
 		// if feed was created but submit() extrinsic wasn't called
@@ -227,14 +723,46 @@ impl<T: Config> PricesManager<CurrencyId> for Pallet<T> {
 			return None;
 		}
 
-		Some(Price::from_inner(answer.try_into().ok()?))
+		if Self::is_round_stale(currency_id, updated_at, <frame_system::Pallet<T>>::block_number()) {
+			return None;
+		}
+
+		Some(Price::from_inner(Self::normalize_answer(
+			feed_id,
+			answer.try_into().ok()?,
+		)?))
+	}
+}
+
+impl<T: Config> PriceSource<CurrencyId> for Pallet<T> {
+	/// Get price underlying token in USD. Returns the locked price if it exists, otherwise
+	/// the latest feed answer.
+	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
+		if !Self::feeding_enabled_storage() {
+			return None;
+		}
+		Self::locked_price_storage(currency_id).or_else(|| Self::get_feed_price(currency_id))
+	}
+}
+
+impl<T: Config> PriceLocker<CurrencyId> for Pallet<T> {
+	/// Locks price at the latest feed answer if it is valid.
+	fn lock_price(currency_id: CurrencyId) {
+		if let Some(price) = Self::get_feed_price(currency_id) {
+			LockedPriceStorage::<T>::insert(currency_id, price);
+			Self::deposit_event(Event::LockPrice(currency_id, price));
+		}
 	}
 
-	// TODO These function will be removed from trait
-	fn lock_price(_currency_id: CurrencyId) {
-		unimplemented!()
+	/// Unlocks price, the latest feed answer is provided again.
+	fn unlock_price(currency_id: CurrencyId) {
+		LockedPriceStorage::<T>::remove(currency_id);
+		Self::deposit_event(Event::UnlockPrice(currency_id));
 	}
-	fn unlock_price(_currency_id: CurrencyId) {
-		unimplemented!()
+}
+
+impl<T: Config> DataProvider<CurrencyId, Price> for Pallet<T> {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		<Pallet<T> as PriceSource<CurrencyId>>::get_underlying_price(*currency_id)
 	}
 }
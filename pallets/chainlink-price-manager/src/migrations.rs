@@ -0,0 +1,71 @@
+//! Storage migrations for the chainlink price manager pallet.
+
+use super::*;
+
+/// Binds the feeds, that were looked up by description, to the underlying assets explicitly.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if StorageVersion::<T>::get() != Releases::V0 {
+		return 0;
+	}
+
+	let feeds = <pallet_chainlink_feed::Feeds<T> as IterableStorageMap<
+		T::FeedId,
+		pallet_chainlink_feed::FeedConfigOf<T>,
+	>>::iter()
+	.map(|(feed_id, feed_config)| (feed_id, feed_config.description.into_ref().as_slice().to_vec()))
+	.collect::<Vec<_>>();
+
+	let mut bound_feeds: Weight = 0;
+	for currency_id in CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset) {
+		let description = Pallet::<T>::convert_to_description(currency_id);
+		if let Some((feed_id, _)) = feeds
+			.iter()
+			.find(|(_, feed_description)| feed_description == description)
+		{
+			FeedIdStorage::<T>::insert(currency_id, feed_id);
+			bound_feeds += 1;
+		}
+	}
+	StorageVersion::<T>::put(Releases::V1);
+
+	log::info!(
+		target: "ChainlinkPriceManager",
+		"migrated to v1: {:?} feeds bound to underlying assets",
+		bound_feeds,
+	);
+
+	T::DbWeight::get().reads_writes((feeds.len() as Weight).saturating_add(1), bound_feeds.saturating_add(1))
+}
+
+/// Enables feeding, which was always enabled before the feeding flag was stored, if the feeds
+/// bound to the enabled currencies are valid.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	if StorageVersion::<T>::get() != Releases::V1 {
+		return 0;
+	}
+
+	let feeding_enabled = Pallet::<T>::check_feeds().is_ok();
+	FeedingEnabledStorage::<T>::put(feeding_enabled);
+	StorageVersion::<T>::put(Releases::V2);
+
+	log::info!(
+		target: "ChainlinkPriceManager",
+		"migrated to v2: feeding enabled: {:?}",
+		feeding_enabled,
+	);
+
+	// All feeds are read to check their descriptions, the feed binding and the feed are read for
+	// every enabled currency.
+	let feeds = <pallet_chainlink_feed::Feeds<T> as IterableStorageMap<
+		T::FeedId,
+		pallet_chainlink_feed::FeedConfigOf<T>,
+	>>::iter()
+	.count() as Weight;
+	let enabled_currencies = CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset).len() as Weight;
+	T::DbWeight::get().reads_writes(
+		feeds
+			.saturating_add(enabled_currencies.saturating_mul(2))
+			.saturating_add(1),
+		2,
+	)
+}
@@ -4,7 +4,7 @@
 
 use crate as chainlink_price_adapter;
 use frame_support::{construct_runtime, ord_parameter_types, parameter_types, PalletId};
-use frame_system::offchain::SendTransactionTypes;
+use frame_system::{offchain::SendTransactionTypes, EnsureSignedBy};
 use minterest_primitives::{Balance, ChainlinkFeedId, ChainlinkPriceValue};
 use sp_runtime::{
 	testing::{Header, TestXt, H256},
@@ -23,7 +23,7 @@ construct_runtime!(
 	{
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		System: frame_system::{Pallet, Call, Event<T>},
-		ChainlinkPriceManager: chainlink_price_adapter::{Pallet, Call, Event<T>, Storage},
+		ChainlinkPriceManager: chainlink_price_adapter::{Pallet, Call, Config<T>, Event<T>, Storage},
 		ChainlinkFeed: pallet_chainlink_feed::{Pallet, Call, Config<T>, Storage, Event<T>},
 	}
 );
@@ -65,7 +65,12 @@ impl pallet_chainlink_feed::Config for Runtime {
 pub type TransactionPriority = u64;
 ord_parameter_types! {
 	pub const LiquidityPoolsPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const ZeroAdmin: AccountId = 0;
+}
 
+parameter_types! {
+	pub const DefaultRoundInitiationPeriod: u64 = 3;
+	pub const RoundAnswerTimeout: u64 = 10;
 }
 
 /// An extrinsic type used for tests.
@@ -83,6 +88,9 @@ impl chainlink_price_adapter::Config for Runtime {
 	type Event = Event;
 	type PalletAccountId = ChainlinkPalletAccountId;
 	type UnsignedPriority = LiquidityPoolsPriority;
+	type UpdateOrigin = EnsureSignedBy<ZeroAdmin, AccountId>;
+	type DefaultRoundInitiationPeriod = DefaultRoundInitiationPeriod;
+	type RoundAnswerTimeout = RoundAnswerTimeout;
 	type ChainlinkPriceManagerWeightInfo = ();
 }
 
@@ -108,6 +116,13 @@ pub fn test_externalities() -> sp_io::TestExternalities {
 	.assimilate_storage(&mut storage)
 	.unwrap();
 
+	chainlink_price_adapter::GenesisConfig::<Runtime> {
+		feeding_enabled: false,
+		_phantom: Default::default(),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
 	let mut test_externalities = sp_io::TestExternalities::new(storage);
 	test_externalities.execute_with(|| System::set_block_number(1));
 	test_externalities
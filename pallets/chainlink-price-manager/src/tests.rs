@@ -4,15 +4,19 @@
 
 use crate::mock::*;
 use codec::Decode;
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize, unsigned::ValidateUnsigned};
 use minterest_primitives::{currency::CurrencyType::UnderlyingAsset, CurrencyId};
 use pallet_chainlink_feed::{FeedInterface, FeedOracle, RoundData};
-use pallet_traits::PricesManager;
+use pallet_traits::{PriceLocker, PriceSource};
 use sp_core::offchain::{
 	testing::{TestOffchainExt, TestTransactionPoolExt},
 	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
 };
-use sp_runtime::{FixedPointNumber, FixedU128};
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	DispatchError::BadOrigin,
+	FixedPointNumber, FixedU128,
+};
 use test_helper::{currency_mock::*, users_mock::*};
 
 fn create_default_feeds() {
@@ -34,6 +38,38 @@ fn create_default_feeds() {
 	}
 }
 
+// Binds feeds created by `create_default_feeds` to the enabled underlying assets.
+fn bind_default_feeds() {
+	for (feed_id, currency) in CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+		.into_iter()
+		.enumerate()
+	{
+		assert_ok!(ChainlinkPriceManager::bind_feed(
+			admin_origin(),
+			currency,
+			feed_id as u32,
+			None
+		));
+	}
+}
+
+fn create_btc_feed(decimals: u8) {
+	ChainlinkFeed::create_feed(
+		alice_origin(),
+		20,
+		10,
+		(10, 1_000 * DOLLARS),
+		1,
+		decimals,
+		ChainlinkPriceManager::convert_to_description(BTC).to_vec(),
+		0,
+		vec![(ORACLE, ORACLES_ADMIN)],
+		None,
+		None,
+	)
+	.unwrap();
+}
+
 #[test]
 fn offchain_worker_test() {
 	let mut ext = test_externalities();
@@ -44,6 +80,7 @@ fn offchain_worker_test() {
 	ext.register_extension(TransactionPoolExt::new(pool));
 	ext.execute_with(|| {
 		create_default_feeds();
+		bind_default_feeds();
 		assert_ok!(ChainlinkPriceManager::_offchain_worker(3));
 
 		// There are 4 pools enabled therefore must be 4 events for each pool
@@ -62,29 +99,308 @@ fn offchain_worker_test() {
 	});
 }
 
+#[test]
+fn offchain_worker_should_respect_round_initiation_period() {
+	let mut ext = test_externalities();
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, trans_pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.execute_with(|| {
+		create_default_feeds();
+		bind_default_feeds();
+		// DOT feed rounds are initiated every 2 blocks, other feeds use the default period.
+		let dot_feed_id = 0_u32;
+		assert_ok!(ChainlinkPriceManager::set_round_initiation_period(
+			admin_origin(),
+			dot_feed_id,
+			Some(2)
+		));
+
+		assert_ok!(ChainlinkPriceManager::_offchain_worker(3));
+		assert_eq!(trans_pool_state.write().transactions.drain(..).count(), 3);
+		assert_ok!(ChainlinkPriceManager::_offchain_worker(4));
+		assert_eq!(trans_pool_state.write().transactions.drain(..).count(), 1);
+
+		// The round is initiated but hasn't collected enough submissions yet.
+		assert_ok!(ChainlinkPriceManager::initiate_new_round(
+			Origin::none(),
+			dot_feed_id,
+			1
+		));
+		assert_eq!(
+			ChainlinkPriceManager::last_initiated_round_storage(dot_feed_id),
+			Some(1)
+		);
+		assert_eq!(
+			ChainlinkPriceManager::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::initiate_new_round(dot_feed_id, 1)
+			),
+			InvalidTransaction::Stale.into()
+		);
+		assert_ok!(ChainlinkPriceManager::_offchain_worker(6));
+		assert_eq!(trans_pool_state.write().transactions.drain(..).count(), 3);
+
+		// The round is answered, the next round is initiated.
+		ChainlinkFeed::submit(Origin::signed(ORACLE), dot_feed_id, 1, 42 * DOLLARS).unwrap();
+		assert_ok!(ChainlinkPriceManager::_offchain_worker(8));
+		let transaction = trans_pool_state.write().transactions.pop().unwrap();
+		assert!(trans_pool_state.read().transactions.is_empty());
+		let ex: Extrinsic = Decode::decode(&mut &*transaction).unwrap();
+		assert_eq!(
+			ex.call,
+			crate::mock::Call::ChainlinkPriceManager(crate::Call::initiate_new_round(dot_feed_id, 2))
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_should_initiate_unanswered_round_again() {
+	let mut ext = test_externalities();
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, trans_pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.execute_with(|| {
+		create_default_feeds();
+		bind_default_feeds();
+		let dot_feed_id = 0_u32;
+		System::set_block_number(3);
+		assert_ok!(ChainlinkPriceManager::initiate_new_round(
+			Origin::none(),
+			dot_feed_id,
+			1
+		));
+		assert_eq!(
+			ChainlinkPriceManager::last_round_initiation_block_storage(dot_feed_id),
+			Some(3)
+		);
+
+		// The round isn't answered within the timeout of 10 blocks.
+		assert_ok!(ChainlinkPriceManager::_offchain_worker(12));
+		assert_eq!(trans_pool_state.write().transactions.drain(..).count(), 3);
+
+		// The unanswered round is initiated again.
+		assert_ok!(ChainlinkPriceManager::_offchain_worker(15));
+		assert_eq!(trans_pool_state.read().transactions.len(), 4);
+		System::set_block_number(15);
+		assert!(ChainlinkPriceManager::validate_unsigned(
+			TransactionSource::External,
+			&crate::Call::initiate_new_round(dot_feed_id, 1)
+		)
+		.is_ok());
+		assert_ok!(ChainlinkPriceManager::initiate_new_round(
+			Origin::none(),
+			dot_feed_id,
+			1
+		));
+		assert_eq!(
+			ChainlinkPriceManager::last_round_initiation_block_storage(dot_feed_id),
+			Some(15)
+		);
+		assert_eq!(
+			ChainlinkPriceManager::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::initiate_new_round(dot_feed_id, 1)
+			),
+			InvalidTransaction::Stale.into()
+		);
+	});
+}
+
+#[test]
+fn set_round_initiation_period_should_work() {
+	test_externalities().execute_with(|| {
+		create_btc_feed(8);
+		let feed_id = 0_u32;
+		assert_ok!(ChainlinkPriceManager::set_round_initiation_period(
+			admin_origin(),
+			feed_id,
+			Some(10)
+		));
+		assert_eq!(
+			ChainlinkPriceManager::round_initiation_period_storage(feed_id),
+			Some(10)
+		);
+		let expected_event =
+			Event::ChainlinkPriceManager(crate::Event::RoundInitiationPeriodChanged(feed_id, Some(10)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_ok!(ChainlinkPriceManager::set_round_initiation_period(
+			admin_origin(),
+			feed_id,
+			None
+		));
+		assert_eq!(ChainlinkPriceManager::round_initiation_period_storage(feed_id), None);
+
+		assert_noop!(
+			ChainlinkPriceManager::set_round_initiation_period(admin_origin(), feed_id, Some(0)),
+			crate::Error::<Runtime>::ZeroRoundInitiationPeriod
+		);
+		assert_noop!(
+			ChainlinkPriceManager::set_round_initiation_period(admin_origin(), 1, Some(10)),
+			crate::Error::<Runtime>::FeedNotExists
+		);
+		assert_noop!(
+			ChainlinkPriceManager::set_round_initiation_period(alice_origin(), feed_id, Some(10)),
+			BadOrigin
+		);
+	});
+}
+
 #[test]
 fn get_feed_id() {
-	let oracle: AccountId = 100;
 	test_externalities().execute_with(|| {
-		ChainlinkFeed::create_feed(
-			alice_origin(),
-			20,
-			10,
-			(10, 1_000 * DOLLARS),
-			1,
-			5,
-			ChainlinkPriceManager::convert_to_description(BTC).to_vec(),
-			0,
-			vec![(oracle, ORACLES_ADMIN)],
-			None,
-			None,
-		)
-		.unwrap();
-		assert_eq!(ChainlinkPriceManager::get_feed_id(BTC).unwrap(), 0);
+		create_btc_feed(5);
+		assert_eq!(ChainlinkPriceManager::get_feed_id(BTC), None);
+		assert_ok!(ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 0, None));
+		assert_eq!(ChainlinkPriceManager::get_feed_id(BTC), Some(0));
 		assert_eq!(ChainlinkPriceManager::get_feed_id(DOT), None);
 	});
 }
 
+#[test]
+fn bind_feed_should_work() {
+	test_externalities().execute_with(|| {
+		create_btc_feed(5);
+		create_btc_feed(5);
+
+		assert_noop!(
+			ChainlinkPriceManager::bind_feed(alice_origin(), BTC, 0, None),
+			BadOrigin
+		);
+		assert_noop!(
+			ChainlinkPriceManager::bind_feed(admin_origin(), MBTC, 0, None),
+			crate::Error::<Runtime>::NotValidUnderlyingAssetId
+		);
+		assert_noop!(
+			ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 2, None),
+			crate::Error::<Runtime>::FeedNotExists
+		);
+		assert_noop!(
+			ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 0, Some(19)),
+			crate::Error::<Runtime>::NotValidFeedDecimals
+		);
+
+		assert_ok!(ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 0, Some(8)));
+		assert_eq!(ChainlinkPriceManager::feed_id_storage(BTC), Some(0));
+		assert_eq!(ChainlinkPriceManager::feed_decimals_storage(0), Some(8));
+		let expected_event = Event::ChainlinkPriceManager(crate::Event::FeedBound(BTC, 0, Some(8)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_noop!(
+			ChainlinkPriceManager::bind_feed(admin_origin(), DOT, 0, None),
+			crate::Error::<Runtime>::FeedAlreadyBound
+		);
+
+		// The feed bound to the currency is replaced.
+		assert_ok!(ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 1, None));
+		assert_eq!(ChainlinkPriceManager::feed_id_storage(BTC), Some(1));
+		assert_eq!(ChainlinkPriceManager::feed_decimals_storage(0), None);
+		assert_eq!(ChainlinkPriceManager::feed_decimals_storage(1), None);
+	});
+}
+
+#[test]
+fn unbind_feed_should_work() {
+	test_externalities().execute_with(|| {
+		create_default_feeds();
+		bind_default_feeds();
+		assert_ok!(ChainlinkPriceManager::enable_feeding(admin_origin()));
+
+		assert_noop!(ChainlinkPriceManager::unbind_feed(alice_origin(), BTC), BadOrigin);
+		assert_noop!(
+			ChainlinkPriceManager::unbind_feed(admin_origin(), BTC),
+			crate::Error::<Runtime>::FeedingIsEnabled
+		);
+
+		assert_ok!(ChainlinkPriceManager::disable_feeding(admin_origin()));
+		assert_ok!(ChainlinkPriceManager::unbind_feed(admin_origin(), BTC));
+		assert_eq!(ChainlinkPriceManager::feed_id_storage(BTC), None);
+		let expected_event = Event::ChainlinkPriceManager(crate::Event::FeedUnbound(BTC, 2));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_noop!(
+			ChainlinkPriceManager::unbind_feed(admin_origin(), BTC),
+			crate::Error::<Runtime>::FeedNotBound
+		);
+		assert_noop!(
+			ChainlinkPriceManager::enable_feeding(admin_origin()),
+			crate::Error::<Runtime>::FeedNotExists
+		);
+	});
+}
+
+#[test]
+fn feed_answer_should_be_normalized() {
+	test_externalities().execute_with(|| {
+		create_btc_feed(8);
+		crate::FeedingEnabledStorage::<Runtime>::put(true);
+		assert_ok!(ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 0, Some(8)));
+
+		// 42.5 with 8 decimals.
+		ChainlinkFeed::submit(Origin::signed(ORACLE), 0_u32, 1, 4_250_000_000).unwrap();
+		assert_eq!(
+			ChainlinkPriceManager::get_underlying_price(BTC),
+			Some(FixedU128::saturating_from_rational(425, 10))
+		);
+	});
+}
+
+#[test]
+fn migrate_to_v1_should_bind_feeds_by_description() {
+	test_externalities().execute_with(|| {
+		create_default_feeds();
+		// The storage of a chain created before the feeds were bound.
+		crate::StorageVersion::<Runtime>::put(crate::Releases::V0);
+
+		assert!(crate::migrations::migrate_to_v1::<Runtime>() > 0);
+		assert_eq!(crate::StorageVersion::<Runtime>::get(), crate::Releases::V1);
+		for (feed_id, currency) in CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.enumerate()
+		{
+			assert_eq!(ChainlinkPriceManager::feed_id_storage(currency), Some(feed_id as u32));
+		}
+
+		// The migration is applied only once.
+		assert_eq!(crate::migrations::migrate_to_v1::<Runtime>(), 0);
+	});
+}
+
+#[test]
+fn migrate_to_v2_should_enable_feeding_with_valid_feeds() {
+	test_externalities().execute_with(|| {
+		// The storage of a chain created before the feeding flag was stored.
+		crate::StorageVersion::<Runtime>::put(crate::Releases::V1);
+		crate::FeedingEnabledStorage::<Runtime>::kill();
+		create_default_feeds();
+		bind_default_feeds();
+
+		assert!(crate::migrations::migrate_to_v2::<Runtime>() > 0);
+		assert_eq!(crate::StorageVersion::<Runtime>::get(), crate::Releases::V2);
+		assert!(ChainlinkPriceManager::feeding_enabled_storage());
+
+		// The migration is applied only once.
+		assert_eq!(crate::migrations::migrate_to_v2::<Runtime>(), 0);
+	});
+}
+
+#[test]
+fn migrate_to_v2_should_not_enable_feeding_without_feeds() {
+	test_externalities().execute_with(|| {
+		crate::StorageVersion::<Runtime>::put(crate::Releases::V1);
+		crate::FeedingEnabledStorage::<Runtime>::kill();
+
+		assert!(crate::migrations::migrate_to_v2::<Runtime>() > 0);
+		assert_eq!(crate::StorageVersion::<Runtime>::get(), crate::Releases::V2);
+		assert!(!ChainlinkPriceManager::feeding_enabled_storage());
+	});
+}
+
 #[test]
 fn create_feed_should_work() {
 	let oracle1: AccountId = 100;
@@ -110,6 +426,9 @@ fn create_feed_should_work() {
 		)
 		.unwrap();
 
+		crate::FeedingEnabledStorage::<Runtime>::put(true);
+		assert_ok!(ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 0, None));
+
 		let feed_id = 0_u32;
 		let feed_created = Event::ChainlinkFeed(pallet_chainlink_feed::Event::FeedCreated(feed_id, ALICE));
 		assert!(System::events().iter().any(|record| record.event == feed_created));
@@ -133,3 +452,176 @@ fn create_feed_should_work() {
 		assert_eq!(ChainlinkPriceManager::get_underlying_price(DOT), None);
 	});
 }
+
+#[test]
+fn set_max_staleness_should_work() {
+	test_externalities().execute_with(|| {
+		assert_ok!(ChainlinkPriceManager::set_max_staleness(admin_origin(), BTC, Some(10)));
+		assert_eq!(ChainlinkPriceManager::max_staleness_storage(BTC), Some(10));
+		let expected_event = Event::ChainlinkPriceManager(crate::Event::MaxStalenessChanged(BTC, Some(10)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_ok!(ChainlinkPriceManager::set_max_staleness(admin_origin(), BTC, None));
+		assert_eq!(ChainlinkPriceManager::max_staleness_storage(BTC), None);
+
+		assert_noop!(
+			ChainlinkPriceManager::set_max_staleness(admin_origin(), BTC, Some(0)),
+			crate::Error::<Runtime>::ZeroMaxStaleness
+		);
+		assert_noop!(
+			ChainlinkPriceManager::set_max_staleness(admin_origin(), MBTC, Some(10)),
+			crate::Error::<Runtime>::NotValidUnderlyingAssetId
+		);
+		assert_noop!(
+			ChainlinkPriceManager::set_max_staleness(alice_origin(), BTC, Some(10)),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn stale_price_should_not_be_returned() {
+	test_externalities().execute_with(|| {
+		create_btc_feed(5);
+		crate::FeedingEnabledStorage::<Runtime>::put(true);
+		assert_ok!(ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 0, None));
+		let feed_id = 0_u32;
+
+		// Round is updated at block 1.
+		ChainlinkFeed::submit(Origin::signed(ORACLE), feed_id, 1, 42 * DOLLARS).unwrap();
+		assert_ok!(ChainlinkPriceManager::set_max_staleness(admin_origin(), BTC, Some(10)));
+
+		System::set_block_number(11);
+		ChainlinkPriceManager::on_initialize(11);
+		assert_eq!(
+			ChainlinkPriceManager::get_underlying_price(BTC),
+			Some(FixedU128::saturating_from_integer(42))
+		);
+		assert_eq!(ChainlinkPriceManager::stale_feeds_storage(BTC), None);
+
+		// The feed goes stale.
+		System::set_block_number(12);
+		ChainlinkPriceManager::on_initialize(12);
+		assert_eq!(ChainlinkPriceManager::get_underlying_price(BTC), None);
+		assert_eq!(ChainlinkPriceManager::stale_feeds_storage(BTC), Some(1));
+		let expected_event = Event::ChainlinkPriceManager(crate::Event::FeedIsStale(BTC, feed_id, 1));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The feed is updated with a new round.
+		ChainlinkFeed::submit(Origin::signed(ORACLE), feed_id, 2, 43 * DOLLARS).unwrap();
+		System::set_block_number(13);
+		ChainlinkPriceManager::on_initialize(13);
+		assert_eq!(
+			ChainlinkPriceManager::get_underlying_price(BTC),
+			Some(FixedU128::saturating_from_integer(43))
+		);
+		assert_eq!(ChainlinkPriceManager::stale_feeds_storage(BTC), None);
+		let expected_event = Event::ChainlinkPriceManager(crate::Event::FeedIsUpdated(BTC, feed_id));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+#[test]
+fn enable_feeding_should_work() {
+	test_externalities().execute_with(|| {
+		assert_noop!(ChainlinkPriceManager::enable_feeding(alice_origin()), BadOrigin);
+
+		// Feeds aren't bound to the enabled currencies.
+		create_btc_feed(5);
+		assert_noop!(
+			ChainlinkPriceManager::enable_feeding(admin_origin()),
+			crate::Error::<Runtime>::FeedNotExists
+		);
+
+		// There are two feeds with the BTC description.
+		create_default_feeds();
+		assert_noop!(
+			ChainlinkPriceManager::enable_feeding(admin_origin()),
+			crate::Error::<Runtime>::FeedDescriptionsAreNotUnique
+		);
+	});
+
+	test_externalities().execute_with(|| {
+		create_default_feeds();
+		bind_default_feeds();
+		// The feed with id 0 is the DOT feed.
+		ChainlinkFeed::submit(Origin::signed(ORACLE), 0_u32, 1, 42 * DOLLARS).unwrap();
+		assert_eq!(ChainlinkPriceManager::get_underlying_price(DOT), None);
+
+		assert_ok!(ChainlinkPriceManager::enable_feeding(admin_origin()));
+		assert!(ChainlinkPriceManager::feeding_enabled_storage());
+		let expected_event = Event::ChainlinkPriceManager(crate::Event::FeedingEnabled);
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(
+			ChainlinkPriceManager::get_underlying_price(DOT),
+			Some(FixedU128::saturating_from_integer(42))
+		);
+
+		assert_noop!(
+			ChainlinkPriceManager::enable_feeding(admin_origin()),
+			crate::Error::<Runtime>::FeedingAlreadyEnabled
+		);
+	});
+}
+
+#[test]
+fn disable_feeding_should_work() {
+	test_externalities().execute_with(|| {
+		create_default_feeds();
+		bind_default_feeds();
+		// The feed with id 0 is the DOT feed.
+		ChainlinkFeed::submit(Origin::signed(ORACLE), 0_u32, 1, 42 * DOLLARS).unwrap();
+		assert_noop!(
+			ChainlinkPriceManager::disable_feeding(admin_origin()),
+			crate::Error::<Runtime>::FeedingAlreadyDisabled
+		);
+		assert_ok!(ChainlinkPriceManager::enable_feeding(admin_origin()));
+
+		assert_noop!(ChainlinkPriceManager::disable_feeding(alice_origin()), BadOrigin);
+		assert_ok!(ChainlinkPriceManager::disable_feeding(admin_origin()));
+		assert!(!ChainlinkPriceManager::feeding_enabled_storage());
+		let expected_event = Event::ChainlinkPriceManager(crate::Event::FeedingDisabled);
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(ChainlinkPriceManager::get_underlying_price(DOT), None);
+	});
+}
+
+#[test]
+fn lock_price_should_work() {
+	test_externalities().execute_with(|| {
+		create_btc_feed(18);
+		crate::FeedingEnabledStorage::<Runtime>::put(true);
+		assert_ok!(ChainlinkPriceManager::bind_feed(admin_origin(), BTC, 0, None));
+		let feed_id = 0_u32;
+
+		// There is no valid feed answer, the price isn't locked.
+		ChainlinkPriceManager::lock_price(BTC);
+		assert_eq!(ChainlinkPriceManager::locked_price_storage(BTC), None);
+
+		ChainlinkFeed::submit(Origin::signed(ORACLE), feed_id, 1, 42 * DOLLARS).unwrap();
+		ChainlinkPriceManager::lock_price(BTC);
+		assert_eq!(
+			ChainlinkPriceManager::locked_price_storage(BTC),
+			Some(FixedU128::saturating_from_integer(42))
+		);
+		let expected_event =
+			Event::ChainlinkPriceManager(crate::Event::LockPrice(BTC, FixedU128::saturating_from_integer(42)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The locked price is provided instead of the new feed answer.
+		ChainlinkFeed::submit(Origin::signed(ORACLE), feed_id, 2, 43 * DOLLARS).unwrap();
+		assert_eq!(
+			ChainlinkPriceManager::get_underlying_price(BTC),
+			Some(FixedU128::saturating_from_integer(42))
+		);
+
+		ChainlinkPriceManager::unlock_price(BTC);
+		assert_eq!(ChainlinkPriceManager::locked_price_storage(BTC), None);
+		assert_eq!(
+			ChainlinkPriceManager::get_underlying_price(BTC),
+			Some(FixedU128::saturating_from_integer(43))
+		);
+		let expected_event = Event::ChainlinkPriceManager(crate::Event::UnlockPrice(BTC));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
@@ -45,34 +45,86 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for chainlink_price_manager.
 pub trait WeightInfo {
+	fn bind_feed() -> Weight;
 	fn disable_feeding() -> Weight;
 	fn enable_feeding() -> Weight;
 	fn initiate_new_round() -> Weight;
+	fn set_max_staleness() -> Weight;
+	fn set_round_initiation_period() -> Weight;
+	fn unbind_feed() -> Weight;
 }
 
 /// Weights for chainlink_price_manager using the Minterest node and recommended hardware.
 pub struct MinterestWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
+	fn bind_feed() -> Weight {
+		(41_307_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
 	fn disable_feeding() -> Weight {
-		(1_072_000 as Weight)
+		(14_872_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn enable_feeding() -> Weight {
-		(1_092_000 as Weight)
+		(38_615_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn initiate_new_round() -> Weight {
-		(15_218_000 as Weight)
+		(16_904_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_staleness() -> Weight {
+		(22_147_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_round_initiation_period() -> Weight {
+		(24_632_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn unbind_feed() -> Weight {
+		(27_518_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	fn bind_feed() -> Weight {
+		(41_307_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
 	fn disable_feeding() -> Weight {
-		(1_072_000 as Weight)
+		(14_872_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	fn enable_feeding() -> Weight {
-		(1_092_000 as Weight)
+		(38_615_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	fn initiate_new_round() -> Weight {
-		(15_218_000 as Weight)
+		(16_904_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_staleness() -> Weight {
+		(22_147_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_round_initiation_period() -> Weight {
+		(24_632_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unbind_feed() -> Weight {
+		(27_518_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 }
@@ -11,7 +11,7 @@
 #![allow(clippy::unnecessary_mut_passed)]
 
 use codec::{Codec, Decode, Encode};
-use minterest_primitives::{Amount, Balance, CurrencyId, Interest, Rate};
+use minterest_primitives::{Amount, Balance, BlockNumber, CurrencyId, Interest, Rate};
 use sp_core::RuntimeDebug;
 use sp_std::prelude::*;
 
@@ -70,6 +70,44 @@ pub struct ProtocolTotalValue {
 	pub pool_total_protocol_interest_in_usd: Balance,
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+pub struct PoolSolvencyData {
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub pool_supply_underlying: Balance,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub pool_borrow_underlying: Balance,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub pool_protocol_interest: Balance,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub pool_bad_debt: Balance,
+	pub exchange_rate: Rate,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+pub struct PoolCheckpointData {
+	pub block_number: BlockNumber,
+	pub exchange_rate: Rate,
+	pub borrow_index: Rate,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct WrappedBalanceData {
+	pub currency_id: CurrencyId,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub wrapped_amount: Balance,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub underlying_amount: Balance,
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
 pub struct UserData {
@@ -116,6 +154,10 @@ sp_api::decl_runtime_apis! {
 
 		fn liquidity_pool_state(pool_id: CurrencyId) -> Option<PoolState>;
 
+		fn get_pool_solvency_data(pool_id: CurrencyId) -> Option<PoolSolvencyData>;
+
+		fn get_pool_history(pool_id: CurrencyId, from: BlockNumber, to: BlockNumber) -> Option<Vec<PoolCheckpointData>>;
+
 		fn get_pool_utilization_rate(pool_id: CurrencyId) -> Option<Rate>;
 
 		fn get_user_total_supply_and_borrow_balance_in_usd(account_id: AccountId) -> Option<UserPoolBalanceData>;
@@ -136,6 +178,8 @@ sp_api::decl_runtime_apis! {
 			pool_id: CurrencyId,
 		) -> Option<BalanceInfo>;
 
+		fn get_user_wrapped_balances(account_id: AccountId) -> Option<Vec<WrappedBalanceData>>;
+
 		fn pool_exists(underlying_asset_id: CurrencyId) -> bool;
 
 		fn get_user_total_supply_borrow_and_net_apy(account_id: AccountId) -> Option<(Interest, Interest, Interest)>;
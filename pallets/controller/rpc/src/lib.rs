@@ -3,11 +3,12 @@
 use codec::Codec;
 use controller_rpc_runtime_api::UserData;
 pub use controller_rpc_runtime_api::{
-	BalanceInfo, ControllerRuntimeApi, HypotheticalLiquidityData, PoolState, ProtocolTotalValue, UserPoolBalanceData,
+	BalanceInfo, ControllerRuntimeApi, HypotheticalLiquidityData, PoolCheckpointData, PoolSolvencyData, PoolState,
+	ProtocolTotalValue, UserPoolBalanceData, WrappedBalanceData,
 };
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use minterest_primitives::{CurrencyId, Interest, Rate};
+use minterest_primitives::{BlockNumber, CurrencyId, Interest, Rate};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
@@ -59,6 +60,56 @@ pub trait ControllerRpcApi<BlockHash, AccountId> {
 	#[rpc(name = "controller_liquidityPoolState")]
 	fn liquidity_pool_state(&self, pool_id: CurrencyId, at: Option<BlockHash>) -> Result<Option<PoolState>>;
 
+	/// Returns the data required to audit the liquidity pool solvency.
+	///
+	/// Parameters:
+	///  - `&self`: Self reference
+	///  - `pool_id`: target pool id.
+	///  - `at`: Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	/// Return:
+	/// - [`pool_supply_underlying`](`PoolSolvencyData::pool_supply_underlying`): current available
+	/// liquidity in the pool.
+	/// - [`pool_borrow_underlying`](`PoolSolvencyData::pool_borrow_underlying`): total borrowed
+	/// including interest in the pool.
+	/// - [`pool_protocol_interest`](`PoolSolvencyData::pool_protocol_interest`): protocol interest
+	/// held in the pool.
+	/// - [`pool_bad_debt`](`PoolSolvencyData::pool_bad_debt`): total bad debt written off in the
+	/// pool since its creation.
+	/// - [`exchange_rate`](`PoolSolvencyData::exchange_rate`): the Exchange Rate between an mToken
+	/// and the underlying asset.
+	#[doc(alias = "MNT RPC")]
+	#[doc(alias = "MNT controller")]
+	#[rpc(name = "controller_poolSolvency")]
+	fn get_pool_solvency_data(&self, pool_id: CurrencyId, at: Option<BlockHash>) -> Result<Option<PoolSolvencyData>>;
+
+	/// Returns the historical series of the exchange rate and the borrow index of the pool.
+	///
+	/// Parameters:
+	///  - `&self`: Self reference
+	///  - `pool_id`: target pool id.
+	///  - `from`: first block number of the requested range.
+	///  - `to`: last block number of the requested range.
+	///  - `at`: Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	/// Return: vector of checkpoints written in the range `[from, to]`, sorted by block number.
+	/// - [`block_number`](`PoolCheckpointData::block_number`): block number at which the
+	/// checkpoint was written.
+	/// - [`exchange_rate`](`PoolCheckpointData::exchange_rate`): the Exchange Rate between an
+	/// mToken and the underlying asset.
+	/// - [`borrow_index`](`PoolCheckpointData::borrow_index`): accumulator of the total earned
+	/// interest rate since the opening of the pool.
+	#[doc(alias = "MNT RPC")]
+	#[doc(alias = "MNT controller")]
+	#[rpc(name = "controller_getPoolHistory")]
+	fn get_pool_history(
+		&self,
+		pool_id: CurrencyId,
+		from: BlockNumber,
+		to: BlockNumber,
+		at: Option<BlockHash>,
+	) -> Result<Option<Vec<PoolCheckpointData>>>;
+
 	/// Returns utilization rate based on pool parameters calculated for current block.
 	///
 	/// Parameters:
@@ -182,6 +233,29 @@ pub trait ControllerRpcApi<BlockHash, AccountId> {
 		at: Option<BlockHash>,
 	) -> Result<Option<BalanceInfo>>;
 
+	/// Returns user balances of all wrapped tokens converted into underlying assets using the
+	/// current exchange rates.
+	///
+	/// Parameters:
+	///  - `&self`: Self reference
+	///  - `account_id`: current account id.
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	///  Return: vector of balances for every wrapped token with created pool.
+	///  - [`currency_id`](`WrappedBalanceData::currency_id`): wrapped token id.
+	///  - [`wrapped_amount`](`WrappedBalanceData::wrapped_amount`): user balance of the wrapped
+	/// token.
+	///  - [`underlying_amount`](`WrappedBalanceData::underlying_amount`): user balance of the
+	/// wrapped token converted into underlying assets.
+	#[doc(alias = "MNT RPC")]
+	#[doc(alias = "MNT controller")]
+	#[rpc(name = "controller_getUserWrappedBalances")]
+	fn get_user_wrapped_balances(
+		&self,
+		account_id: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Vec<WrappedBalanceData>>>;
+
 	/// Checks whether the pool is created in storage.
 	///
 	/// Parameters:
@@ -334,6 +408,40 @@ where
 		})
 	}
 
+	fn get_pool_solvency_data(
+		&self,
+		pool_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PoolSolvencyData>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		api.get_pool_solvency_data(&at, pool_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get pool solvency data.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pool_history(
+		&self,
+		pool_id: CurrencyId,
+		from: BlockNumber,
+		to: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Vec<PoolCheckpointData>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		api.get_pool_history(&at, pool_id, from, to).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get pool history.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
 	fn get_pool_utilization_rate(
 		&self,
 		pool_id: CurrencyId,
@@ -448,6 +556,22 @@ where
 			})
 	}
 
+	fn get_user_wrapped_balances(
+		&self,
+		account_id: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Vec<WrappedBalanceData>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		api.get_user_wrapped_balances(&at, account_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get user wrapped balances.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
 	fn pool_exists(&self, underlying_asset_id: CurrencyId, at: Option<<Block as BlockT>::Hash>) -> Result<bool> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
@@ -20,17 +20,17 @@ use minterest_primitives::{
 	constants::time::BLOCKS_PER_YEAR,
 	currency::CurrencyType::{UnderlyingAsset, WrappedToken},
 };
-use minterest_primitives::{Balance, CurrencyId, Interest, Operation, Rate};
+use minterest_primitives::{Balance, CurrencyId, Interest, Operation, Price, Rate};
 pub use module::*;
 use orml_traits::MultiCurrency;
 use pallet_traits::{
 	ControllerManager, CurrencyConverter, LiquidityPoolStorageProvider, MinterestModelManager, MntManager,
-	PoolsManager, PricesManager, UserCollateral, UserStorageProvider,
+	PoolOperationPauser, PoolsManager, PriceSource, UserCollateral, UserStorageProvider,
 };
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, Zero},
+	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, UniqueSaturatedInto, Zero},
 	DispatchError, DispatchResult, FixedPointNumber, FixedU128, RuntimeDebug,
 };
 use sp_std::{cmp::Ordering, collections::btree_set::BTreeSet, convert::TryInto, prelude::Vec, result};
@@ -68,6 +68,20 @@ pub struct ControllerData<BlockNumber> {
 	pub protocol_interest_threshold: Balance,
 }
 
+/// Historical checkpoint of the pool state.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct PoolCheckpoint<BlockNumber> {
+	/// Block number at which the checkpoint was written.
+	pub block_number: BlockNumber,
+
+	/// The exchange rate between a wrapped token and the underlying asset.
+	pub exchange_rate: Rate,
+
+	/// Accumulator of the total earned interest rate since the opening of the pool.
+	pub borrow_index: Rate,
+}
+
 /// The Root or half MinterestCouncil can pause certain actions as a safety mechanism.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Default)]
@@ -129,7 +143,7 @@ pub mod module {
 		type MultiCurrency: MultiCurrency<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
 
 		/// The price source of currencies
-		type PriceSource: PricesManager<CurrencyId>;
+		type PriceSource: PriceSource<CurrencyId>;
 
 		/// Provides the basic liquidity pools manager and liquidity pool functionality.
 		type LiquidityPoolsManager: LiquidityPoolStorageProvider<Self::AccountId, PoolData>
@@ -154,6 +168,16 @@ pub mod module {
 
 		/// Provides MNT token distribution functionality.
 		type MntManager: MntManager<Self::AccountId>;
+
+		#[pallet::constant]
+		/// The minimum number of blocks between two pool history checkpoints.
+		/// Zero value disables the pool history.
+		type PoolHistoryCheckpointInterval: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The maximum number of checkpoints stored per pool. The oldest checkpoints are
+		/// overwritten by the new ones.
+		type PoolHistoryMaxLength: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -261,6 +285,26 @@ pub mod module {
 	pub(crate) type PauseKeeperStorage<T: Config> =
 		StorageMap<_, Twox64Concat, CurrencyId, PauseKeeper, ValueQuery, GetAllPaused>;
 
+	/// Ring buffer of the pool state checkpoints: (block_number, exchange_rate, borrow_index).
+	/// Checkpoints are written by `accrue_interest_rate` not more often than once per
+	/// `PoolHistoryCheckpointInterval` blocks. The slot of the checkpoint is calculated as
+	/// `(block_number / PoolHistoryCheckpointInterval) % PoolHistoryMaxLength`.
+	///
+	/// Return:
+	/// - `block_number`: block number at which the checkpoint was written.
+	/// - `exchange_rate`: the exchange rate between a wrapped token and the underlying asset.
+	/// - `borrow_index`: accumulator of the total earned interest rate since the opening of the
+	/// pool.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=controller::module::Pallet::pool_history_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT controller")]
+	#[pallet::storage]
+	#[pallet::getter(fn pool_history_storage)]
+	pub(crate) type PoolHistoryStorage<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, u32, PoolCheckpoint<T::BlockNumber>, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		#[allow(clippy::type_complexity)]
@@ -296,7 +340,22 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Accrues interest in all created pools once per block. All subsequent calls of
+		/// `accrue_interest_rate` within the same block are short-circuited.
+		fn on_initialize(_block_number: T::BlockNumber) -> Weight {
+			CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+				.into_iter()
+				.filter(|pool_id| T::LiquidityPoolsManager::pool_exists(pool_id))
+				.for_each(|pool_id| {
+					// In case of an error, the pool state is not changed. The error is returned to
+					// the caller of the next operation with this pool.
+					let _ = Self::accrue_interest_rate(pool_id);
+				});
+
+			T::ControllerWeightInfo::on_initialize()
+		}
+	}
 
 	// Admin functions
 	#[pallet::call]
@@ -324,15 +383,7 @@ pub mod module {
 				Error::<T>::PoolNotFound
 			);
 
-			PauseKeeperStorage::<T>::mutate(pool_id, |pool| match operation {
-				Operation::Deposit => pool.deposit_paused = true,
-				Operation::Redeem => pool.redeem_paused = true,
-				Operation::Borrow => pool.borrow_paused = true,
-				Operation::Repay => pool.repay_paused = true,
-				Operation::Transfer => pool.transfer_paused = true,
-			});
-
-			Self::deposit_event(Event::OperationIsPaused(pool_id, operation));
+			Self::do_pause_operation(pool_id, operation);
 			Ok(().into())
 		}
 
@@ -359,15 +410,7 @@ pub mod module {
 				Error::<T>::PoolNotFound
 			);
 
-			PauseKeeperStorage::<T>::mutate(pool_id, |pool| match operation {
-				Operation::Deposit => pool.deposit_paused = false,
-				Operation::Redeem => pool.redeem_paused = false,
-				Operation::Borrow => pool.borrow_paused = false,
-				Operation::Repay => pool.repay_paused = false,
-				Operation::Transfer => pool.transfer_paused = false,
-			});
-
-			Self::deposit_event(Event::OperationIsUnPaused(pool_id, operation));
+			Self::do_resume_operation(pool_id, operation);
 			Ok(().into())
 		}
 
@@ -537,7 +580,7 @@ impl<T: Config> Pallet<T> {
 	/// Return true if pool borrow underlying will exceed borrow cap, otherwise false.
 	fn is_borrow_cap_reached(pool_id: CurrencyId, borrow_amount: Balance) -> Result<bool, DispatchError> {
 		if let Some(borrow_cap) = Self::controller_data_storage(pool_id).borrow_cap {
-			let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+			let oracle_price = Self::get_borrow_price(pool_id)?;
 			let pool_borrow_underlying = T::LiquidityPoolsManager::get_pool_borrow_underlying(pool_id);
 
 			// new_borrow_balance_in_usd = (pool_borrow_underlying + borrow_amount) * oracle_price
@@ -650,6 +693,102 @@ impl<T: Config> Pallet<T> {
 		Ok(interest_factor)
 	}
 
+	/// Writes the pool state checkpoint into the history ring buffer. Only one checkpoint is
+	/// written per `PoolHistoryCheckpointInterval` blocks.
+	///
+	/// - `pool_id`: PoolID for which the checkpoint is written.
+	/// - `current_block_number`: Current block number.
+	/// - `borrow_index`: Pool borrow index calculated for the current block.
+	fn write_pool_checkpoint(pool_id: CurrencyId, current_block_number: T::BlockNumber, borrow_index: Rate) {
+		let checkpoint_interval = T::PoolHistoryCheckpointInterval::get();
+		let history_max_length = T::PoolHistoryMaxLength::get();
+		if checkpoint_interval.is_zero() || history_max_length.is_zero() {
+			return;
+		}
+
+		let checkpoint_period = current_block_number / checkpoint_interval;
+		let slot: u32 = (checkpoint_period % T::BlockNumber::from(history_max_length)).unique_saturated_into();
+
+		// The checkpoint for the current period has already been written.
+		if let Some(checkpoint) = Self::pool_history_storage(pool_id, slot) {
+			if checkpoint.block_number / checkpoint_interval == checkpoint_period {
+				return;
+			}
+		}
+
+		if let Ok(exchange_rate) = T::LiquidityPoolsManager::get_exchange_rate(pool_id) {
+			PoolHistoryStorage::<T>::insert(
+				pool_id,
+				slot,
+				PoolCheckpoint {
+					block_number: current_block_number,
+					exchange_rate,
+					borrow_index,
+				},
+			);
+		}
+	}
+
+	/// Returns pool state checkpoints written in the range of blocks `[from, to]`, sorted by
+	/// block number.
+	///
+	/// - `pool_id`: PoolID for which the history is requested.
+	/// - `from`: first block number of the range.
+	/// - `to`: last block number of the range.
+	pub fn get_pool_history(
+		pool_id: CurrencyId,
+		from: T::BlockNumber,
+		to: T::BlockNumber,
+	) -> Vec<PoolCheckpoint<T::BlockNumber>> {
+		let mut history: Vec<PoolCheckpoint<T::BlockNumber>> = PoolHistoryStorage::<T>::iter_prefix_values(pool_id)
+			.filter(|checkpoint| checkpoint.block_number >= from && checkpoint.block_number <= to)
+			.collect();
+		history.sort_by(|x, y| x.block_number.cmp(&y.block_number));
+		history
+	}
+
+	/// Returns the price at which the collateral is valued: min(spot, TWAP).
+	/// The spot price is used if the TWAP isn't available.
+	fn get_collateral_price(pool_id: CurrencyId) -> result::Result<Price, DispatchError> {
+		let spot_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		Ok(T::PriceSource::get_underlying_twap_price(pool_id)
+			.map_or(spot_price, |twap_price| spot_price.min(twap_price)))
+	}
+
+	/// Returns the price at which the borrows are valued: max(spot, TWAP).
+	/// The spot price is used if the TWAP isn't available.
+	fn get_borrow_price(pool_id: CurrencyId) -> result::Result<Price, DispatchError> {
+		let spot_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		Ok(T::PriceSource::get_underlying_twap_price(pool_id)
+			.map_or(spot_price, |twap_price| spot_price.max(twap_price)))
+	}
+
+	/// Pauses the operation with the pool and emits the event.
+	fn do_pause_operation(pool_id: CurrencyId, operation: Operation) {
+		PauseKeeperStorage::<T>::mutate(pool_id, |pool| match operation {
+			Operation::Deposit => pool.deposit_paused = true,
+			Operation::Redeem => pool.redeem_paused = true,
+			Operation::Borrow => pool.borrow_paused = true,
+			Operation::Repay => pool.repay_paused = true,
+			Operation::Transfer => pool.transfer_paused = true,
+		});
+
+		Self::deposit_event(Event::OperationIsPaused(pool_id, operation));
+	}
+
+	/// Resumes the operation with the pool and emits the event.
+	fn do_resume_operation(pool_id: CurrencyId, operation: Operation) {
+		PauseKeeperStorage::<T>::mutate(pool_id, |pool| match operation {
+			Operation::Deposit => pool.deposit_paused = false,
+			Operation::Redeem => pool.redeem_paused = false,
+			Operation::Borrow => pool.borrow_paused = false,
+			Operation::Repay => pool.repay_paused = false,
+			Operation::Transfer => pool.transfer_paused = false,
+		});
+
+		Self::deposit_event(Event::OperationIsUnPaused(pool_id, operation));
+	}
+
 	fn is_valid_max_borrow_rate(max_borrow_rate: Rate) -> bool {
 		!max_borrow_rate.is_zero()
 	}
@@ -666,6 +805,27 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> PoolOperationPauser for Pallet<T> {
+	/// Pauses the operation with the existing pool.
+	fn pause_operation(pool_id: CurrencyId, operation: Operation) {
+		if T::LiquidityPoolsManager::pool_exists(&pool_id) {
+			Self::do_pause_operation(pool_id, operation);
+		}
+	}
+
+	/// Resumes the operation with the existing pool.
+	fn resume_operation(pool_id: CurrencyId, operation: Operation) {
+		if T::LiquidityPoolsManager::pool_exists(&pool_id) {
+			Self::do_resume_operation(pool_id, operation);
+		}
+	}
+
+	/// Checks if the operation with the pool is paused.
+	fn is_operation_paused(pool_id: CurrencyId, operation: Operation) -> bool {
+		!<Self as ControllerManager<T::AccountId>>::is_operation_allowed(pool_id, operation)
+	}
+}
+
 impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 	/// This is a part of a pool creation flow
 	/// Creates storage records for ControllerParams and PauseKeepers
@@ -758,15 +918,16 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 			let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(underlying_asset)?;
 			let collateral_factor = Self::controller_data_storage(underlying_asset).collateral_factor;
 
-			// Get the normalized price of the asset.
-			let oracle_price =
-				T::PriceSource::get_underlying_price(underlying_asset).ok_or(Error::<T>::InvalidFeedPrice)?;
+			// Get the normalized prices of the asset: collateral is valued at min(spot, TWAP),
+			// borrows are valued at max(spot, TWAP).
+			let collateral_price = Self::get_collateral_price(underlying_asset)?;
+			let oracle_price = Self::get_borrow_price(underlying_asset)?;
 
 			// Pre-compute a conversion factor from tokens -> dollars (normalized price value)
-			// tokens_to_denom = collateral_factor * exchange_rate * oracle_price
+			// tokens_to_denom = collateral_factor * exchange_rate * collateral_price
 			let tokens_to_denom = collateral_factor
 				.checked_mul(&exchange_rate)
-				.and_then(|v| v.checked_mul(&oracle_price))
+				.and_then(|v| v.checked_mul(&collateral_price))
 				.ok_or(Error::<T>::NumOverflow)?;
 
 			if T::LiquidityPoolsManager::is_pool_collateral(&account, underlying_asset) {
@@ -894,6 +1055,7 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 				protocol_interest: updated_pool_protocol_interest,
 			},
 		);
+		Self::write_pool_checkpoint(underlying_asset, current_block_number, updated_borrow_index);
 		Ok(())
 	}
 
@@ -1022,6 +1184,27 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 		Some((exchange_rate, borrow_rate, supply_rate))
 	}
 
+	/// Gets the pool supply, borrow, protocol interest, written off bad debt and exchange rate.
+	/// The values are calculated for the current block.
+	fn get_pool_solvency_data(pool_id: CurrencyId) -> Option<(Balance, Balance, Balance, Balance, Rate)> {
+		if !T::LiquidityPoolsManager::pool_exists(&pool_id) {
+			return None;
+		}
+		Self::accrue_interest_rate(pool_id).ok()?;
+		let pool_supply_underlying = T::LiquidityPoolsManager::get_pool_available_liquidity(pool_id);
+		let pool_data = T::LiquidityPoolsManager::get_pool_data(pool_id);
+		let pool_bad_debt = T::LiquidityPoolsManager::get_pool_bad_debt(pool_id);
+		let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(pool_id).ok()?;
+
+		Some((
+			pool_supply_underlying,
+			pool_data.borrowed,
+			pool_data.protocol_interest,
+			pool_bad_debt,
+			exchange_rate,
+		))
+	}
+
 	/// Gets current utilization rate of the pool. The rate is calculated for the current block.
 	fn get_pool_utilization_rate(pool_id: CurrencyId) -> Option<Rate> {
 		Self::accrue_interest_rate(pool_id).ok()?;
@@ -1145,7 +1328,7 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 			.try_fold(Balance::zero(), |acc, &pool_id| -> BalanceResult {
 				let user_supply_underlying = Self::get_user_supply_underlying_balance(&who, pool_id)?;
 				let pool_collateral_factor = Self::controller_data_storage(pool_id).collateral_factor;
-				let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+				let oracle_price = Self::get_collateral_price(pool_id)?;
 				let user_supply_usd =
 					T::LiquidityPoolsManager::underlying_to_usd(user_supply_underlying, oracle_price)?;
 				let user_collateral_in_usd = Rate::from_inner(user_supply_usd)
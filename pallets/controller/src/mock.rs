@@ -12,7 +12,7 @@ pub use minterest_primitives::{
 	Balance, CurrencyId, Interest, Rate,
 };
 use orml_traits::parameter_type_with_key;
-use pallet_traits::PricesManager;
+use pallet_traits::PriceSource;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -37,7 +37,7 @@ construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Call, Event<T>, Config<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
-		TestPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+		TestPools: liquidity_pools::{Pallet, Storage, Call, Event, Config<T>},
 		TestController: controller::{Pallet, Storage, Call, Event, Config<T>},
 		TestMinterestModel: minterest_model::{Pallet, Storage, Call, Event, Config<T>},
 		TestMntToken: mnt_token::{Pallet, Storage, Call, Event<T>, Config<T>},
@@ -72,6 +72,7 @@ parameter_types! {
 // -----------------------------------------------------------------------------------------
 thread_local! {
 	static UNDERLYING_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+	static UNDERLYING_TWAP_PRICE: RefCell<Option<Price>> = RefCell::new(None);
 }
 
 pub struct MockPriceSource;
@@ -79,14 +80,19 @@ impl MockPriceSource {
 	pub fn set_underlying_price(price: Option<Price>) {
 		UNDERLYING_PRICE.with(|v| *v.borrow_mut() = price);
 	}
+
+	pub fn set_underlying_twap_price(price: Option<Price>) {
+		UNDERLYING_TWAP_PRICE.with(|v| *v.borrow_mut() = price);
+	}
 }
 
-impl PricesManager<CurrencyId> for MockPriceSource {
+impl PriceSource<CurrencyId> for MockPriceSource {
 	fn get_underlying_price(_currency_id: CurrencyId) -> Option<Price> {
 		UNDERLYING_PRICE.with(|v| *v.borrow_mut())
 	}
-	fn lock_price(_currency_id: CurrencyId) {}
-	fn unlock_price(_currency_id: CurrencyId) {}
+	fn get_underlying_twap_price(_currency_id: CurrencyId) -> Option<Price> {
+		UNDERLYING_TWAP_PRICE.with(|v| *v.borrow_mut())
+	}
 }
 
 // -----------------------------------------------------------------------------------------
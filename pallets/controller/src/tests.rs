@@ -1,7 +1,7 @@
 //! Tests for the controller module.
 use super::*;
 use crate::mock::{Event, *};
-use frame_support::{assert_err, assert_noop, assert_ok};
+use frame_support::{assert_err, assert_noop, assert_ok, traits::OnInitialize};
 pub use minterest_primitives::{Balance, CurrencyId, Interest, Operation, Rate};
 use pallet_traits::{ControllerManager, PoolsManager, UserCollateral};
 use sp_runtime::DispatchError::BadOrigin;
@@ -183,6 +183,128 @@ fn accrue_interest_should_work() {
 		});
 }
 
+#[test]
+fn on_initialize_should_accrue_interest_in_all_pools_once_per_block() {
+	ExtBuilder::default()
+		.set_minterest_model_params(
+			DOT,                                                // currency_id
+			Rate::saturating_from_rational(8, 10),              // kink
+			Rate::zero(),                                       // base_rate_per_block
+			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
+			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		)
+		.init_pool(
+			DOT,              // pool_id
+			dollars(80_u128), // total_borrowed
+			Rate::one(),      // borrow_index
+			Balance::zero(),  // total_protocol_interest
+		)
+		.set_controller_data(
+			DOT,                                     // currency_id
+			0,                                       // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),   // protocol_interest_factor
+			Rate::saturating_from_rational(5, 1000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),   //collateral_factor
+			None,                                    // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,    // protocol_interest_threshold
+		)
+		.set_pool_balance(TestPools::pools_account_id(), DOT, dollars(20_u128))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let weight = TestController::on_initialize(1);
+
+			assert_eq!(weight, <() as WeightInfo>::on_initialize());
+
+			// Only the DOT pool is created.
+			assert_eq!(
+				TestController::controller_data_storage(DOT).last_interest_accrued_block,
+				1
+			);
+			let pool_data = TestPools::pool_data_storage(DOT);
+			assert_eq!(pool_data.borrowed, 80_000_000_576_000_000_000);
+
+			// Subsequent calls within the same block don't change the pool state.
+			assert_ok!(TestController::accrue_interest_rate(DOT));
+			assert_eq!(TestPools::pool_data_storage(DOT), pool_data);
+		});
+}
+
+#[test]
+fn accrue_interest_should_write_pool_history() {
+	ExtBuilder::default()
+		.set_minterest_model_params(
+			DOT,                                                // currency_id
+			Rate::saturating_from_rational(8, 10),              // kink
+			Rate::zero(),                                       // base_rate_per_block
+			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
+			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		)
+		.init_pool(
+			DOT,              // pool_id
+			dollars(80_u128), // total_borrowed
+			Rate::one(),      // borrow_index
+			Balance::zero(),  // total_protocol_interest
+		)
+		.set_controller_data(
+			DOT,                                     // currency_id
+			0,                                       // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),   // protocol_interest_factor
+			Rate::saturating_from_rational(5, 1000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),   //collateral_factor
+			None,                                    // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,    // protocol_interest_threshold
+		)
+		.set_pool_balance(TestPools::pools_account_id(), DOT, dollars(20_u128))
+		.build()
+		.execute_with(|| {
+			// Checkpoint interval is 10 blocks, history max length is 5 checkpoints.
+			System::set_block_number(10);
+			assert_ok!(TestController::accrue_interest_rate(DOT));
+			let first_checkpoint_borrow_index = TestPools::pool_data_storage(DOT).borrow_index;
+			assert_eq!(
+				TestController::get_pool_history(DOT, 0, 100),
+				vec![PoolCheckpoint {
+					block_number: 10,
+					exchange_rate: Rate::one(),
+					borrow_index: first_checkpoint_borrow_index,
+				}]
+			);
+
+			// Only one checkpoint is written per interval.
+			System::set_block_number(15);
+			assert_ok!(TestController::accrue_interest_rate(DOT));
+			assert_eq!(TestController::get_pool_history(DOT, 0, 100).len(), 1);
+
+			for block_number in (20..=60).step_by(10) {
+				System::set_block_number(block_number);
+				assert_ok!(TestController::accrue_interest_rate(DOT));
+			}
+
+			// The oldest checkpoint has been overwritten.
+			let history = TestController::get_pool_history(DOT, 0, 100);
+			assert_eq!(
+				history
+					.iter()
+					.map(|checkpoint| checkpoint.block_number)
+					.collect::<Vec<_>>(),
+				vec![20, 30, 40, 50, 60]
+			);
+			assert_eq!(
+				history.last().map(|checkpoint| checkpoint.borrow_index),
+				Some(TestPools::pool_data_storage(DOT).borrow_index)
+			);
+			assert_eq!(
+				TestController::get_pool_history(DOT, 30, 50)
+					.iter()
+					.map(|checkpoint| checkpoint.block_number)
+					.collect::<Vec<_>>(),
+				vec![30, 40, 50]
+			);
+			assert!(TestController::get_pool_history(BTC, 0, 100).is_empty());
+		});
+}
+
 #[test]
 fn accrue_interest_should_not_work() {
 	ExtBuilder::default()
@@ -565,6 +687,58 @@ fn get_hypothetical_account_liquidity_two_currencies_from_borrow_should_work() {
 		});
 }
 
+#[test]
+fn get_hypothetical_account_liquidity_should_use_twap_price() {
+	ExtBuilder::default()
+		// ALICE deposit 60 DOT and borrow 30 DOT
+		.set_user_balance(ALICE, DOT, 70)
+		.set_user_balance(ALICE, MDOT, 60)
+		.set_pool_balance(TestPools::pools_account_id(), DOT, 60)
+		.init_pool(
+			DOT,             // pool_id
+			30,              // borrowed
+			Rate::one(),     // borrow_index
+			Balance::zero(), // protocol_interest
+		)
+		.set_pool_user_data(
+			DOT,         // pool_id
+			ALICE,       // user
+			30,          // borrowed
+			Rate::one(), // interest_index
+			true,        // is_collateral
+		)
+		.set_controller_data(
+			DOT,                                     // currency_id
+			0,                                       // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),   // protocol_interest_factor
+			Rate::saturating_from_rational(5, 1000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),   //collateral_factor
+			None,                                    // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,    // protocol_interest_threshold
+		)
+		.build()
+		.execute_with(|| {
+			// Spot price = 2.00 USD, TWAP = 1.00 USD.
+			// Collateral is valued at TWAP: 60 * 1.5 * 0.9 * 1 = 81.
+			// Borrows are valued at spot: (30 + 10) * 2 = 80.
+			MockPriceSource::set_underlying_price(Some(Price::from_inner(2 * DOLLARS)));
+			MockPriceSource::set_underlying_twap_price(Some(Price::from_inner(DOLLARS)));
+			assert_eq!(
+				TestController::get_hypothetical_account_liquidity(&ALICE, Some(DOT), 0, 10),
+				Ok((1, 0))
+			);
+
+			// Spot price = 2.00 USD, TWAP = 4.00 USD.
+			// Collateral is valued at spot: 60 * 1.5 * 0.9 * 2 = 162.
+			// Borrows are valued at TWAP: (30 + 10) * 4 = 160.
+			MockPriceSource::set_underlying_twap_price(Some(Price::from_inner(4 * DOLLARS)));
+			assert_eq!(
+				TestController::get_hypothetical_account_liquidity(&ALICE, Some(DOT), 0, 10),
+				Ok((2, 0))
+			);
+		});
+}
+
 #[test]
 fn get_liquidity_pool_exchange_rate_should_work() {
 	ExtBuilder::default()
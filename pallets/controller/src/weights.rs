@@ -52,6 +52,7 @@ pub trait WeightInfo {
 	fn set_collateral_factor() -> Weight;
 	fn set_borrow_cap() -> Weight;
 	fn set_protocol_interest_threshold() -> Weight;
+	fn on_initialize() -> Weight;
 }
 
 /// Weights for controller using the Minterest node and recommended hardware.
@@ -92,6 +93,12 @@ impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Estimated as the accrual of every enabled pool. Regenerate with the `on_initialize` benchmark.
+	fn on_initialize() -> Weight {
+		(190_048_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(40 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -131,4 +138,10 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Estimated as the accrual of every enabled pool. Regenerate with the `on_initialize` benchmark.
+	fn on_initialize() -> Weight {
+		(190_048_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(40 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
 }
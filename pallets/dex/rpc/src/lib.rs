@@ -0,0 +1,110 @@
+//! RPC interface for the dex pallet.
+//!
+//! RPC installation: `rpc/src/lib.rc`
+//!
+//! Corresponding runtime API declaration: `pallets/dex/rpc/run-time/src/lib.rs`
+//! Corresponding runtime API implementation: `runtime/src/lib.rs`
+
+pub use dex_rpc_runtime_api::{DexBalanceInfo, DexRuntimeApi};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use minterest_primitives::CurrencyId;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc]
+/// Base trait for RPC interface of dex
+pub trait DexRpcApi<BlockHash> {
+	/// Finds the trading path on DEX with the greatest target amount for the exact supply
+	/// amount. The path may go through intermediate currencies if it is more profitable than
+	/// the direct swap, or if there is no liquidity pool for the currencies.
+	///
+	/// Parameters:
+	///  - `&self` :  Self reference
+	///  - `supply_currency_id`: the currency to sell.
+	///  - `target_currency_id`: the currency to buy.
+	///  - `supply_amount`: the amount of `supply_currency_id` to sell.
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	/// Return:
+	/// (path, target_amount): None if there is no trading path between the currencies.
+	///
+	/// - `path`: currencies of the trading path, starting with `supply_currency_id` and ending
+	/// with `target_currency_id`.
+	/// - [`target_amount`](`DexBalanceInfo::amount`): expected amount of `target_currency_id`
+	/// to receive.
+	///
+	/// # Example:
+	/// ``` ignore
+	/// curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0",
+	/// "id":1, "method":"dex_getBestRoute", "params": [{"UnderlyingAsset":"KSM"},
+	/// {"UnderlyingAsset":"BTC"}, {"amount":"1000000000000000000"}]}'
+	/// ```
+	#[doc(alias = "MNT RPC")]
+	#[doc(alias = "MNT dex")]
+	#[rpc(name = "dex_getBestRoute")]
+	fn get_best_route(
+		&self,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: DexBalanceInfo,
+		at: Option<BlockHash>,
+	) -> Result<Option<(Vec<CurrencyId>, DexBalanceInfo)>>;
+}
+
+/// A struct that implements the `DexRpcApi`.
+pub struct DexRpcImpl<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> DexRpcImpl<C, B> {
+	/// Create new `DexRpcImpl` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// Implementation of 'DexRpcApi'
+impl<C, Block> DexRpcApi<<Block as BlockT>::Hash> for DexRpcImpl<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DexRuntimeApi<Block>,
+{
+	fn get_best_route(
+		&self,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: DexBalanceInfo,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<(Vec<CurrencyId>, DexBalanceInfo)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		api.get_best_route(&at, supply_currency_id, target_currency_id, supply_amount.amount)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get the best route.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
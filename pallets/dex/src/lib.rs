@@ -4,22 +4,73 @@
 //!
 //! This is a pallet for trading tokens with DeXes. May be used when balancing Liquidation pools or
 //! buying back MNT tokens for re-distribution.
+//!
+//! Trading is performed against constant product (x * y = k) liquidity pools. Each trading pair
+//! has its own liquidity pool, which reserves are provided by liquidity providers in exchange
+//! for liquidity shares of the pool. A swap fee is charged on the supply amount of every swap
+//! and is left in the pool, increasing the value of the liquidity shares.
+//!
+//! Currencies without a common liquidity pool are swapped along a trading path through
+//! intermediate currencies. The best trading path can be found with the `dex_getBestRoute` RPC.
+//!
+//! ### Cumulative prices
+//!
+//! Each trading pair keeps cumulative price accumulators in the style of Uniswap V2. Before
+//! every swap and liquidity change the spot prices of the pair currencies are multiplied by the
+//! number of blocks elapsed since the previous update and added to the accumulators. The
+//! difference of two accumulator values divided by the number of blocks between them is the
+//! time weighted average price over that interval, which is expensive to manipulate within a
+//! single block.
+//!
+//! The average prices are taken over windows of `PriceAveragingPeriod` blocks and are provided
+//! by `DexPriceProvider`, which implements `DataProvider<CurrencyId, Price>` and may be used as a
+//! price source of the prices pallet.
+//!
+//! ### Liquidity mining
+//!
+//! Liquidity providers of the trading pairs may be rewarded with MNT by the mnt-token pallet.
+//! MNT accrued by the liquidity provider is distributed before every change of its liquidity
+//! shares and can be claimed with `claim_mnt`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
 use frame_support::{pallet_prelude::*, transactional, PalletId};
-use minterest_primitives::{Balance, CurrencyId};
+use frame_system::pallet_prelude::*;
+use minterest_primitives::{Balance, CurrencyId, Price, Rate, TradingPair};
 pub use module::*;
-use orml_traits::MultiCurrency;
-use pallet_traits::DEXManager;
-use sp_runtime::traits::{AccountIdConversion, Zero};
+use orml_traits::{DataProvider, MultiCurrency};
+use pallet_traits::{DEXManager, DexSharesProvider, MntManager};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, IntegerSquareRoot, One, SaturatedConversion, Saturating, Zero},
+	FixedPointNumber,
+};
+use sp_std::{result, vec, vec::Vec};
+pub use weights::WeightInfo;
 
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod weights;
+
+type BalanceResult = result::Result<Balance, DispatchError>;
+
+/// Cumulative prices of the trading pair currencies.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct CumulativePrices<BlockNumber> {
+	/// Sum of the prices of the first currency of the pair in the second currency, weighted by
+	/// the number of blocks each price lasted. Inner value of `Price`, wraps around on overflow.
+	pub price_0_cumulative: u128,
+	/// Sum of the prices of the second currency of the pair in the first currency, weighted by
+	/// the number of blocks each price lasted. Inner value of `Price`, wraps around on overflow.
+	pub price_1_cumulative: u128,
+	/// The block number of the accumulators update.
+	pub block_number: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -38,12 +89,64 @@ pub mod module {
 		#[pallet::constant]
 		/// The Dex account id.
 		type DexAccountId: Get<Self::AccountId>;
+
+		#[pallet::constant]
+		/// The maximum number of currencies in a trading path.
+		type TradingPathLimit: Get<u32>;
+
+		#[pallet::constant]
+		/// The maximum number of trading pairs in a single `claim_mnt` call.
+		type MaxClaimTradingPairs: Get<u32>;
+
+		#[pallet::constant]
+		/// The fee charged on the supply amount of every swap. The fee stays in the liquidity
+		/// pool.
+		type SwapFee: Get<Rate>;
+
+		#[pallet::constant]
+		/// The number of blocks over which the average prices of the trading pairs are
+		/// calculated.
+		type PriceAveragingPeriod: Get<Self::BlockNumber>;
+
+		/// Public API of the mnt-token pallet, distributes MNT to the liquidity providers.
+		type MntManager: MntManager<Self::AccountId>;
+
+		/// Weight information for the extrinsics.
+		type DexWeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Insufficient available dex balance.
-		InsufficientDexBalance,
+		/// Number overflow in calculation.
+		NumOverflow,
+		/// The currency can't be traded on the DEX.
+		NotValidCurrencyId,
+		/// Supply and target currencies are the same.
+		IdenticalCurrencies,
+		/// Not enough liquidity in the liquidity pool.
+		InsufficientLiquidity,
+		/// Liquidity pool of the trading pair is empty.
+		LiquidityPoolNotFound,
+		/// The amount of liquidity to add is invalid.
+		InvalidLiquidityIncrement,
+		/// The share increment is less than the minimum accepted by the provider.
+		UnacceptableShareIncrement,
+		/// The withdrawn liquidity is less than the minimum accepted by the provider.
+		UnacceptableLiquidityWithdrawn,
+		/// The account doesn't own enough liquidity shares.
+		InsufficientShares,
+		/// The target amount of the swap is less than `min_target_amount`.
+		InsufficientTargetAmount,
+		/// The supply amount of the swap is greater than `max_supply_amount`.
+		ExcessiveSupplyAmount,
+		/// Swap with zero amount is not allowed.
+		ZeroSwapAmount,
+		/// The trading path is shorter than two or longer than `TradingPathLimit` currencies.
+		InvalidTradingPathLength,
+		/// The trading path goes through the same liquidity pool more than once.
+		DuplicateTradingPair,
+		/// The number of trading pairs to claim MNT in exceeds `MaxClaimTradingPairs`.
+		TooManyTradingPairs,
 	}
 
 	#[pallet::event]
@@ -52,8 +155,80 @@ pub mod module {
 		/// Use supply currency to swap target currency. \[trader, supply_currency_id,
 		/// target_currency_id supply_currency_amount, target_currency_amount\]
 		Swap(T::AccountId, CurrencyId, CurrencyId, Balance, Balance),
+		/// Liquidity has been added to the pool: \[who, currency_id_0, amount_0, currency_id_1,
+		/// amount_1, share_increment\]
+		AddLiquidity(T::AccountId, CurrencyId, Balance, CurrencyId, Balance, Balance),
+		/// Liquidity has been removed from the pool: \[who, currency_id_0, amount_0,
+		/// currency_id_1, amount_1, share_decrement\]
+		RemoveLiquidity(T::AccountId, CurrencyId, Balance, CurrencyId, Balance, Balance),
+		/// MNT accrued by the liquidity provider has been claimed: \[who\]
+		Claimed(T::AccountId),
 	}
 
+	/// Reserves of the liquidity pool of the trading pair, in the order of the pair currencies.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=dex::module::Pallet::liquidity_pool_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT dex")]
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_pool_storage)]
+	pub type LiquidityPoolStorage<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (Balance, Balance), ValueQuery>;
+
+	/// Total amount of liquidity shares issued for the trading pair.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=dex::module::Pallet::total_shares_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT dex")]
+	#[pallet::storage]
+	#[pallet::getter(fn total_shares_storage)]
+	pub type TotalSharesStorage<T: Config> = StorageMap<_, Twox64Concat, TradingPair, Balance, ValueQuery>;
+
+	/// Liquidity shares of the trading pair owned by the account.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=dex::module::Pallet::shares_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT dex")]
+	#[pallet::storage]
+	#[pallet::getter(fn shares_storage)]
+	pub type SharesStorage<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// Cumulative prices of the trading pair as of the last swap or liquidity change.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=dex::module::Pallet::cumulative_prices_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT dex")]
+	#[pallet::storage]
+	#[pallet::getter(fn cumulative_prices_storage)]
+	pub type CumulativePricesStorage<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, CumulativePrices<T::BlockNumber>, ValueQuery>;
+
+	/// Cumulative prices of the trading pair at the start of the current averaging window.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=dex::module::Pallet::price_checkpoint_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT dex")]
+	#[pallet::storage]
+	#[pallet::getter(fn price_checkpoint_storage)]
+	pub type PriceCheckpointStorage<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, CumulativePrices<T::BlockNumber>, OptionQuery>;
+
+	/// Average prices of the trading pair currencies over the last complete averaging window:
+	/// (price_0, price_1).
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=dex::module::Pallet::average_prices_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT dex")]
+	#[pallet::storage]
+	#[pallet::getter(fn average_prices_storage)]
+	pub type AveragePricesStorage<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (Price, Price), OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -61,62 +236,384 @@ pub mod module {
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Adds liquidity to the pool of the trading pair in exchange for liquidity shares.
+		///
+		/// If the pool is empty, both maximum amounts are deposited and define the initial price.
+		/// Otherwise the amounts are deposited at the current ratio of the pool reserves.
+		///
+		/// Parameters:
+		/// - `currency_id_a`: the first currency of the trading pair.
+		/// - `currency_id_b`: the second currency of the trading pair.
+		/// - `max_amount_a`: the maximum amount of `currency_id_a` to deposit.
+		/// - `max_amount_b`: the maximum amount of `currency_id_b` to deposit.
+		/// - `min_share_increment`: the minimum amount of liquidity shares to receive.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT dex")]
+		#[pallet::weight(T::DexWeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			max_amount_a: Balance,
+			max_amount_b: Balance,
+			min_share_increment: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_add_liquidity(
+				&who,
+				currency_id_a,
+				currency_id_b,
+				max_amount_a,
+				max_amount_b,
+				min_share_increment,
+			)?;
+			Ok(().into())
+		}
+
+		/// Burns liquidity shares and withdraws the corresponding part of the pool reserves.
+		///
+		/// Parameters:
+		/// - `currency_id_a`: the first currency of the trading pair.
+		/// - `currency_id_b`: the second currency of the trading pair.
+		/// - `remove_share`: the amount of liquidity shares to burn.
+		/// - `min_withdrawn_a`: the minimum amount of `currency_id_a` to receive.
+		/// - `min_withdrawn_b`: the minimum amount of `currency_id_b` to receive.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT dex")]
+		#[pallet::weight(T::DexWeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			remove_share: Balance,
+			min_withdrawn_a: Balance,
+			min_withdrawn_b: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_remove_liquidity(
+				&who,
+				currency_id_a,
+				currency_id_b,
+				remove_share,
+				min_withdrawn_a,
+				min_withdrawn_b,
+			)?;
+			Ok(().into())
+		}
+
+		/// Claims all the MNT accrued by the liquidity provider in the specified trading pairs.
+		///
+		/// Parameters:
+		/// - `trading_pairs`: the trading pairs to claim MNT in, at most `MaxClaimTradingPairs`.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT dex")]
+		#[pallet::weight(T::DexWeightInfo::claim_mnt(trading_pairs.len() as u32))]
+		#[transactional]
+		pub fn claim_mnt(
+			origin: OriginFor<T>,
+			trading_pairs: Vec<(CurrencyId, CurrencyId)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				trading_pairs.len() <= T::MaxClaimTradingPairs::get() as usize,
+				Error::<T>::TooManyTradingPairs
+			);
+			trading_pairs
+				.into_iter()
+				.try_for_each(|(currency_id_a, currency_id_b)| -> DispatchResult {
+					let trading_pair = Self::get_trading_pair(currency_id_a, currency_id_b)?;
+					T::MntManager::update_dex_pair_mnt_index(trading_pair)?;
+					T::MntManager::distribute_liquidity_provider_mnt(trading_pair, &who, true)?;
+					Ok(())
+				})?;
+			Self::deposit_event(Event::Claimed(who));
+			Ok(().into())
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
 	/// Ensured atomic.
 	#[transactional]
-	fn do_swap_with_exact_supply(
+	fn do_add_liquidity(
 		who: &T::AccountId,
-		supply_currency_id: CurrencyId,
-		target_currency_id: CurrencyId,
-		_supply_amount: Balance,
-		_min_target_amount: Balance,
-	) -> sp_std::result::Result<Balance, DispatchError> {
-		let actual_supply_amount = Balance::zero();
-		let actual_target_amount = Balance::zero();
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		min_share_increment: Balance,
+	) -> DispatchResult {
+		let trading_pair = Self::get_trading_pair(currency_id_a, currency_id_b)?;
+		let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.first() {
+			(max_amount_a, max_amount_b)
+		} else {
+			(max_amount_b, max_amount_a)
+		};
+		ensure!(
+			!max_amount_0.is_zero() && !max_amount_1.is_zero(),
+			Error::<T>::InvalidLiquidityIncrement
+		);
+
+		let (reserve_0, reserve_1) = Self::liquidity_pool_storage(trading_pair);
+		let total_shares = Self::total_shares_storage(trading_pair);
+
+		let (amount_0, amount_1, share_increment) = if total_shares.is_zero() {
+			// The initial deposit defines the price. Shares are issued as the geometric mean of
+			// the deposited amounts.
+			let share_increment = max_amount_0
+				.integer_sqrt()
+				.checked_mul(max_amount_1.integer_sqrt())
+				.ok_or(Error::<T>::NumOverflow)?;
+			(max_amount_0, max_amount_1, share_increment)
+		} else {
+			let amount_1_optimal =
+				multiply_by_rational(max_amount_0, reserve_1, reserve_0).map_err(|_| Error::<T>::NumOverflow)?;
+			let (amount_0, amount_1) = if amount_1_optimal <= max_amount_1 {
+				(max_amount_0, amount_1_optimal)
+			} else {
+				let amount_0_optimal =
+					multiply_by_rational(max_amount_1, reserve_0, reserve_1).map_err(|_| Error::<T>::NumOverflow)?;
+				(amount_0_optimal, max_amount_1)
+			};
+			let share_increment =
+				multiply_by_rational(amount_0, total_shares, reserve_0).map_err(|_| Error::<T>::NumOverflow)?;
+			(amount_0, amount_1, share_increment)
+		};
+
+		ensure!(
+			!amount_0.is_zero() && !amount_1.is_zero() && !share_increment.is_zero(),
+			Error::<T>::InvalidLiquidityIncrement
+		);
+		ensure!(
+			share_increment >= min_share_increment,
+			Error::<T>::UnacceptableShareIncrement
+		);
 
-		Self::deposit_event(Event::Swap(
+		T::MntManager::update_dex_pair_mnt_index(trading_pair)?;
+		T::MntManager::distribute_liquidity_provider_mnt(trading_pair, who, false)?;
+
+		let module_account_id = Self::dex_account_id();
+		T::MultiCurrency::transfer(trading_pair.first(), who, &module_account_id, amount_0)?;
+		T::MultiCurrency::transfer(trading_pair.second(), who, &module_account_id, amount_1)?;
+
+		let new_reserve_0 = reserve_0.checked_add(amount_0).ok_or(Error::<T>::NumOverflow)?;
+		let new_reserve_1 = reserve_1.checked_add(amount_1).ok_or(Error::<T>::NumOverflow)?;
+		let new_total_shares = total_shares
+			.checked_add(share_increment)
+			.ok_or(Error::<T>::NumOverflow)?;
+		Self::update_cumulative_prices(trading_pair, reserve_0, reserve_1);
+		LiquidityPoolStorage::<T>::insert(trading_pair, (new_reserve_0, new_reserve_1));
+		TotalSharesStorage::<T>::insert(trading_pair, new_total_shares);
+		SharesStorage::<T>::mutate(trading_pair, who, |shares| {
+			*shares = shares.saturating_add(share_increment)
+		});
+
+		Self::deposit_event(Event::AddLiquidity(
 			who.clone(),
-			supply_currency_id,
-			target_currency_id,
-			actual_supply_amount,
-			actual_target_amount,
+			trading_pair.first(),
+			amount_0,
+			trading_pair.second(),
+			amount_1,
+			share_increment,
 		));
+		Ok(())
+	}
+
+	/// Ensured atomic.
+	#[transactional]
+	fn do_remove_liquidity(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		remove_share: Balance,
+		min_withdrawn_a: Balance,
+		min_withdrawn_b: Balance,
+	) -> DispatchResult {
+		let trading_pair = Self::get_trading_pair(currency_id_a, currency_id_b)?;
+		let (min_withdrawn_0, min_withdrawn_1) = if currency_id_a == trading_pair.first() {
+			(min_withdrawn_a, min_withdrawn_b)
+		} else {
+			(min_withdrawn_b, min_withdrawn_a)
+		};
+		ensure!(!remove_share.is_zero(), Error::<T>::InsufficientShares);
+
+		let shares = Self::shares_storage(trading_pair, who);
+		ensure!(shares >= remove_share, Error::<T>::InsufficientShares);
+
+		let (reserve_0, reserve_1) = Self::liquidity_pool_storage(trading_pair);
+		let total_shares = Self::total_shares_storage(trading_pair);
+		let amount_0 =
+			multiply_by_rational(remove_share, reserve_0, total_shares).map_err(|_| Error::<T>::NumOverflow)?;
+		let amount_1 =
+			multiply_by_rational(remove_share, reserve_1, total_shares).map_err(|_| Error::<T>::NumOverflow)?;
+		ensure!(
+			amount_0 >= min_withdrawn_0 && amount_1 >= min_withdrawn_1,
+			Error::<T>::UnacceptableLiquidityWithdrawn
+		);
 
-		Ok(actual_supply_amount)
+		T::MntManager::update_dex_pair_mnt_index(trading_pair)?;
+		T::MntManager::distribute_liquidity_provider_mnt(trading_pair, who, false)?;
+
+		let module_account_id = Self::dex_account_id();
+		T::MultiCurrency::transfer(trading_pair.first(), &module_account_id, who, amount_0)?;
+		T::MultiCurrency::transfer(trading_pair.second(), &module_account_id, who, amount_1)?;
+
+		let new_reserve_0 = reserve_0.checked_sub(amount_0).ok_or(Error::<T>::NumOverflow)?;
+		let new_reserve_1 = reserve_1.checked_sub(amount_1).ok_or(Error::<T>::NumOverflow)?;
+		let new_total_shares = total_shares.checked_sub(remove_share).ok_or(Error::<T>::NumOverflow)?;
+		if new_total_shares.is_zero() {
+			LiquidityPoolStorage::<T>::remove(trading_pair);
+			TotalSharesStorage::<T>::remove(trading_pair);
+			// The prices of the removed pool are not provided. The accumulators and the averaging
+			// window start again when the liquidity is added to the pool.
+			CumulativePricesStorage::<T>::remove(trading_pair);
+			PriceCheckpointStorage::<T>::remove(trading_pair);
+			AveragePricesStorage::<T>::remove(trading_pair);
+		} else {
+			Self::update_cumulative_prices(trading_pair, reserve_0, reserve_1);
+			LiquidityPoolStorage::<T>::insert(trading_pair, (new_reserve_0, new_reserve_1));
+			TotalSharesStorage::<T>::insert(trading_pair, new_total_shares);
+		}
+		SharesStorage::<T>::mutate_exists(trading_pair, who, |maybe_shares| {
+			let remaining = shares - remove_share;
+			*maybe_shares = if remaining.is_zero() { None } else { Some(remaining) };
+		});
+
+		Self::deposit_event(Event::RemoveLiquidity(
+			who.clone(),
+			trading_pair.first(),
+			amount_0,
+			trading_pair.second(),
+			amount_1,
+			remove_share,
+		));
+		Ok(())
+	}
+
+	/// Ensured atomic.
+	#[transactional]
+	fn do_swap_with_exact_supply(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		min_target_amount: Balance,
+	) -> BalanceResult {
+		ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSwapAmount);
+		let amounts = Self::get_target_amounts(path, supply_amount)?;
+		let target_amount = amounts[amounts.len() - 1];
+		ensure!(target_amount >= min_target_amount, Error::<T>::InsufficientTargetAmount);
+
+		Self::do_swap(who, path, &amounts)?;
+		Ok(target_amount)
 	}
 
 	/// Ensured atomic.
-	///
-	/// TODO Temporary implementation. Makes an exchange at the rate of 1:1
-	/// (for example: 1 ETH = 1 BTC)
 	#[transactional]
 	pub fn do_swap_with_exact_target(
 		who: &T::AccountId,
-		supply_currency_id: CurrencyId,
-		target_currency_id: CurrencyId,
+		path: &[CurrencyId],
 		max_supply_amount: Balance,
 		target_amount: Balance,
-	) -> sp_std::result::Result<Balance, DispatchError> {
-		let target_dex_balance = Self::get_dex_available_liquidity(target_currency_id);
+	) -> BalanceResult {
+		ensure!(!target_amount.is_zero(), Error::<T>::ZeroSwapAmount);
+		let amounts = Self::get_supply_amounts(path, target_amount)?;
+		let supply_amount = amounts[0];
+		ensure!(supply_amount <= max_supply_amount, Error::<T>::ExcessiveSupplyAmount);
+
+		Self::do_swap(who, path, &amounts)?;
+		Ok(supply_amount)
+	}
+
+	/// Transfers the swapped amounts and updates the reserves of all the liquidity pools along
+	/// the trading path. Intermediate currencies never leave the DEX account.
+	/// - `path`: the trading path.
+	/// - `amounts`: the amounts of currencies of the `path` moving along the path.
+	fn do_swap(who: &T::AccountId, path: &[CurrencyId], amounts: &[Balance]) -> DispatchResult {
+		let last = path.len() - 1;
 		let module_account_id = Self::dex_account_id();
+		T::MultiCurrency::transfer(path[0], who, &module_account_id, amounts[0])?;
+		T::MultiCurrency::transfer(path[last], &module_account_id, who, amounts[last])?;
 
-		ensure!(target_dex_balance >= target_amount, Error::<T>::InsufficientDexBalance);
+		for (currencies, swap_amounts) in path.windows(2).zip(amounts.windows(2)) {
+			let (supply_currency_id, target_currency_id) = (currencies[0], currencies[1]);
+			let trading_pair = Self::get_trading_pair(supply_currency_id, target_currency_id)?;
+			LiquidityPoolStorage::<T>::try_mutate(trading_pair, |(reserve_0, reserve_1)| -> DispatchResult {
+				Self::update_cumulative_prices(trading_pair, *reserve_0, *reserve_1);
+				let (supply_reserve, target_reserve) = if supply_currency_id == trading_pair.first() {
+					(reserve_0, reserve_1)
+				} else {
+					(reserve_1, reserve_0)
+				};
+				*supply_reserve = supply_reserve
+					.checked_add(swap_amounts[0])
+					.ok_or(Error::<T>::NumOverflow)?;
+				*target_reserve = target_reserve
+					.checked_sub(swap_amounts[1])
+					.ok_or(Error::<T>::InsufficientLiquidity)?;
+				Ok(())
+			})?;
+		}
 
-		T::MultiCurrency::transfer(supply_currency_id, &who, &module_account_id, max_supply_amount)?;
-		T::MultiCurrency::transfer(target_currency_id, &module_account_id, &who, target_amount)?;
+		Self::deposit_event(Event::Swap(who.clone(), path[0], path[last], amounts[0], amounts[last]));
+		Ok(())
+	}
 
-		Self::deposit_event(Event::Swap(
-			who.clone(),
-			supply_currency_id,
-			target_currency_id,
-			max_supply_amount,
-			target_amount,
-		));
+	/// Accumulates the prices of the trading pair since the previous update. Must be called
+	/// before the reserves of the liquidity pool are changed. Closes the averaging window and
+	/// stores the average prices, if `PriceAveragingPeriod` blocks have passed since its start.
+	/// - `trading_pair`: the trading pair.
+	/// - `reserve_0`, `reserve_1`: the reserves of the liquidity pool before the change.
+	fn update_cumulative_prices(trading_pair: TradingPair, reserve_0: Balance, reserve_1: Balance) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let cumulative_prices =
+			Self::accumulate_prices(Self::cumulative_prices_storage(trading_pair), reserve_0, reserve_1, now);
+		CumulativePricesStorage::<T>::insert(trading_pair, &cumulative_prices);
 
-		Ok(target_amount)
+		match Self::price_checkpoint_storage(trading_pair) {
+			Some(checkpoint) if now.saturating_sub(checkpoint.block_number) < T::PriceAveragingPeriod::get() => {}
+			Some(checkpoint) => {
+				if let Some(average_prices) = Self::calculate_average_prices(&checkpoint, &cumulative_prices) {
+					AveragePricesStorage::<T>::insert(trading_pair, average_prices);
+				}
+				PriceCheckpointStorage::<T>::insert(trading_pair, cumulative_prices);
+			}
+			None => PriceCheckpointStorage::<T>::insert(trading_pair, cumulative_prices),
+		}
+	}
+
+	/// Adds the spot prices of the pool, multiplied by the number of blocks elapsed since the
+	/// accumulators update, to the accumulators. Nothing is accumulated for an empty pool.
+	fn accumulate_prices(
+		cumulative_prices: CumulativePrices<T::BlockNumber>,
+		reserve_0: Balance,
+		reserve_1: Balance,
+		now: T::BlockNumber,
+	) -> CumulativePrices<T::BlockNumber> {
+		let elapsed: u128 = now.saturating_sub(cumulative_prices.block_number).saturated_into();
+		let (mut price_0_cumulative, mut price_1_cumulative) = (
+			cumulative_prices.price_0_cumulative,
+			cumulative_prices.price_1_cumulative,
+		);
+		if !reserve_0.is_zero() && !reserve_1.is_zero() {
+			let price_0 = Price::saturating_from_rational(reserve_1, reserve_0).into_inner();
+			let price_1 = Price::saturating_from_rational(reserve_0, reserve_1).into_inner();
+			price_0_cumulative = price_0_cumulative.wrapping_add(price_0.wrapping_mul(elapsed));
+			price_1_cumulative = price_1_cumulative.wrapping_add(price_1.wrapping_mul(elapsed));
+		}
+		CumulativePrices {
+			price_0_cumulative,
+			price_1_cumulative,
+			block_number: now,
+		}
 	}
 }
 
@@ -126,10 +623,275 @@ impl<T: Config> Pallet<T> {
 		T::DexPalletId::get().into_account()
 	}
 
-	/// Gets current the total amount of cash the dex has.
-	fn get_dex_available_liquidity(dex_id: CurrencyId) -> Balance {
-		let module_account_id = Self::dex_account_id();
-		T::MultiCurrency::free_balance(dex_id, &module_account_id)
+	/// Creates a trading pair from two currencies, that can be traded on the DEX.
+	fn get_trading_pair(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> result::Result<TradingPair, Error<T>> {
+		ensure!(
+			!currency_id_a.is_supported_wrapped_asset() && !currency_id_b.is_supported_wrapped_asset(),
+			Error::<T>::NotValidCurrencyId
+		);
+		TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::IdenticalCurrencies)
+	}
+
+	/// Gets the reserves of the liquidity pool in the order of the given currencies.
+	///
+	/// Returns: (supply_reserve, target_reserve)
+	pub fn get_liquidity(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+	) -> result::Result<(Balance, Balance), DispatchError> {
+		let trading_pair = Self::get_trading_pair(supply_currency_id, target_currency_id)?;
+		let (reserve_0, reserve_1) = Self::liquidity_pool_storage(trading_pair);
+		ensure!(
+			!reserve_0.is_zero() && !reserve_1.is_zero(),
+			Error::<T>::LiquidityPoolNotFound
+		);
+		if supply_currency_id == trading_pair.first() {
+			Ok((reserve_0, reserve_1))
+		} else {
+			Ok((reserve_1, reserve_0))
+		}
+	}
+
+	/// Calculates the average prices between two observations of the cumulative prices.
+	///
+	/// Returns: (price_0, price_1) or `None` if no blocks have passed between the observations.
+	fn calculate_average_prices(
+		start: &CumulativePrices<T::BlockNumber>,
+		end: &CumulativePrices<T::BlockNumber>,
+	) -> Option<(Price, Price)> {
+		let elapsed: u128 = end.block_number.saturating_sub(start.block_number).saturated_into();
+		if elapsed.is_zero() {
+			return None;
+		}
+		let price_0 = end.price_0_cumulative.wrapping_sub(start.price_0_cumulative) / elapsed;
+		let price_1 = end.price_1_cumulative.wrapping_sub(start.price_1_cumulative) / elapsed;
+		Some((Price::from_inner(price_0), Price::from_inner(price_1)))
+	}
+
+	/// Gets the time weighted average price of `base_currency_id` in `quote_currency_id` over
+	/// the last complete averaging window. If the current window has already ended but hasn't
+	/// been closed by a swap or a liquidity change yet, the average over it is calculated on
+	/// the fly using the current reserves of the pool.
+	///
+	/// Returns `None` if the trading pair has no completed averaging window yet.
+	pub fn get_average_price(base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
+		let trading_pair = Self::get_trading_pair(base_currency_id, quote_currency_id).ok()?;
+		let checkpoint = Self::price_checkpoint_storage(trading_pair)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		let (price_0, price_1) = if now.saturating_sub(checkpoint.block_number) >= T::PriceAveragingPeriod::get() {
+			let (reserve_0, reserve_1) = Self::liquidity_pool_storage(trading_pair);
+			let cumulative_prices =
+				Self::accumulate_prices(Self::cumulative_prices_storage(trading_pair), reserve_0, reserve_1, now);
+			Self::calculate_average_prices(&checkpoint, &cumulative_prices)?
+		} else {
+			Self::average_prices_storage(trading_pair)?
+		};
+		if base_currency_id == trading_pair.first() {
+			Some(price_0)
+		} else {
+			Some(price_1)
+		}
+	}
+
+	/// Checks that the trading path consists of at least two and at most `TradingPathLimit`
+	/// currencies and goes through each liquidity pool at most once.
+	fn ensure_valid_path(path: &[CurrencyId]) -> DispatchResult {
+		ensure!(
+			path.len() >= 2 && path.len() <= T::TradingPathLimit::get() as usize,
+			Error::<T>::InvalidTradingPathLength
+		);
+		let trading_pairs = path
+			.windows(2)
+			.map(|currencies| Self::get_trading_pair(currencies[0], currencies[1]))
+			.collect::<result::Result<Vec<TradingPair>, _>>()?;
+		ensure!(
+			(1..trading_pairs.len()).all(|i| !trading_pairs[..i].contains(&trading_pairs[i])),
+			Error::<T>::DuplicateTradingPair
+		);
+		Ok(())
+	}
+
+	/// Calculates the amounts of currencies moving along the trading path for the exact supply
+	/// amount.
+	///
+	/// Returns: the amounts in the order of the `path` currencies, the last one is the target
+	/// amount.
+	pub fn get_target_amounts(
+		path: &[CurrencyId],
+		supply_amount: Balance,
+	) -> result::Result<Vec<Balance>, DispatchError> {
+		Self::ensure_valid_path(path)?;
+		let mut amounts = vec![supply_amount];
+		for currencies in path.windows(2) {
+			let (supply_reserve, target_reserve) = Self::get_liquidity(currencies[0], currencies[1])?;
+			let target_amount = Self::get_target_amount(supply_reserve, target_reserve, amounts[amounts.len() - 1])?;
+			ensure!(!target_amount.is_zero(), Error::<T>::ZeroSwapAmount);
+			amounts.push(target_amount);
+		}
+		Ok(amounts)
+	}
+
+	/// Calculates the amounts of currencies moving along the trading path for the exact target
+	/// amount.
+	///
+	/// Returns: the amounts in the order of the `path` currencies, the first one is the supply
+	/// amount.
+	pub fn get_supply_amounts(
+		path: &[CurrencyId],
+		target_amount: Balance,
+	) -> result::Result<Vec<Balance>, DispatchError> {
+		Self::ensure_valid_path(path)?;
+		let mut amounts = vec![Balance::zero(); path.len()];
+		amounts[path.len() - 1] = target_amount;
+		for i in (1..path.len()).rev() {
+			let (supply_reserve, target_reserve) = Self::get_liquidity(path[i - 1], path[i])?;
+			amounts[i - 1] = Self::get_supply_amount(supply_reserve, target_reserve, amounts[i])?;
+		}
+		Ok(amounts)
+	}
+
+	/// Collects all the trading paths from `supply_currency_id` to `target_currency_id` through
+	/// the existing liquidity pools, that are not longer than `TradingPathLimit`.
+	///
+	/// Iterates over all the liquidity pools, so it isn't used in the dispatchable calls.
+	pub fn get_trading_paths(supply_currency_id: CurrencyId, target_currency_id: CurrencyId) -> Vec<Vec<CurrencyId>> {
+		let trading_pairs: Vec<TradingPair> = LiquidityPoolStorage::<T>::iter()
+			.map(|(trading_pair, _)| trading_pair)
+			.collect();
+		let mut paths = Vec::new();
+		if supply_currency_id != target_currency_id {
+			Self::collect_trading_paths(
+				&trading_pairs,
+				&mut vec![supply_currency_id],
+				target_currency_id,
+				&mut paths,
+			);
+		}
+		paths
+	}
+
+	/// Depth-first search of the trading paths, that continue the `path`.
+	fn collect_trading_paths(
+		trading_pairs: &[TradingPair],
+		path: &mut Vec<CurrencyId>,
+		target_currency_id: CurrencyId,
+		paths: &mut Vec<Vec<CurrencyId>>,
+	) {
+		let current_currency_id = path[path.len() - 1];
+		for trading_pair in trading_pairs {
+			let next_currency_id = if trading_pair.first() == current_currency_id {
+				trading_pair.second()
+			} else if trading_pair.second() == current_currency_id {
+				trading_pair.first()
+			} else {
+				continue;
+			};
+
+			if next_currency_id == target_currency_id {
+				let mut found_path = path.clone();
+				found_path.push(target_currency_id);
+				paths.push(found_path);
+			} else if path.len() + 1 < T::TradingPathLimit::get() as usize && !path.contains(&next_currency_id) {
+				path.push(next_currency_id);
+				Self::collect_trading_paths(trading_pairs, path, target_currency_id, paths);
+				path.pop();
+			}
+		}
+	}
+
+	/// Finds the trading path with the greatest target amount for the exact supply amount.
+	///
+	/// Returns: (path, target_amount)
+	pub fn get_best_path_for_exact_supply(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+	) -> Option<(Vec<CurrencyId>, Balance)> {
+		Self::get_trading_paths(supply_currency_id, target_currency_id)
+			.into_iter()
+			.filter_map(|path| {
+				let target_amount = *Self::get_target_amounts(&path, supply_amount).ok()?.last()?;
+				Some((path, target_amount))
+			})
+			.fold(None, |best, (path, target_amount)| match best {
+				Some((_, best_target_amount)) if best_target_amount >= target_amount => best,
+				_ => Some((path, target_amount)),
+			})
+	}
+
+	/// Finds the trading path with the least supply amount for the exact target amount.
+	///
+	/// Returns: (path, supply_amount)
+	pub fn get_best_path_for_exact_target(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		target_amount: Balance,
+	) -> Option<(Vec<CurrencyId>, Balance)> {
+		Self::get_trading_paths(supply_currency_id, target_currency_id)
+			.into_iter()
+			.filter_map(|path| {
+				let supply_amount = *Self::get_supply_amounts(&path, target_amount).ok()?.first()?;
+				Some((path, supply_amount))
+			})
+			.fold(None, |best, (path, supply_amount)| match best {
+				Some((_, best_supply_amount)) if best_supply_amount <= supply_amount => best,
+				_ => Some((path, supply_amount)),
+			})
+	}
+
+	/// Calculates the amount of target currency received for the exact supply amount.
+	///
+	/// target_amount = target_reserve * supply_amount_with_fee /
+	/// (supply_reserve + supply_amount_with_fee)
+	pub fn get_target_amount(
+		supply_reserve: Balance,
+		target_reserve: Balance,
+		supply_amount: Balance,
+	) -> BalanceResult {
+		let supply_amount_with_fee = (Rate::one() - T::SwapFee::get()).saturating_mul_int(supply_amount);
+		let denominator = supply_reserve
+			.checked_add(supply_amount_with_fee)
+			.ok_or(Error::<T>::NumOverflow)?;
+		let target_amount = multiply_by_rational(target_reserve, supply_amount_with_fee, denominator)
+			.map_err(|_| Error::<T>::NumOverflow)?;
+		Ok(target_amount)
+	}
+
+	/// Calculates the amount of supply currency required to receive the exact target amount.
+	/// The result is rounded up in favour of the liquidity pool.
+	///
+	/// supply_amount = supply_reserve * target_amount / (target_reserve - target_amount) /
+	/// (1 - swap_fee)
+	pub fn get_supply_amount(
+		supply_reserve: Balance,
+		target_reserve: Balance,
+		target_amount: Balance,
+	) -> BalanceResult {
+		ensure!(target_amount < target_reserve, Error::<T>::InsufficientLiquidity);
+		let supply_amount_with_fee =
+			multiply_by_rational(supply_reserve, target_amount, target_reserve - target_amount)
+				.ok()
+				.and_then(|amount| amount.checked_add(1))
+				.ok_or(Error::<T>::NumOverflow)?;
+		let supply_amount = multiply_by_rational(
+			supply_amount_with_fee,
+			Rate::accuracy(),
+			(Rate::one() - T::SwapFee::get()).into_inner(),
+		)
+		.ok()
+		.and_then(|amount| amount.checked_add(1))
+		.ok_or(Error::<T>::NumOverflow)?;
+		Ok(supply_amount)
+	}
+}
+
+impl<T: Config> DexSharesProvider<T::AccountId> for Pallet<T> {
+	fn get_total_shares(trading_pair: TradingPair) -> Balance {
+		Self::total_shares_storage(trading_pair)
+	}
+
+	fn get_shares(trading_pair: TradingPair, who: &T::AccountId) -> Balance {
+		Self::shares_storage(trading_pair, who)
 	}
 }
 
@@ -143,8 +905,7 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 	) -> sp_std::result::Result<Balance, DispatchError> {
 		Self::do_swap_with_exact_supply(
 			who,
-			supply_currency_id,
-			target_currency_id,
+			&[supply_currency_id, target_currency_id],
 			supply_amount,
 			min_target_amount,
 		)
@@ -159,10 +920,65 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 	) -> sp_std::result::Result<Balance, DispatchError> {
 		Self::do_swap_with_exact_target(
 			who,
-			supply_currency_id,
-			target_currency_id,
+			&[supply_currency_id, target_currency_id],
 			max_supply_amount,
 			target_amount,
 		)
 	}
+
+	fn swap_with_exact_supply_by_path(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		min_target_amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		Self::do_swap_with_exact_supply(who, path, supply_amount, min_target_amount)
+	}
+
+	fn swap_with_exact_target_by_path(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		max_supply_amount: Balance,
+		target_amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		Self::do_swap_with_exact_target(who, path, max_supply_amount, target_amount)
+	}
+
+	fn get_best_path_for_exact_supply(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+	) -> Option<(Vec<CurrencyId>, Balance)> {
+		Self::get_best_path_for_exact_supply(supply_currency_id, target_currency_id, supply_amount)
+	}
+
+	fn get_best_path_for_exact_target(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		target_amount: Balance,
+	) -> Option<(Vec<CurrencyId>, Balance)> {
+		Self::get_best_path_for_exact_target(supply_currency_id, target_currency_id, target_amount)
+	}
+
+	fn get_supply_amount_by_path(path: &[CurrencyId], target_amount: Balance) -> Option<Balance> {
+		Self::get_supply_amounts(path, target_amount).ok()?.first().copied()
+	}
+}
+
+/// Provides the time weighted average prices of the DEX liquidity pools, quoted in
+/// `QuoteCurrencyId`. May be used as a price source of the prices pallet.
+pub struct DexPriceProvider<T, QuoteCurrencyId>(PhantomData<(T, QuoteCurrencyId)>);
+
+impl<T, QuoteCurrencyId> DataProvider<CurrencyId, Price> for DexPriceProvider<T, QuoteCurrencyId>
+where
+	T: Config,
+	QuoteCurrencyId: Get<CurrencyId>,
+{
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		let quote_currency_id = QuoteCurrencyId::get();
+		if *currency_id == quote_currency_id {
+			return Some(Price::one());
+		}
+		Pallet::<T>::get_average_price(*currency_id, quote_currency_id)
+	}
 }
@@ -12,12 +12,12 @@ pub use minterest_primitives::{
 	currency::DOT,
 };
 pub(crate) use minterest_primitives::{Balance, CurrencyId, Price, Rate};
-use orml_traits::parameter_type_with_key;
-pub(crate) use pallet_traits::PricesManager;
+use orml_traits::{parameter_type_with_key, MultiCurrency};
+pub(crate) use pallet_traits::PriceSource;
 use sp_core::H256;
 use sp_runtime::{
 	testing::{Header, TestXt},
-	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup, One},
+	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup, One, Zero},
 };
 use sp_std::cell::RefCell;
 
@@ -39,7 +39,7 @@ construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Call, Event<T>, Config<T>},
-		TestPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+		TestPools: liquidity_pools::{Pallet, Storage, Call, Event, Config<T>},
 		TestLiquidationPools: liquidation_pools::{Pallet, Storage, Call, Event<T>, Config<T>, ValidateUnsigned},
 		TestDex: dex::{Pallet, Storage, Call, Event<T>},
 		TestController: controller::{Pallet, Storage, Call, Event, Config<T>},
@@ -73,7 +73,7 @@ mock_impl_dex_config!(TestRuntime);
 mock_impl_balances_config!(TestRuntime);
 mock_impl_controller_config!(TestRuntime, ZeroAdmin);
 mock_impl_minterest_model_config!(TestRuntime, ZeroAdmin);
-mock_impl_mnt_token_config!(TestRuntime, ZeroAdmin);
+mock_impl_mnt_token_config!(TestRuntime, ZeroAdmin, dex::Pallet<TestRuntime>);
 
 // -----------------------------------------------------------------------------------------
 // 									MOCK PRICE
@@ -89,12 +89,10 @@ impl MockPriceSource {
 	}
 }
 
-impl PricesManager<CurrencyId> for MockPriceSource {
+impl PriceSource<CurrencyId> for MockPriceSource {
 	fn get_underlying_price(_currency_id: CurrencyId) -> Option<Price> {
 		UNDERLYING_PRICE.with(|v| *v.borrow_mut())
 	}
-	fn lock_price(_currency_id: CurrencyId) {}
-	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
 // -----------------------------------------------------------------------------------------
@@ -102,17 +100,28 @@ impl PricesManager<CurrencyId> for MockPriceSource {
 // -----------------------------------------------------------------------------------------
 pub struct ExtBuilder {
 	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+	liquidity_pools: Vec<(AccountId, CurrencyId, CurrencyId, Balance, Balance)>,
 }
 
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			endowed_accounts: vec![],
+			liquidity_pools: vec![],
 		}
 	}
 }
 
 impl ExtBuilder {
+	/// Set balance of the user
+	/// - 'who': account id
+	/// - 'currency_id': currency id
+	/// - 'balance': balance to set
+	pub fn set_user_balance(mut self, who: AccountId, currency_id: CurrencyId, balance: Balance) -> Self {
+		self.endowed_accounts.push((who, currency_id, balance));
+		self
+	}
+
 	/// Set balance of the liquidation pool
 	/// - 'currency_id': pool / currency id
 	/// - 'balance': balance to set
@@ -128,13 +137,20 @@ impl ExtBuilder {
 		self
 	}
 
-	/// Set DEX balance
-	/// - 'currency_id': currency id
-	/// - 'balance': balance value
-	pub fn set_dex_balance(mut self, account_id: AccountId, currency_id: CurrencyId, balance: Balance) -> Self {
-		self.endowed_accounts
-			//TestDex::dex_account_id()
-			.push((account_id, currency_id, balance));
+	/// Add liquidity to the DEX liquidity pool of the trading pair
+	/// - 'provider': account id of the liquidity provider
+	/// - 'currency_id_a', 'currency_id_b': trading pair
+	/// - 'amount_a', 'amount_b': amounts of liquidity to add
+	pub fn set_dex_liquidity_pool(
+		mut self,
+		provider: AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		amount_a: Balance,
+		amount_b: Balance,
+	) -> Self {
+		self.liquidity_pools
+			.push((provider, currency_id_a, currency_id_b, amount_a, amount_b));
 		self
 	}
 
@@ -150,7 +166,24 @@ impl ExtBuilder {
 		.unwrap();
 
 		let mut ext = sp_io::TestExternalities::new(t);
-		ext.execute_with(|| System::set_block_number(1));
+		ext.execute_with(|| {
+			self.liquidity_pools.into_iter().for_each(
+				|(provider, currency_id_a, currency_id_b, amount_a, amount_b)| {
+					Currencies::deposit(currency_id_a, &provider, amount_a).unwrap();
+					Currencies::deposit(currency_id_b, &provider, amount_b).unwrap();
+					TestDex::add_liquidity(
+						Origin::signed(provider),
+						currency_id_a,
+						currency_id_b,
+						amount_a,
+						amount_b,
+						Balance::zero(),
+					)
+					.expect("liquidity pool should be created");
+				},
+			);
+			System::set_block_number(1)
+		});
 		ext
 	}
 }
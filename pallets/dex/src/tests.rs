@@ -4,105 +4,628 @@
 
 use super::*;
 use crate::mock::{Event, *};
-use frame_support::assert_err;
-use orml_traits::MultiCurrency;
+use frame_support::{assert_noop, assert_ok, parameter_types};
+use orml_traits::{DataProvider, MultiCurrency};
 use pallet_traits::{DEXManager, PoolsManager};
 
+parameter_types! {
+	pub const GetEthCurrencyId: CurrencyId = ETH;
+}
+
+type EthPriceProvider = DexPriceProvider<TestRuntime, GetEthCurrencyId>;
+
+fn dex_trading_pair(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> TradingPair {
+	TradingPair::from_currency_ids(currency_id_a, currency_id_b).unwrap()
+}
+
 #[test]
-fn swap_with_exact_target_should_work() {
+fn add_liquidity_should_work() {
 	ExtBuilder::default()
-		.set_liquidation_pool_balance(TestLiquidationPools::pools_account_id(), DOT, 300_000 * DOLLARS)
-		.set_liquidation_pool_balance(TestLiquidationPools::pools_account_id(), ETH, 400_000 * DOLLARS)
-		.set_dex_balance(TestDex::dex_account_id(), DOT, 500_000 * DOLLARS)
-		.set_dex_balance(TestDex::dex_account_id(), ETH, 500_000 * DOLLARS)
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 200_000 * DOLLARS)
+		.set_user_balance(BOB, DOT, 100_000 * DOLLARS)
+		.set_user_balance(BOB, ETH, 100_000 * DOLLARS)
 		.build()
 		.execute_with(|| {
+			let trading_pair = dex_trading_pair(DOT, ETH);
+
+			// The initial liquidity defines the price: 1 DOT = 2 ETH.
+			assert_ok!(TestDex::add_liquidity(
+				Origin::signed(ALICE),
+				ETH,
+				DOT,
+				200_000 * DOLLARS,
+				100_000 * DOLLARS,
+				0
+			));
+			let alice_shares = 141_421_356_236_631_842_761_984;
+			let expected_event = Event::TestDex(crate::Event::AddLiquidity(
+				ALICE,
+				DOT,
+				100_000 * DOLLARS,
+				ETH,
+				200_000 * DOLLARS,
+				alice_shares,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
 			assert_eq!(
-				TestDex::swap_with_exact_target(
-					&TestLiquidationPools::pools_account_id(),
+				TestDex::liquidity_pool_storage(trading_pair),
+				(100_000 * DOLLARS, 200_000 * DOLLARS)
+			);
+			assert_eq!(TestDex::shares_storage(trading_pair, ALICE), alice_shares);
+			assert_eq!(TestDex::total_shares_storage(trading_pair), alice_shares);
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestDex::dex_account_id()),
+				100_000 * DOLLARS
+			);
+			assert_eq!(
+				Currencies::free_balance(ETH, &TestDex::dex_account_id()),
+				200_000 * DOLLARS
+			);
+
+			// The following liquidity is added at the current ratio of the reserves.
+			assert_noop!(
+				TestDex::add_liquidity(
+					Origin::signed(BOB),
+					DOT,
+					ETH,
+					10_000 * DOLLARS,
+					30_000 * DOLLARS,
+					alice_shares
+				),
+				Error::<TestRuntime>::UnacceptableShareIncrement
+			);
+			assert_ok!(TestDex::add_liquidity(
+				Origin::signed(BOB),
+				DOT,
+				ETH,
+				10_000 * DOLLARS,
+				30_000 * DOLLARS,
+				0
+			));
+			let bob_shares = alice_shares / 10;
+			assert_eq!(
+				TestDex::liquidity_pool_storage(trading_pair),
+				(110_000 * DOLLARS, 220_000 * DOLLARS)
+			);
+			assert_eq!(TestDex::shares_storage(trading_pair, BOB), bob_shares);
+			assert_eq!(TestDex::total_shares_storage(trading_pair), alice_shares + bob_shares);
+			assert_eq!(Currencies::free_balance(DOT, &BOB), 90_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &BOB), 80_000 * DOLLARS);
+
+			assert_noop!(
+				TestDex::add_liquidity(Origin::signed(BOB), DOT, DOT, DOLLARS, DOLLARS, 0),
+				Error::<TestRuntime>::IdenticalCurrencies
+			);
+			assert_noop!(
+				TestDex::add_liquidity(Origin::signed(BOB), MDOT, ETH, DOLLARS, DOLLARS, 0),
+				Error::<TestRuntime>::NotValidCurrencyId
+			);
+			assert_noop!(
+				TestDex::add_liquidity(Origin::signed(BOB), DOT, ETH, DOLLARS, 0, 0),
+				Error::<TestRuntime>::InvalidLiquidityIncrement
+			);
+		});
+}
+
+#[test]
+fn remove_liquidity_should_work() {
+	ExtBuilder::default()
+		.set_dex_liquidity_pool(ALICE, DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let trading_pair = dex_trading_pair(DOT, ETH);
+			let alice_shares = TestDex::shares_storage(trading_pair, ALICE);
+
+			assert_noop!(
+				TestDex::remove_liquidity(Origin::signed(ALICE), DOT, ETH, alice_shares + 1, 0, 0),
+				Error::<TestRuntime>::InsufficientShares
+			);
+			assert_noop!(
+				TestDex::remove_liquidity(
+					Origin::signed(ALICE),
 					DOT,
 					ETH,
-					50_000 * DOLLARS,
-					50_000 * DOLLARS
+					alice_shares / 2,
+					50_000 * DOLLARS + 1,
+					0
 				),
-				Ok(50_000 * DOLLARS)
+				Error::<TestRuntime>::UnacceptableLiquidityWithdrawn
 			);
 
+			assert_ok!(TestDex::remove_liquidity(
+				Origin::signed(ALICE),
+				DOT,
+				ETH,
+				alice_shares / 2,
+				50_000 * DOLLARS,
+				100_000 * DOLLARS
+			));
+			let expected_event = Event::TestDex(crate::Event::RemoveLiquidity(
+				ALICE,
+				DOT,
+				50_000 * DOLLARS,
+				ETH,
+				100_000 * DOLLARS,
+				alice_shares / 2,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
 			assert_eq!(
-				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
-				250_000 * DOLLARS
+				TestDex::liquidity_pool_storage(trading_pair),
+				(50_000 * DOLLARS, 100_000 * DOLLARS)
 			);
+			assert_eq!(Currencies::free_balance(DOT, &ALICE), 50_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &ALICE), 100_000 * DOLLARS);
+
+			// Removing all the shares empties the liquidity pool.
+			assert_ok!(TestDex::remove_liquidity(
+				Origin::signed(ALICE),
+				ETH,
+				DOT,
+				alice_shares / 2,
+				0,
+				0
+			));
+			assert_eq!(TestDex::liquidity_pool_storage(trading_pair), (0, 0));
+			assert_eq!(TestDex::total_shares_storage(trading_pair), 0);
+			assert_eq!(TestDex::shares_storage(trading_pair, ALICE), 0);
+			assert_eq!(Currencies::free_balance(DOT, &ALICE), 100_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &ALICE), 200_000 * DOLLARS);
+		});
+}
+
+#[test]
+fn swap_with_exact_supply_should_work() {
+	ExtBuilder::default()
+		.set_liquidation_pool_balance(TestLiquidationPools::pools_account_id(), DOT, 300_000 * DOLLARS)
+		.set_dex_liquidity_pool(ALICE, DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let pools_account_id = TestLiquidationPools::pools_account_id();
+			// 200_000 * 997 / (100_000 + 997) ETH for 1_000 DOT, the fee is 0.3%
+			let expected_target_amount = 1_974_316_068_794_122_597_700;
+
+			assert_noop!(
+				TestDex::swap_with_exact_supply(
+					&pools_account_id,
+					DOT,
+					ETH,
+					1_000 * DOLLARS,
+					expected_target_amount + 1
+				),
+				Error::<TestRuntime>::InsufficientTargetAmount
+			);
+			assert_noop!(
+				TestDex::swap_with_exact_supply(&pools_account_id, DOT, KSM, 1_000 * DOLLARS, 0),
+				Error::<TestRuntime>::LiquidityPoolNotFound
+			);
+
 			assert_eq!(
-				Currencies::free_balance(ETH, &TestLiquidationPools::pools_account_id()),
-				450_000 * DOLLARS
+				TestDex::swap_with_exact_supply(&pools_account_id, DOT, ETH, 1_000 * DOLLARS, expected_target_amount),
+				Ok(expected_target_amount)
 			);
+			let expected_event = Event::TestDex(crate::Event::Swap(
+				pools_account_id,
+				DOT,
+				ETH,
+				1_000 * DOLLARS,
+				expected_target_amount,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
 
 			assert_eq!(
-				Currencies::free_balance(DOT, &TestDex::dex_account_id()),
-				550_000 * DOLLARS
+				TestDex::liquidity_pool_storage(dex_trading_pair(DOT, ETH)),
+				(101_000 * DOLLARS, 200_000 * DOLLARS - expected_target_amount)
 			);
+			assert_eq!(Currencies::free_balance(DOT, &pools_account_id), 299_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &pools_account_id), expected_target_amount);
 			assert_eq!(
-				Currencies::free_balance(ETH, &TestDex::dex_account_id()),
-				450_000 * DOLLARS
+				Currencies::free_balance(DOT, &TestDex::dex_account_id()),
+				101_000 * DOLLARS
 			);
 		});
 }
 
 #[test]
-fn do_swap_with_exact_target_should_work() {
+fn swap_with_exact_target_should_work() {
 	ExtBuilder::default()
-		.set_liquidation_pool_balance(TestLiquidationPools::pools_account_id(), DOT, 300_000 * DOLLARS)
 		.set_liquidation_pool_balance(TestLiquidationPools::pools_account_id(), ETH, 400_000 * DOLLARS)
-		.set_dex_balance(TestDex::dex_account_id(), DOT, 50_000 * DOLLARS)
-		.set_dex_balance(TestDex::dex_account_id(), ETH, 50_000 * DOLLARS)
+		.set_dex_liquidity_pool(ALICE, DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS)
 		.build()
 		.execute_with(|| {
-			assert_eq!(
-				TestDex::do_swap_with_exact_target(
-					&TestLiquidationPools::pools_account_id(),
-					DOT,
+			let pools_account_id = TestLiquidationPools::pools_account_id();
+			// 200_000 * 1_000 / (100_000 - 1_000) / 0.997 ETH for 1_000 DOT, rounded up
+			let expected_supply_amount = 2_026_280_862_790_391_376_150;
+
+			assert_noop!(
+				TestDex::swap_with_exact_target(
+					&pools_account_id,
 					ETH,
-					10_000 * DOLLARS,
-					10_000 * DOLLARS
+					DOT,
+					expected_supply_amount - 1,
+					1_000 * DOLLARS
 				),
-				Ok(10_000 * DOLLARS)
+				Error::<TestRuntime>::ExcessiveSupplyAmount
+			);
+			assert_noop!(
+				TestDex::swap_with_exact_target(&pools_account_id, ETH, DOT, 400_000 * DOLLARS, 100_000 * DOLLARS),
+				Error::<TestRuntime>::InsufficientLiquidity
+			);
+
+			assert_eq!(
+				TestDex::swap_with_exact_target(&pools_account_id, ETH, DOT, 10_000 * DOLLARS, 1_000 * DOLLARS),
+				Ok(expected_supply_amount)
 			);
 			let expected_event = Event::TestDex(crate::Event::Swap(
-				TestLiquidationPools::pools_account_id(),
-				DOT,
+				pools_account_id,
 				ETH,
-				10_000 * DOLLARS,
-				10_000 * DOLLARS,
+				DOT,
+				expected_supply_amount,
+				1_000 * DOLLARS,
 			));
 			assert!(System::events().iter().any(|record| record.event == expected_event));
 
 			assert_eq!(
-				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
-				290_000 * DOLLARS
+				TestDex::liquidity_pool_storage(dex_trading_pair(DOT, ETH)),
+				(99_000 * DOLLARS, 200_000 * DOLLARS + expected_supply_amount)
 			);
 			assert_eq!(
-				Currencies::free_balance(ETH, &TestLiquidationPools::pools_account_id()),
-				410_000 * DOLLARS
+				Currencies::free_balance(ETH, &pools_account_id),
+				400_000 * DOLLARS - expected_supply_amount
 			);
+			assert_eq!(Currencies::free_balance(DOT, &pools_account_id), 1_000 * DOLLARS);
+		});
+}
+
+#[test]
+fn swap_by_path_should_work() {
+	ExtBuilder::default()
+		.set_liquidation_pool_balance(TestLiquidationPools::pools_account_id(), DOT, 10_000 * DOLLARS)
+		.set_dex_liquidity_pool(ALICE, DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS)
+		.set_dex_liquidity_pool(ALICE, ETH, BTC, 200_000 * DOLLARS, 20_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let pools_account_id = TestLiquidationPools::pools_account_id();
+			let path = vec![DOT, ETH, BTC];
+			// 5_000 DOT -> ~9_496 ETH -> ~904 BTC
+			let expected_eth_amount = 9_496_594_751_631_185_407_439;
+			let expected_btc_amount = 904_013_999_539_539_090_725;
 
 			assert_eq!(
-				Currencies::free_balance(DOT, &TestDex::dex_account_id()),
-				60_000 * DOLLARS
+				TestDex::get_target_amounts(&path, 5_000 * DOLLARS),
+				Ok(vec![5_000 * DOLLARS, expected_eth_amount, expected_btc_amount])
+			);
+			assert_noop!(
+				TestDex::swap_with_exact_supply_by_path(&pools_account_id, &[DOT], 5_000 * DOLLARS, 0),
+				Error::<TestRuntime>::InvalidTradingPathLength
+			);
+			assert_noop!(
+				TestDex::swap_with_exact_supply_by_path(&pools_account_id, &[DOT, ETH, BTC, KSM], 5_000 * DOLLARS, 0),
+				Error::<TestRuntime>::InvalidTradingPathLength
+			);
+			// The path can't go through the same liquidity pool twice.
+			assert_noop!(
+				TestDex::swap_with_exact_supply_by_path(&pools_account_id, &[DOT, ETH, DOT], 5_000 * DOLLARS, 0),
+				Error::<TestRuntime>::DuplicateTradingPair
+			);
+			assert_noop!(
+				TestDex::swap_with_exact_target_by_path(
+					&pools_account_id,
+					&[ETH, DOT, ETH],
+					u128::MAX,
+					1_000 * DOLLARS
+				),
+				Error::<TestRuntime>::DuplicateTradingPair
 			);
+			assert_noop!(
+				TestDex::swap_with_exact_supply_by_path(
+					&pools_account_id,
+					&path,
+					5_000 * DOLLARS,
+					expected_btc_amount + 1
+				),
+				Error::<TestRuntime>::InsufficientTargetAmount
+			);
+
+			assert_eq!(
+				TestDex::swap_with_exact_supply_by_path(&pools_account_id, &path, 5_000 * DOLLARS, expected_btc_amount),
+				Ok(expected_btc_amount)
+			);
+			let expected_event = Event::TestDex(crate::Event::Swap(
+				pools_account_id,
+				DOT,
+				BTC,
+				5_000 * DOLLARS,
+				expected_btc_amount,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			assert_eq!(
+				TestDex::liquidity_pool_storage(dex_trading_pair(DOT, ETH)),
+				(105_000 * DOLLARS, 200_000 * DOLLARS - expected_eth_amount)
+			);
+			assert_eq!(
+				TestDex::liquidity_pool_storage(dex_trading_pair(ETH, BTC)),
+				(
+					20_000 * DOLLARS - expected_btc_amount,
+					200_000 * DOLLARS + expected_eth_amount
+				)
+			);
+			assert_eq!(Currencies::free_balance(DOT, &pools_account_id), 5_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &pools_account_id), 0);
+			assert_eq!(Currencies::free_balance(BTC, &pools_account_id), expected_btc_amount);
 			assert_eq!(
 				Currencies::free_balance(ETH, &TestDex::dex_account_id()),
-				40_000 * DOLLARS
+				400_000 * DOLLARS
 			);
+		});
+}
 
-			assert_err!(
-				TestDex::do_swap_with_exact_target(
-					&TestLiquidationPools::pools_account_id(),
-					DOT,
-					ETH,
-					100_000 * DOLLARS,
-					100_000 * DOLLARS
+#[test]
+fn get_best_path_should_work() {
+	ExtBuilder::default()
+		// 1 BTC = 5 DOT through ETH
+		.set_dex_liquidity_pool(ALICE, DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS)
+		.set_dex_liquidity_pool(ALICE, ETH, BTC, 200_000 * DOLLARS, 20_000 * DOLLARS)
+		// 1 BTC = 4 DOT in the shallow direct pool
+		.set_dex_liquidity_pool(ALICE, DOT, BTC, 10_000 * DOLLARS, 2_500 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let mut paths = TestDex::get_trading_paths(DOT, BTC);
+			paths.sort();
+			assert_eq!(paths, vec![vec![DOT, BTC], vec![DOT, ETH, BTC]]);
+			assert!(TestDex::get_trading_paths(DOT, KSM).is_empty());
+
+			// The direct pool is better for small amounts, the deep pools are better for large ones.
+			assert_eq!(
+				TestDex::get_best_path_for_exact_supply(DOT, BTC, 1_000 * DOLLARS),
+				Some((vec![DOT, BTC], 226_652_723_470_037_282_895))
+			);
+			assert_eq!(
+				TestDex::get_best_path_for_exact_supply(DOT, BTC, 5_000 * DOLLARS),
+				Some((vec![DOT, ETH, BTC], 904_013_999_539_539_090_725))
+			);
+			assert_eq!(
+				TestDex::get_best_path_for_exact_target(DOT, BTC, 200 * DOLLARS),
+				Some((vec![DOT, BTC], 872_181_762_679_342_374_952))
+			);
+			assert_eq!(
+				TestDex::get_best_path_for_exact_target(DOT, BTC, 1_000 * DOLLARS),
+				Some((vec![DOT, ETH, BTC], 5_589_973_956_870_332_336_810))
+			);
+			assert_eq!(
+				TestDex::get_supply_amount_by_path(&[DOT, ETH, BTC], 1_000 * DOLLARS),
+				Some(5_589_973_956_870_332_336_810)
+			);
+			assert_eq!(TestDex::get_supply_amount_by_path(&[DOT, KSM], 1_000 * DOLLARS), None);
+			assert_eq!(TestDex::get_best_path_for_exact_supply(DOT, KSM, 1_000 * DOLLARS), None);
+		});
+}
+
+#[test]
+fn cumulative_prices_should_accumulate_on_swaps_and_liquidity_changes() {
+	ExtBuilder::default()
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 100_000 * DOLLARS)
+		.set_dex_liquidity_pool(BOB, DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let trading_pair = dex_trading_pair(DOT, ETH);
+			let price = |n: u128| Price::saturating_from_integer(n).into_inner();
+
+			// The accumulators and the averaging window start at the creation of the pool.
+			let initial = CumulativePrices {
+				price_0_cumulative: 0,
+				price_1_cumulative: 0,
+				block_number: 0,
+			};
+			assert_eq!(TestDex::cumulative_prices_storage(trading_pair), initial);
+			assert_eq!(TestDex::price_checkpoint_storage(trading_pair), Some(initial));
+
+			// 1 DOT = 2 ETH lasted for 4 blocks.
+			System::set_block_number(4);
+			assert_ok!(TestDex::add_liquidity(
+				Origin::signed(ALICE),
+				DOT,
+				ETH,
+				10_000 * DOLLARS,
+				20_000 * DOLLARS,
+				0
+			));
+			assert_eq!(
+				TestDex::cumulative_prices_storage(trading_pair),
+				CumulativePrices {
+					price_0_cumulative: price(8),
+					price_1_cumulative: price(2),
+					block_number: 4,
+				}
+			);
+
+			// The price lasted for 2 more blocks before the swap.
+			System::set_block_number(6);
+			assert_ok!(TestDex::swap_with_exact_supply(&ALICE, DOT, ETH, 10_000 * DOLLARS, 0));
+			assert_eq!(
+				TestDex::cumulative_prices_storage(trading_pair),
+				CumulativePrices {
+					price_0_cumulative: price(12),
+					price_1_cumulative: price(3),
+					block_number: 6,
+				}
+			);
+			assert_eq!(TestDex::average_prices_storage(trading_pair), None);
+
+			// The averaging window is closed by the next update after `PriceAveragingPeriod` blocks.
+			let (reserve_0, reserve_1) = TestDex::liquidity_pool_storage(trading_pair);
+			let price_0 = Price::saturating_from_rational(reserve_1, reserve_0).into_inner();
+			let price_1 = Price::saturating_from_rational(reserve_0, reserve_1).into_inner();
+			let expected_average_prices = (
+				Price::from_inner((price(12) + price_0 * 4) / 10),
+				Price::from_inner((price(3) + price_1 * 4) / 10),
+			);
+			System::set_block_number(10);
+			let alice_shares = TestDex::shares_storage(trading_pair, ALICE);
+			assert_ok!(TestDex::remove_liquidity(
+				Origin::signed(ALICE),
+				DOT,
+				ETH,
+				alice_shares,
+				0,
+				0
+			));
+			assert_eq!(
+				TestDex::average_prices_storage(trading_pair),
+				Some(expected_average_prices)
+			);
+			assert_eq!(
+				TestDex::price_checkpoint_storage(trading_pair),
+				Some(TestDex::cumulative_prices_storage(trading_pair))
+			);
+		});
+}
+
+#[test]
+fn cumulative_prices_should_be_removed_with_liquidity_pool() {
+	ExtBuilder::default()
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 100_000 * DOLLARS)
+		.set_dex_liquidity_pool(BOB, DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let trading_pair = dex_trading_pair(DOT, ETH);
+
+			// The swap closes the averaging window.
+			System::set_block_number(10);
+			assert_ok!(TestDex::swap_with_exact_supply(&ALICE, DOT, ETH, 10_000 * DOLLARS, 0));
+			assert!(TestDex::average_prices_storage(trading_pair).is_some());
+
+			let bob_shares = TestDex::shares_storage(trading_pair, BOB);
+			assert_ok!(TestDex::remove_liquidity(
+				Origin::signed(BOB),
+				DOT,
+				ETH,
+				bob_shares,
+				0,
+				0
+			));
+			assert_eq!(
+				TestDex::cumulative_prices_storage(trading_pair),
+				CumulativePrices::default()
+			);
+			assert_eq!(TestDex::price_checkpoint_storage(trading_pair), None);
+			assert_eq!(TestDex::average_prices_storage(trading_pair), None);
+			assert_eq!(EthPriceProvider::get(&DOT), None);
+
+			// The accumulators and the averaging window start again with the new liquidity.
+			System::set_block_number(12);
+			assert_ok!(TestDex::add_liquidity(
+				Origin::signed(ALICE),
+				DOT,
+				ETH,
+				10_000 * DOLLARS,
+				20_000 * DOLLARS,
+				0
+			));
+			let initial = CumulativePrices {
+				price_0_cumulative: 0,
+				price_1_cumulative: 0,
+				block_number: 12,
+			};
+			assert_eq!(TestDex::cumulative_prices_storage(trading_pair), initial);
+			assert_eq!(TestDex::price_checkpoint_storage(trading_pair), Some(initial));
+			assert_eq!(TestDex::average_prices_storage(trading_pair), None);
+		});
+}
+
+#[test]
+fn dex_price_provider_should_work() {
+	ExtBuilder::default()
+		.set_user_balance(BOB, DOT, 100_000 * DOLLARS)
+		.set_dex_liquidity_pool(ALICE, DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let trading_pair = dex_trading_pair(DOT, ETH);
+
+			// No complete averaging window yet.
+			System::set_block_number(9);
+			assert_eq!(EthPriceProvider::get(&DOT), None);
+			assert_eq!(EthPriceProvider::get(&ETH), Some(Price::one()));
+			assert_eq!(EthPriceProvider::get(&KSM), None);
+
+			// The window has ended but hasn't been closed yet, the average is calculated on the fly.
+			System::set_block_number(10);
+			assert_eq!(EthPriceProvider::get(&DOT), Some(Price::saturating_from_integer(2)));
+			assert_eq!(
+				TestDex::get_average_price(ETH, DOT),
+				Some(Price::saturating_from_rational(1, 2))
+			);
+			assert_eq!(TestDex::average_prices_storage(trading_pair), None);
+
+			// The swap closes the window, the following swaps don't affect the average price until
+			// the end of the next window.
+			assert_ok!(TestDex::swap_with_exact_supply(&BOB, DOT, ETH, 10_000 * DOLLARS, 0));
+			assert_eq!(
+				TestDex::average_prices_storage(trading_pair),
+				Some((Price::saturating_from_integer(2), Price::saturating_from_rational(1, 2)))
+			);
+			System::set_block_number(19);
+			assert_eq!(EthPriceProvider::get(&DOT), Some(Price::saturating_from_integer(2)));
+		});
+}
+
+#[test]
+fn liquidity_providers_should_accrue_and_claim_mnt() {
+	ExtBuilder::default()
+		.set_user_balance(BOB, DOT, 100 * DOLLARS)
+		.set_user_balance(BOB, ETH, 100 * DOLLARS)
+		.set_dex_liquidity_pool(ALICE, DOT, ETH, 100 * DOLLARS, 100 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::deposit(
+				MNT,
+				&TestMntToken::get_account_id(),
+				1_000 * DOLLARS
+			));
+			assert_ok!(TestMntToken::set_dex_pair_speed(admin_origin(), DOT, ETH, DOLLARS));
+
+			// Alice is the only liquidity provider for 10 blocks.
+			System::set_block_number(11);
+			assert_ok!(TestDex::add_liquidity(
+				Origin::signed(BOB),
+				DOT,
+				ETH,
+				100 * DOLLARS,
+				100 * DOLLARS,
+				0
+			));
+			assert_ok!(TestDex::claim_mnt(Origin::signed(ALICE), vec![(ETH, DOT)]));
+			let expected_event = Event::TestDex(crate::Event::Claimed(ALICE));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(Currencies::free_balance(MNT, &ALICE), 10 * DOLLARS);
+			assert_eq!(Currencies::free_balance(MNT, &BOB), Balance::zero());
+
+			// MNT is distributed to the provider when the liquidity is removed.
+			System::set_block_number(21);
+			let bob_shares = TestDex::shares_storage(dex_trading_pair(DOT, ETH), BOB);
+			assert_ok!(TestDex::remove_liquidity(
+				Origin::signed(BOB),
+				DOT,
+				ETH,
+				bob_shares,
+				0,
+				0
+			));
+			assert_eq!(Currencies::free_balance(MNT, &BOB), 5 * DOLLARS);
+
+			assert_noop!(
+				TestDex::claim_mnt(Origin::signed(ALICE), vec![(MDOT, ETH)]),
+				Error::<TestRuntime>::NotValidCurrencyId
+			);
+			assert_noop!(
+				TestDex::claim_mnt(
+					Origin::signed(ALICE),
+					vec![(DOT, ETH), (DOT, KSM), (DOT, BTC), (ETH, KSM)]
 				),
-				Error::<TestRuntime>::InsufficientDexBalance
+				Error::<TestRuntime>::TooManyTradingPairs
 			);
 		});
 }
@@ -0,0 +1,107 @@
+// This file is part of Minterest.
+
+// Copyright (C) 2021 Minterest finance.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for dex
+//!
+//! The weights are estimated and aren't generated by the benchmark CLI yet. Regenerate them
+//! on the reference hardware with the following command.
+
+// Command:
+// ./target/release/minterest
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=dex
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=./pallets/dex/src/weights.rs
+// --template=./templates/weight-template-for-pallet.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for dex.
+pub trait WeightInfo {
+	fn add_liquidity() -> Weight;
+	fn remove_liquidity() -> Weight;
+	fn claim_mnt(n: u32, ) -> Weight;
+}
+
+/// Weights for dex using the Minterest node and recommended hardware.
+pub struct MinterestWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
+	// Estimated as the liquidity change with the cumulative price update and the MNT distribution
+	// to the provider. Regenerate with the `add_liquidity` benchmark.
+	fn add_liquidity() -> Weight {
+		(98_514_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Estimated as the liquidity change with the cumulative price update and the MNT distribution
+	// to the provider. Regenerate with the `remove_liquidity` benchmark.
+	fn remove_liquidity() -> Weight {
+		(94_370_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Estimated as the MNT distribution to the provider in each of `n` trading pairs and the
+	// transfer of the accrued MNT. Regenerate with the `claim_mnt` benchmark.
+	fn claim_mnt(n: u32, ) -> Weight {
+		(25_310_000 as Weight)
+			.saturating_add((46_512_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Estimated as the liquidity change with the cumulative price update and the MNT distribution
+	// to the provider. Regenerate with the `add_liquidity` benchmark.
+	fn add_liquidity() -> Weight {
+		(98_514_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	// Estimated as the liquidity change with the cumulative price update and the MNT distribution
+	// to the provider. Regenerate with the `remove_liquidity` benchmark.
+	fn remove_liquidity() -> Weight {
+		(94_370_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	// Estimated as the MNT distribution to the provider in each of `n` trading pairs and the
+	// transfer of the accrued MNT. Regenerate with the `claim_mnt` benchmark.
+	fn claim_mnt(n: u32, ) -> Weight {
+		(25_310_000 as Weight)
+			.saturating_add((46_512_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+}
@@ -21,8 +21,9 @@ mod tests {
 	pub use minterest_primitives::currency::CurrencyType::{UnderlyingAsset, WrappedToken};
 	use minterest_primitives::{Balance, CurrencyId, Price, Rate};
 	use minterest_protocol::{Error as MinterestProtocolError, PoolInitData};
+	use mnt_token::RewardTarget;
 	use orml_traits::{parameter_type_with_key, MultiCurrency};
-	use pallet_traits::{ControllerManager, CurrencyConverter, PoolsManager, PricesManager};
+	use pallet_traits::{ControllerManager, CurrencyConverter, PoolsManager, PriceSource};
 	use sp_core::H256;
 	use sp_runtime::{
 		testing::{Header, TestXt},
@@ -56,7 +57,7 @@ mod tests {
 			Tokens: orml_tokens::{Pallet, Storage, Call, Event<T>, Config<T>},
 			Currencies: orml_currencies::{Pallet, Call, Event<T>},
 			MinterestProtocol: minterest_protocol::{Pallet, Storage, Call, Event<T>},
-			TestPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+			TestPools: liquidity_pools::{Pallet, Storage, Call, Event, Config<T>},
 			TestLiquidationPools: liquidation_pools::{Pallet, Storage, Call, Event<T>, Config<T>},
 			TestController: controller::{Pallet, Storage, Call, Event, Config<T>},
 			TestMinterestModel: minterest_model::{Pallet, Storage, Call, Event, Config<T>},
@@ -117,14 +118,10 @@ mod tests {
 		}
 	}
 
-	impl PricesManager<CurrencyId> for MockPriceSource {
+	impl PriceSource<CurrencyId> for MockPriceSource {
 		fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
 			UNDERLYING_PRICE.with(|v| v.borrow().get(&currency_id).copied())
 		}
-
-		fn lock_price(_currency_id: CurrencyId) {}
-
-		fn unlock_price(_currency_id: CurrencyId) {}
 	}
 
 	thread_local! {
@@ -5,9 +5,9 @@ mod tests {
 	use crate::tests::*;
 
 	fn test_mnt_speeds(speed_dot: Balance, speed_eth: Balance, speed_btc: Balance) {
-		assert_eq!(TestMntToken::mnt_speed_storage(DOT), speed_dot);
-		assert_eq!(TestMntToken::mnt_speed_storage(ETH), speed_eth);
-		assert_eq!(TestMntToken::mnt_speed_storage(BTC), speed_btc);
+		assert_eq!(TestMntToken::mnt_speed_storage(RewardTarget::Pool(DOT)), speed_dot);
+		assert_eq!(TestMntToken::mnt_speed_storage(RewardTarget::Pool(ETH)), speed_eth);
+		assert_eq!(TestMntToken::mnt_speed_storage(RewardTarget::Pool(BTC)), speed_btc);
 	}
 
 	// This scenario works with two users and three pools.
@@ -48,10 +48,18 @@ mod tests {
 			.mnt_claim_threshold(dollars(100))
 			.build()
 			.execute_with(|| {
-				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(DOT));
-				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(ETH));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(BTC));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(KSM));
+				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					DOT
+				)));
+				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					ETH
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					BTC
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					KSM
+				)));
 				// Set initial state of pools for distribution MNT tokens.
 				vec![DOT, ETH, BTC].into_iter().for_each(|pool_id| {
 					assert_ok!(MinterestProtocol::deposit_underlying(
@@ -269,10 +277,18 @@ mod tests {
 			.mnt_claim_threshold(dollars(100))
 			.build()
 			.execute_with(|| {
-				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(DOT));
-				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(ETH));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(BTC));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(KSM));
+				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					DOT
+				)));
+				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					ETH
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					BTC
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					KSM
+				)));
 				// Set initial state of pools for distribution MNT tokens.
 				vec![DOT, ETH].into_iter().for_each(|pool_id| {
 					assert_ok!(MinterestProtocol::deposit_underlying(
@@ -414,10 +430,18 @@ mod tests {
 			.mnt_account_balance(ONE_HUNDRED_THOUSAND)
 			.build()
 			.execute_with(|| {
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(DOT));
-				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(ETH));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(BTC));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(KSM));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					DOT
+				)));
+				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					ETH
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					BTC
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					KSM
+				)));
 				// Set initial state of pools for distribution MNT tokens.
 				System::set_block_number(10);
 				// Alice starts taking part in the distribution (ETH) from block 10
@@ -521,10 +545,18 @@ mod tests {
 			.mnt_account_balance(ONE_HUNDRED_THOUSAND)
 			.build()
 			.execute_with(|| {
-				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(DOT));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(ETH));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(BTC));
-				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(KSM));
+				assert!(mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					DOT
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					ETH
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					BTC
+				)));
+				assert!(!mnt_token::MntSpeedStorage::<Test>::contains_key(RewardTarget::Pool(
+					KSM
+				)));
 				// Initialize distribution of MNT tokens.
 				assert_ok!(MinterestProtocol::deposit_underlying(
 					admin_origin(),
@@ -0,0 +1,93 @@
+//! RPC interface for the liquidation pools pallet.
+//!
+//! RPC installation: `rpc/src/lib.rc`
+//!
+//! Corresponding runtime API declaration: `pallets/liquidation-pools/rpc/runtime-api/src/lib.rs`
+//! Corresponding runtime API implementation: `runtime/src/lib.rs`
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use liquidation_pools_rpc_runtime_api::{
+	BalancingPlan, BalancingSale, LiquidationPoolBalancingInfo, LiquidationPoolsRuntimeApi,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc]
+/// Base trait for RPC interface of liquidation pools
+pub trait LiquidationPoolsRpcApi<BlockHash> {
+	/// Returns the state of liquidation pools and the sales the offchain worker would submit to
+	/// balance the pools at the block.
+	///
+	/// Parameters:
+	///  - `&self` :  Self reference
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	/// Return:
+	/// - [`pools`](`BalancingPlan::pools`): per-pool balance, ideal balance, oversupply,
+	/// shortfall and deviation borders in USD.
+	/// - [`sales`](`BalancingPlan::sales`): USD amounts to transfer from the oversupplied pools
+	/// to the pools in shortfall. The status of a sale tells whether the offchain worker submits
+	/// it or why it doesn't: the pools are postponed after failed swaps or take part in an
+	/// auction, or there is no acceptable trading path on DEX.
+	///
+	/// # Example:
+	/// ``` ignore
+	/// curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0",
+	/// "id":1, "method":"liquidationPools_getBalancingPlan", "params": []}'
+	/// ```
+	#[doc(alias = "MNT RPC")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[rpc(name = "liquidationPools_getBalancingPlan")]
+	fn get_balancing_plan(&self, at: Option<BlockHash>) -> Result<Option<BalancingPlan>>;
+}
+
+/// A struct that implements the `LiquidationPoolsRpcApi`.
+pub struct LiquidationPoolsRpcImpl<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> LiquidationPoolsRpcImpl<C, B> {
+	/// Create new `LiquidationPoolsRpcImpl` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// Implementation of 'LiquidationPoolsRpcApi'
+impl<C, Block> LiquidationPoolsRpcApi<<Block as BlockT>::Hash> for LiquidationPoolsRpcImpl<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: LiquidationPoolsRuntimeApi<Block>,
+{
+	fn get_balancing_plan(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Option<BalancingPlan>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		api.get_balancing_plan(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get balancing plan.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
@@ -5,6 +5,52 @@
 //! Liquidation Pools are responsible for holding funds for automatic liquidation.
 //! This module has offchain worker implemented which is running constantly.
 //! Offchain worker keeps pools in balance to avoid lack of funds for liquidation.
+//!
+//! ### Protocol-owned liquidity
+//!
+//! A configurable portion (`protocol_owned_liquidity_factor`) of the liquidation pool balance above
+//! its ideal balance can be deposited by governance into the liquidity pool as protocol-owned
+//! supply, so that it earns interest instead of sitting idle. Wrapped tokens received for this
+//! supply are held by the liquidation pools account and accrue MNT as any other supply.
+//! Protocol-owned supply is automatically redeemed back into the liquidation pool when its balance
+//! is not enough for liquidations.
+//!
+//! ### Slippage-bounded balancing
+//!
+//! The maximum supply amount of a balancing swap is derived from the oracle prices and increased by
+//! the maximum slippage configured for the pair of pools. The offchain worker quotes the swap on DEX
+//! before submitting it. A swap with excessive slippage or a failed swap postpones balancing of
+//! the pair of pools for `BalancingBackoffPeriod` blocks, the period doubles with each consecutive
+//! failure.
+//!
+//! ### Auctions
+//!
+//! Instead of swapping on DEX, the oversupplied asset of a pool in `BalancingMode::Auction` is
+//! offered through a fixed-duration (`AuctionDuration`) auction. The lot is the maximum supply
+//! amount of the sale computed on-chain, participants bid the shortfall asset starting from the
+//! target amount of the sale. A pool takes part in at most one auction at a time. The lot and the
+//! bids are held by the auctions account. Ended auctions are settled automatically at
+//! the beginning of the block: the lot goes to the highest bidder and the bid goes to the
+//! liquidation pool. An auction without bids returns the lot and postpones balancing of the pools.
+//!
+//! ### At-risk debt
+//!
+//! By default the ideal balance of a liquidation pool is `balance_ratio` of the pool borrows. The
+//! ideal balance of a pool in `IdealBalanceMode::AtRiskDebt` is the borrow value in the pool of the
+//! accounts whose health factor (total collateral to total borrow value) is below
+//! `at_risk_health_threshold` (1.1 until set by governance). The at-risk debt is updated at the
+//! beginning of the block by a pass over the pool members, at most `AtRiskDebtAccountsPerBlock`
+//! members are checked per block. Until the first pass is complete the ideal balance is calculated
+//! as in `IdealBalanceMode::BorrowRatio`. In both modes the ideal balance is limited by
+//! `max_ideal_balance_usd`.
+//!
+//! ### Treasury withdrawals
+//!
+//! Governance can withdraw the liquidation pool surplus above
+//! `max_ideal_balance_usd * (1 + deviation_threshold)` to the treasury account. The treasury account
+//! is set by governance, withdrawals are not allowed until it is set. The value of the
+//! withdrawals is limited per call (`MaxTreasuryWithdrawalPerCall`) and per period of
+//! `TreasuryWithdrawalPeriod` blocks (`MaxTreasuryWithdrawalPerPeriod`).
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -18,17 +64,21 @@ use frame_system::{
 };
 use liquidity_pools::PoolData;
 use minterest_primitives::{
-	arithmetic::sum_with_mult_result, currency::CurrencyType::UnderlyingAsset, Balance, CurrencyId, OffchainErr, Rate,
+	arithmetic::sum_with_mult_result, currency::CurrencyType::UnderlyingAsset, Balance, CurrencyId, OffchainErr,
+	Operation, Price, Rate,
 };
 pub use module::*;
 use orml_traits::MultiCurrency;
 use pallet_traits::{
 	ControllerManager, CurrencyConverter, DEXManager, LiquidationPoolsManager, LiquidityPoolStorageProvider,
-	PoolsManager, PricesManager,
+	MntManager, PoolsManager, PriceSource,
 };
 use sp_runtime::{
-	offchain::storage_lock::{StorageLock, Time},
-	traits::{AccountIdConversion, CheckedMul, One, Zero},
+	offchain::{
+		storage::StorageValueRef,
+		storage_lock::{StorageLock, Time},
+	},
+	traits::{AccountIdConversion, CheckedMul, One, Saturating, Zero},
 	transaction_validity::TransactionPriority,
 	DispatchResult, FixedPointNumber, RuntimeDebug,
 };
@@ -45,6 +95,13 @@ pub mod weights;
 pub use weights::WeightInfo;
 
 const OFFCHAIN_LIQUIDATION_WORKER_LOCK: &[u8] = b"pallets/liquidation-pools/lock/";
+const OFFCHAIN_BALANCING_BACKOFF_PREFIX: &[u8] = b"pallets/liquidation-pools/backoff/";
+/// The backoff period stops doubling after this number of consecutive failures.
+const MAX_BACKOFF_EXPONENT: u32 = 6;
+/// The amounts of a balancing call may differ from the amounts of the sale computed on-chain by
+/// 1/SALE_AMOUNT_TOLERANCE_DIVISOR, as prices and pool balances can change before the transaction
+/// is included in a block.
+const SALE_AMOUNT_TOLERANCE_DIVISOR: Balance = 100;
 
 /// Liquidation Pool metadata
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -60,6 +117,66 @@ pub struct LiquidationPoolData {
 	pub max_ideal_balance_usd: Option<Balance>,
 }
 
+/// Consecutive failures of the balancing swaps between a pair of liquidation pools.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct BalancingBackoff<BlockNumber> {
+	/// Number of consecutive failed (or rejected due to excessive slippage) swaps.
+	pub failures: u32,
+	/// Block number starting from which the balancing swap can be retried.
+	pub retry_block: BlockNumber,
+}
+
+/// The way the oversupplied asset of a liquidation pool is sold during balancing.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum BalancingMode {
+	/// Swap on DEX.
+	Dex,
+	/// Fixed-duration auction.
+	Auction,
+}
+
+impl Default for BalancingMode {
+	fn default() -> Self {
+		BalancingMode::Dex
+	}
+}
+
+/// The way the ideal balance of a liquidation pool is calculated.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum IdealBalanceMode {
+	/// `balance_ratio` of the pool borrows.
+	BorrowRatio,
+	/// Borrows of the accounts with health factor below `at_risk_health_threshold`.
+	AtRiskDebt,
+}
+
+impl Default for IdealBalanceMode {
+	fn default() -> Self {
+		IdealBalanceMode::BorrowRatio
+	}
+}
+
+pub type AuctionId = u32;
+
+/// Auction of the oversupplied liquidation pool asset.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq)]
+pub struct AuctionInfo<AccountId, BlockNumber> {
+	/// The pool which asset is offered.
+	pub supply_pool_id: CurrencyId,
+	/// The pool which asset is bid.
+	pub target_pool_id: CurrencyId,
+	/// The amount of `supply_pool_id` asset offered.
+	pub lot: Balance,
+	/// The minimum amount of `target_pool_id` asset to bid.
+	pub min_bid: Balance,
+	/// The highest bid: (bidder, amount of `target_pool_id` asset).
+	pub bid: Option<(AccountId, Balance)>,
+	/// The auction is settled at this block.
+	pub end_block: BlockNumber,
+}
+
 type BalanceResult = sp_std::result::Result<Balance, DispatchError>;
 
 #[frame_support::pallet]
@@ -89,7 +206,7 @@ pub mod module {
 		type LiquidationPoolAccountId: Get<Self::AccountId>;
 
 		/// The price source of currencies
-		type PriceSource: PricesManager<CurrencyId>;
+		type PriceSource: PriceSource<CurrencyId>;
 
 		/// The basic liquidity pools manager.
 		type LiquidityPoolsManager: LiquidityPoolStorageProvider<Self::AccountId, PoolData>
@@ -108,6 +225,45 @@ pub mod module {
 
 		/// Public API of controller pallet
 		type ControllerManager: ControllerManager<Self::AccountId>;
+
+		/// Public API of MNT token pallet, distributes MNT to protocol-owned supply.
+		type MntManager: MntManager<Self::AccountId>;
+
+		#[pallet::constant]
+		/// The number of blocks for which balancing of a pair of pools is postponed after a failed
+		/// swap. The period doubles with each consecutive failure.
+		type BalancingBackoffPeriod: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The number of blocks the auction of the oversupplied asset lasts.
+		type AuctionDuration: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The maximum value in USD withdrawn to the treasury by a single call.
+		type MaxTreasuryWithdrawalPerCall: Get<Balance>;
+
+		#[pallet::constant]
+		/// The maximum value in USD withdrawn to the treasury during `TreasuryWithdrawalPeriod`.
+		type MaxTreasuryWithdrawalPerPeriod: Get<Balance>;
+
+		#[pallet::constant]
+		/// The number of blocks in the period of the treasury withdrawals limit.
+		type TreasuryWithdrawalPeriod: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The maximum number of members of a pool in `IdealBalanceMode::AtRiskDebt` checked per
+		/// block to update the at-risk debt of the pool. Must be greater than zero.
+		type AtRiskDebtAccountsPerBlock: Get<u32>;
+
+		#[pallet::constant]
+		/// The maximum slippage of the balancing swaps of a pair of pools for which it is not set
+		/// by `set_max_slippage`. Must be within [0, 1].
+		type DefaultMaxSlippage: Get<Rate>;
+
+		#[pallet::constant]
+		/// The maximum number of currencies in the trading path of the balancing swaps. Must match
+		/// the trading path limit of DEX.
+		type TradingPathLimit: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -136,6 +292,42 @@ pub mod module {
 		ZeroBalanceTransaction,
 		/// Wrong state for balansing switcher. QA only!
 		BalacingStateChangeError,
+		/// Value must be in range [0..1]
+		NotValidProtocolOwnedLiquidityFactorValue,
+		/// Operation (deposit) is paused in the liquidity pool.
+		OperationPaused,
+		/// There is no trading path between the pools on DEX.
+		TradingPathNotFound,
+		/// The trading path doesn't lead from the supply pool to the target pool.
+		InvalidTradingPath,
+		/// Value must be in range [0..1]
+		NotValidMaxSlippageValue,
+		/// Supply and target pools must be different.
+		IdenticalPools,
+		/// The amount to be sold on DEX exceeds the maximum supply amount.
+		ExcessiveSlippage,
+		/// The balancing swap doesn't match any sale required for balancing of the pools.
+		SaleNotRequired,
+		/// Balancing of the pools is postponed after failed swaps.
+		BalancingPostponed,
+		/// The pool takes part in the auction that is not settled yet.
+		AuctionInProgress,
+		/// Could not find an auction with required id.
+		AuctionNotFound,
+		/// The auction has ended and is waiting for settlement.
+		AuctionEnded,
+		/// The bid must be not less than the minimum bid and greater than the highest bid.
+		BidTooLow,
+		/// Value must be greater than or equal to 1.
+		NotValidHealthThresholdValue,
+		/// The withdrawal exceeds the liquidation pool surplus above the maximum ideal balance.
+		WithdrawalExceedsSurplus,
+		/// The withdrawal exceeds the limit of a single treasury withdrawal.
+		WithdrawalLimitExceeded,
+		/// The withdrawal exceeds the limit of treasury withdrawals for the current period.
+		PeriodWithdrawalLimitExceeded,
+		/// The treasury account is not set.
+		TreasuryAccountNotSet,
 	}
 
 	#[pallet::event]
@@ -154,6 +346,41 @@ pub mod module {
 		TransferToLiquidationPool(CurrencyId, Balance, T::AccountId),
 		/// Pool balancing state switched: \[new_state\]. QA only!
 		PoolBalacingStateChanged(bool),
+		///  Protocol-owned liquidity factor has been successfully changed: \[pool_id, new_factor\]
+		ProtocolOwnedLiquidityFactorChanged(CurrencyId, Rate),
+		/// Idle liquidation pool balance deposited into the liquidity pool:
+		/// \[pool_id, underlying_amount, wrapped_amount\]
+		ProtocolOwnedLiquidityDeposited(CurrencyId, Balance, Balance),
+		/// Protocol-owned supply redeemed back into the liquidation pool:
+		/// \[pool_id, underlying_amount, wrapped_amount\]
+		ProtocolOwnedLiquidityRedeemed(CurrencyId, Balance, Balance),
+		///  Maximum slippage has been successfully changed:
+		/// \[supply_pool_id, target_pool_id, new_max_slippage\]
+		MaxSlippageChanged(CurrencyId, CurrencyId, Rate),
+		/// Balancing swap failed, balancing of the pools is postponed:
+		/// \[supply_pool_id, target_pool_id, retry_block_number\]
+		BalancingSwapFailed(CurrencyId, CurrencyId, T::BlockNumber),
+		///  Balancing mode has been successfully changed: \[pool_id, new_mode\]
+		BalancingModeChanged(CurrencyId, BalancingMode),
+		/// Auction of the oversupplied asset started:
+		/// \[auction_id, supply_pool_id, target_pool_id, lot, min_bid, end_block_number\]
+		AuctionStarted(AuctionId, CurrencyId, CurrencyId, Balance, Balance, T::BlockNumber),
+		/// New highest bid: \[auction_id, who, amount\]
+		AuctionBid(AuctionId, T::AccountId, Balance),
+		/// The lot is transferred to the highest bidder: \[auction_id, who, amount\]
+		AuctionSettled(AuctionId, T::AccountId, Balance),
+		/// The auction ended without bids, the lot is returned to the liquidation pool:
+		/// \[auction_id\]
+		AuctionCancelled(AuctionId),
+		///  Ideal balance mode has been successfully changed: \[pool_id, new_mode\]
+		IdealBalanceModeChanged(CurrencyId, IdealBalanceMode),
+		///  At-risk health threshold has been successfully changed: \[new_threshold_value\]
+		AtRiskHealthThresholdChanged(Rate),
+		/// Liquidation pool surplus withdrawn to the treasury:
+		/// \[pool_id, underlying_amount, amount_usd\]
+		WithdrawnToTreasury(CurrencyId, Balance, Balance),
+		/// Treasury account has been successfully changed: \[new_treasury_account\]
+		TreasuryAccountChanged(T::AccountId),
 	}
 
 	/// Return parameters for liquidation pool configuration.
@@ -175,6 +402,176 @@ pub mod module {
 	pub type LiquidationPoolDataStorage<T: Config> =
 		StorageMap<_, Twox64Concat, CurrencyId, LiquidationPoolData, ValueQuery>;
 
+	/// The portion of the liquidation pool balance above the ideal balance, that is deposited
+	/// into the liquidity pool as protocol-owned supply. Zero value disables the deposits.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::protocol_owned_liquidity_factor_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_owned_liquidity_factor_storage)]
+	pub type ProtocolOwnedLiquidityFactorStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, ValueQuery>;
+
+	/// The maximum slippage of the balancing swaps from the supply pool to the target pool. The
+	/// maximum supply amount is the oracle equivalent of the target amount increased by this value.
+	/// Defaults to `DefaultMaxSlippage`.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::max_slippage_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn max_slippage_storage)]
+	pub type MaxSlippageStorage<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyId,
+		Twox64Concat,
+		CurrencyId,
+		Rate,
+		ValueQuery,
+		MaxSlippageDefault<T>,
+	>;
+
+	/// The maximum slippage of the balancing swaps until it is set by governance.
+	#[pallet::type_value]
+	pub fn MaxSlippageDefault<T: Config>() -> Rate {
+		T::DefaultMaxSlippage::get()
+	}
+
+	/// Consecutive failures of the balancing swaps from the supply pool to the target pool.
+	/// Balancing of the pools is postponed until `retry_block`.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::balancing_backoff_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn balancing_backoff_storage)]
+	pub type BalancingBackoffStorage<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyId,
+		Twox64Concat,
+		CurrencyId,
+		BalancingBackoff<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The way the oversupplied asset of the pool is sold during balancing.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::balancing_mode_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn balancing_mode_storage)]
+	pub type BalancingModeStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, BalancingMode, ValueQuery>;
+
+	/// Auctions that are not settled yet.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::auctions_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_storage)]
+	pub type AuctionsStorage<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, AuctionInfo<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// The auction that is not settled yet, in which the pool takes part.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::pool_auction_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn pool_auction_storage)]
+	pub type PoolAuctionStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, AuctionId, OptionQuery>;
+
+	/// The id of the next auction.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::next_auction_id_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn next_auction_id_storage)]
+	pub type NextAuctionIdStorage<T: Config> = StorageValue<_, AuctionId, ValueQuery>;
+
+	/// The way the ideal balance of the pool is calculated.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::ideal_balance_mode_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn ideal_balance_mode_storage)]
+	pub type IdealBalanceModeStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, IdealBalanceMode, ValueQuery>;
+
+	/// The health factor below which the borrows of an account are considered to be at risk of
+	/// liquidation. Used to calculate the ideal balance of the pools in `IdealBalanceMode::AtRiskDebt`.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::at_risk_health_threshold_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn at_risk_health_threshold_storage)]
+	pub type AtRiskHealthThresholdStorage<T: Config> =
+		StorageValue<_, Rate, ValueQuery, AtRiskHealthThresholdDefault<T>>;
+
+	/// The health factor below which borrows are at risk until the threshold is set by governance.
+	#[pallet::type_value]
+	pub fn AtRiskHealthThresholdDefault<T: Config>() -> Rate {
+		Rate::saturating_from_rational(11, 10)
+	}
+
+	/// The at-risk debt of the pool in the underlying asset computed by the last complete pass over
+	/// the pool members.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::at_risk_debt_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn at_risk_debt_storage)]
+	pub type AtRiskDebtStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, OptionQuery>;
+
+	/// The last pool member checked by the current pass over the pool members and the at-risk debt
+	/// of the members checked so far.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::at_risk_debt_pass_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn at_risk_debt_pass_storage)]
+	pub type AtRiskDebtPassStorage<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, (Option<T::AccountId>, Balance), ValueQuery>;
+
+	/// The account to which the liquidation pools surplus is withdrawn.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::treasury_account_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn treasury_account_storage)]
+	pub type TreasuryAccountStorage<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The first block of the current period of the treasury withdrawals limit and the value in USD
+	/// withdrawn to the treasury during the period.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidation_pools::module::Pallet::treasury_withdrawals_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidation_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn treasury_withdrawals_storage)]
+	pub type TreasuryWithdrawalsStorage<T: Config> = StorageValue<_, (T::BlockNumber, Balance), ValueQuery>;
+
 	#[pallet::type_value]
 	pub fn BalancingStateDefault<T: Config>() -> bool {
 		true
@@ -214,6 +611,38 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// A pool takes part in at most one auction at a time, so the number of auctions is
+			// limited by the number of pools.
+			let auctions: Vec<(AuctionId, AuctionInfo<T::AccountId, T::BlockNumber>)> =
+				AuctionsStorage::<T>::iter().collect();
+			let ended_auctions: Vec<&(AuctionId, AuctionInfo<T::AccountId, T::BlockNumber>)> = auctions
+				.iter()
+				.filter(|(_, auction)| auction.end_block <= now)
+				.collect();
+			ended_auctions.iter().for_each(|(auction_id, auction)| {
+				if let Err(error) = Self::settle_auction(*auction_id, auction, now) {
+					log::error!(
+						target: "liquidation-pools",
+						"failed to settle auction {:?}: {:?}",
+						auction_id,
+						error,
+					);
+				}
+			});
+			T::DbWeight::get()
+				.reads((auctions.len() as Weight).saturating_add(1))
+				.saturating_add(
+					T::LiquidationPoolsWeightInfo::settle_auction().saturating_mul(ended_auctions.len() as Weight),
+				)
+				.saturating_add(Self::update_pools_at_risk_debt())
+		}
+
+		fn integrity_test() {
+			assert!(T::AtRiskDebtAccountsPerBlock::get() > 0);
+			assert!(T::DefaultMaxSlippage::get() <= Rate::one());
+		}
+
 		fn offchain_worker(now: T::BlockNumber) {
 			if let Err(error) = Self::_offchain_worker(now) {
 				log::info!(
@@ -369,121 +798,561 @@ pub mod module {
 			Ok(().into())
 		}
 
-		/// Make balance the liquidation pools.
+		/// Set new value of protocol-owned liquidity factor.
 		///
-		/// The dispatch origin of this call must be _None_.
+		/// Parameters:
+		/// - `pool_id`: the CurrencyId of the pool for which the parameter value is being set.
+		/// - `factor`: New value of protocol-owned liquidity factor.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::set_protocol_owned_liquidity_factor())]
+		#[transactional]
+		pub fn set_protocol_owned_liquidity_factor(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			factor: u128,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				pool_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+
+			let new_factor = Rate::from_inner(factor);
+			ensure!(
+				Self::is_valid_protocol_owned_liquidity_factor(new_factor),
+				Error::<T>::NotValidProtocolOwnedLiquidityFactorValue
+			);
+
+			// Write new value into storage.
+			ProtocolOwnedLiquidityFactorStorage::<T>::insert(pool_id, new_factor);
+
+			Self::deposit_event(Event::ProtocolOwnedLiquidityFactorChanged(pool_id, new_factor));
+
+			Ok(().into())
+		}
+
+		/// Set new value of maximum slippage of the balancing swaps between the pools.
 		///
 		/// Parameters:
-		/// - `supply_pool_id`: the pool from which tokens are sent for sale on DEX
-		/// - `target_pool_id`: pool for which tokens are bought on DEX
-		/// - `max_supply_amount`: the maximum number of tokens for sale from the `supply_pool_id`
-		/// pool on DEX to buy `target_amount` of tokens
-		/// - `target_amount`: number of tokens to buy in `target_pool_id` on DEX
+		/// - `supply_pool_id`: the pool from which tokens are sent for sale on DEX.
+		/// - `target_pool_id`: the pool for which tokens are bought on DEX.
+		/// - `max_slippage`: New value of maximum slippage.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
 		#[doc(alias = "MNT Extrinsic")]
 		#[doc(alias = "MNT liquidation_pools")]
-		#[pallet::weight(T::LiquidationPoolsWeightInfo::balance_liquidation_pools())]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::set_max_slippage())]
 		#[transactional]
-		pub fn balance_liquidation_pools(
+		pub fn set_max_slippage(
 			origin: OriginFor<T>,
 			supply_pool_id: CurrencyId,
 			target_pool_id: CurrencyId,
-			max_supply_amount_underlying: Balance,
-			target_amount_underlying: Balance,
+			max_slippage: u128,
 		) -> DispatchResultWithPostInfo {
-			let _ = ensure_none(origin)?;
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				supply_pool_id.is_supported_underlying_asset() && target_pool_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(supply_pool_id != target_pool_id, Error::<T>::IdenticalPools);
 			ensure!(
 				T::LiquidityPoolsManager::pool_exists(&supply_pool_id)
 					&& T::LiquidityPoolsManager::pool_exists(&target_pool_id),
 				Error::<T>::PoolNotFound
 			);
 
-			let module_id = Self::pools_account_id();
-			T::Dex::swap_with_exact_target(
-				&module_id,
+			let new_max_slippage = Rate::from_inner(max_slippage);
+			ensure!(
+				Self::is_valid_max_slippage(new_max_slippage),
+				Error::<T>::NotValidMaxSlippageValue
+			);
+
+			// Write new value into storage.
+			MaxSlippageStorage::<T>::insert(supply_pool_id, target_pool_id, new_max_slippage);
+
+			Self::deposit_event(Event::MaxSlippageChanged(
 				supply_pool_id,
 				target_pool_id,
-				max_supply_amount_underlying,
-				target_amount_underlying,
-			)?;
-			Self::deposit_event(Event::LiquidationPoolsBalanced);
+				new_max_slippage,
+			));
+
 			Ok(().into())
 		}
 
-		/// Seed the liquidation pool
+		/// Set the way the oversupplied asset of the pool is sold during balancing.
 		///
 		/// Parameters:
-		/// - `underlying_asset_id`: currency of transfer
-		/// - `underlying_amount`: amount to transfer to liquidation pool
+		/// - `pool_id`: the CurrencyId of the pool for which the parameter value is being set.
+		/// - `mode`: New balancing mode.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
 		#[doc(alias = "MNT Extrinsic")]
 		#[doc(alias = "MNT liquidation_pools")]
-		#[pallet::weight(T::LiquidationPoolsWeightInfo::transfer_to_liquidation_pool())]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::set_balancing_mode())]
 		#[transactional]
-		pub fn transfer_to_liquidation_pool(
+		pub fn set_balancing_mode(
 			origin: OriginFor<T>,
-			underlying_asset_id: CurrencyId,
-			underlying_amount: Balance,
+			pool_id: CurrencyId,
+			mode: BalancingMode,
 		) -> DispatchResultWithPostInfo {
-			let who = ensure_signed(origin)?;
+			T::UpdateOrigin::ensure_origin(origin)?;
 
 			ensure!(
-				underlying_asset_id.is_supported_underlying_asset(),
+				pool_id.is_supported_underlying_asset(),
 				Error::<T>::NotValidUnderlyingAssetId
 			);
 			ensure!(
-				T::LiquidityPoolsManager::pool_exists(&underlying_asset_id),
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
 				Error::<T>::PoolNotFound
 			);
-			ensure!(underlying_amount > Balance::zero(), Error::<T>::ZeroBalanceTransaction);
-			ensure!(
-				underlying_amount <= T::MultiCurrency::free_balance(underlying_asset_id, &who),
-				Error::<T>::NotEnoughLiquidityAvailable
-			);
 
-			T::MultiCurrency::transfer(underlying_asset_id, &who, &Self::pools_account_id(), underlying_amount)?;
+			// Write new value into storage.
+			BalancingModeStorage::<T>::insert(pool_id, mode);
+
+			Self::deposit_event(Event::BalancingModeChanged(pool_id, mode));
 
-			Self::deposit_event(Event::TransferToLiquidationPool(
-				underlying_asset_id,
-				underlying_amount,
-				who,
-			));
 			Ok(().into())
 		}
-	}
 
-	#[pallet::validate_unsigned]
-	impl<T: Config> ValidateUnsigned for Pallet<T> {
-		type Call = Call<T>;
+		/// Set the way the ideal balance of the pool is calculated.
+		///
+		/// Parameters:
+		/// - `pool_id`: the CurrencyId of the pool for which the parameter value is being set.
+		/// - `mode`: New ideal balance mode.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::set_ideal_balance_mode())]
+		#[transactional]
+		pub fn set_ideal_balance_mode(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			mode: IdealBalanceMode,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
 
-		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-			match call {
-				Call::balance_liquidation_pools(
-					_supply_pool_id,
-					_target_pool_id,
-					_max_supply_amount,
-					_target_amount,
-				) => ValidTransaction::with_tag_prefix("LiquidationPoolsOffchainWorker")
-					.priority(T::UnsignedPriority::get())
-					.and_provides(<frame_system::Pallet<T>>::block_number())
-					.longevity(64_u64)
-					.propagate(true)
-					.build(),
-				_ => InvalidTransaction::Call.into(),
-			}
-		}
-	}
-}
+			ensure!(
+				pool_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+
+			// Write new value into storage.
+			IdealBalanceModeStorage::<T>::insert(pool_id, mode);
+			// The at-risk debt of the pool is computed from scratch once the mode is set.
+			AtRiskDebtStorage::<T>::remove(pool_id);
+			AtRiskDebtPassStorage::<T>::remove(pool_id);
+
+			Self::deposit_event(Event::IdealBalanceModeChanged(pool_id, mode));
+
+			Ok(().into())
+		}
+
+		/// Set the health factor below which the borrows of an account are considered to be at
+		/// risk of liquidation.
+		///
+		/// Parameters:
+		/// - `threshold`: New value of the health threshold.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::set_at_risk_health_threshold())]
+		#[transactional]
+		pub fn set_at_risk_health_threshold(origin: OriginFor<T>, threshold: u128) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let new_threshold = Rate::from_inner(threshold);
+			ensure!(
+				Self::is_valid_health_threshold(new_threshold),
+				Error::<T>::NotValidHealthThresholdValue
+			);
+
+			// Write new value into storage.
+			AtRiskHealthThresholdStorage::<T>::put(new_threshold);
+
+			Self::deposit_event(Event::AtRiskHealthThresholdChanged(new_threshold));
+
+			Ok(().into())
+		}
+
+		/// Bid for the lot of the auction. The bid is held by the auctions account, the previous
+		/// highest bid is returned to its bidder.
+		///
+		/// Parameters:
+		/// - `auction_id`: the id of the auction.
+		/// - `amount`: the amount of the target pool asset to pay for the lot.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::bid())]
+		#[transactional]
+		pub fn bid(origin: OriginFor<T>, auction_id: AuctionId, amount: Balance) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			AuctionsStorage::<T>::try_mutate(auction_id, |maybe_auction| -> DispatchResultWithPostInfo {
+				let auction = maybe_auction.as_mut().ok_or(Error::<T>::AuctionNotFound)?;
+				ensure!(
+					<frame_system::Pallet<T>>::block_number() < auction.end_block,
+					Error::<T>::AuctionEnded
+				);
+				ensure!(
+					amount >= auction.min_bid && auction.bid.as_ref().map_or(true, |(_, highest)| amount > *highest),
+					Error::<T>::BidTooLow
+				);
+
+				let auctions_account_id = Self::auctions_account_id();
+				T::MultiCurrency::transfer(auction.target_pool_id, &who, &auctions_account_id, amount)?;
+				if let Some((bidder, highest)) = auction.bid.take() {
+					T::MultiCurrency::transfer(auction.target_pool_id, &auctions_account_id, &bidder, highest)?;
+				}
+				auction.bid = Some((who.clone(), amount));
+
+				Self::deposit_event(Event::AuctionBid(auction_id, who, amount));
+				Ok(().into())
+			})
+		}
+
+		/// Deposit the portion of the liquidation pool balance above its ideal balance into the
+		/// liquidity pool as protocol-owned supply.
+		///
+		/// Parameters:
+		/// - `pool_id`: the CurrencyId of the pool.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::deposit_protocol_owned_liquidity())]
+		#[transactional]
+		pub fn deposit_protocol_owned_liquidity(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				pool_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+
+			<Self as LiquidationPoolsManager<T::AccountId>>::deposit_protocol_owned_liquidity(pool_id)?;
+
+			Ok(().into())
+		}
+
+		/// Set the account to which the liquidation pools surplus is withdrawn.
+		///
+		/// Parameters:
+		/// - `treasury_account`: the new treasury account.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::set_treasury_account())]
+		#[transactional]
+		pub fn set_treasury_account(
+			origin: OriginFor<T>,
+			treasury_account: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			// Write new value into storage.
+			TreasuryAccountStorage::<T>::put(treasury_account.clone());
+
+			Self::deposit_event(Event::TreasuryAccountChanged(treasury_account));
+
+			Ok(().into())
+		}
+
+		/// Withdraw the liquidation pool surplus to the treasury. Only the pool balance above
+		/// `max_ideal_balance_usd * (1 + deviation_threshold)` can be withdrawn.
+		///
+		/// Parameters:
+		/// - `pool_id`: the CurrencyId of the pool.
+		/// - `underlying_amount`: the amount of the underlying asset to withdraw.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::withdraw_to_treasury())]
+		#[transactional]
+		pub fn withdraw_to_treasury(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			underlying_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				pool_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+			ensure!(!underlying_amount.is_zero(), Error::<T>::ZeroBalanceTransaction);
+			let treasury_account = Self::treasury_account_storage().ok_or(Error::<T>::TreasuryAccountNotSet)?;
+
+			let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+			let amount_usd = T::LiquidityPoolsManager::underlying_to_usd(underlying_amount, oracle_price)?;
+			ensure!(
+				amount_usd <= Self::calculate_pool_surplus_usd(pool_id)?,
+				Error::<T>::WithdrawalExceedsSurplus
+			);
+			ensure!(
+				amount_usd <= T::MaxTreasuryWithdrawalPerCall::get(),
+				Error::<T>::WithdrawalLimitExceeded
+			);
+
+			// The period starts with the first withdrawal after the end of the previous period.
+			let now = frame_system::Pallet::<T>::block_number();
+			let (period_start, withdrawn_usd) = Self::treasury_withdrawals_storage();
+			let (period_start, withdrawn_usd) =
+				if now >= period_start.saturating_add(T::TreasuryWithdrawalPeriod::get()) {
+					(now, Balance::zero())
+				} else {
+					(period_start, withdrawn_usd)
+				};
+			let new_withdrawn_usd = withdrawn_usd
+				.checked_add(amount_usd)
+				.ok_or(Error::<T>::BalanceOverflow)?;
+			ensure!(
+				new_withdrawn_usd <= T::MaxTreasuryWithdrawalPerPeriod::get(),
+				Error::<T>::PeriodWithdrawalLimitExceeded
+			);
+
+			T::MultiCurrency::transfer(pool_id, &Self::pools_account_id(), &treasury_account, underlying_amount)?;
+			TreasuryWithdrawalsStorage::<T>::put((period_start, new_withdrawn_usd));
+
+			Self::deposit_event(Event::WithdrawnToTreasury(pool_id, underlying_amount, amount_usd));
+
+			Ok(().into())
+		}
+
+		/// Make balance the liquidation pools. Tokens are swapped on DEX along the trading path
+		/// found by the offchain worker. If the supply pool is in `BalancingMode::Auction`, an
+		/// auction of the maximum supply amount of the sale with the minimum bid of the target
+		/// amount of the sale is started instead.
+		///
+		/// The swap must match a sale from the sales list computed on-chain: `target_amount` and
+		/// `max_supply_amount` must be within 1/SALE_AMOUNT_TOLERANCE_DIVISOR of the amounts
+		/// required for the sale. The swap is made with the amounts required for the sale.
+		///
+		/// If the swap along the trading path isn't possible or requires more than the maximum
+		/// supply amount of the sale, the failure is recorded and balancing of the pools is
+		/// postponed (see `BalancingBackoffStorage`). Other errors of the swap fail the call.
+		///
+		/// The dispatch origin of this call must be _None_.
+		///
+		/// Parameters:
+		/// - `supply_pool_id`: the pool from which tokens are sent for sale on DEX
+		/// - `target_pool_id`: pool for which tokens are bought on DEX
+		/// - `max_supply_amount`: the maximum number of tokens for sale from the `supply_pool_id`
+		/// pool on DEX to buy `target_amount` of tokens
+		/// - `target_amount`: number of tokens to buy in `target_pool_id` on DEX
+		/// - `path`: the trading path from `supply_pool_id` to `target_pool_id` on DEX, ignored in
+		/// `BalancingMode::Auction`
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::balance_liquidation_pools(
+			CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset).len() as u32,
+			T::TradingPathLimit::get(),
+		))]
+		#[transactional]
+		pub fn balance_liquidation_pools(
+			origin: OriginFor<T>,
+			supply_pool_id: CurrencyId,
+			target_pool_id: CurrencyId,
+			max_supply_amount_underlying: Balance,
+			target_amount_underlying: Balance,
+			path: Vec<CurrencyId>,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_none(origin)?;
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&supply_pool_id)
+					&& T::LiquidityPoolsManager::pool_exists(&target_pool_id),
+				Error::<T>::PoolNotFound
+			);
+			let (required_max_supply_amount, required_target_amount) = Self::ensure_required_sale(
+				supply_pool_id,
+				target_pool_id,
+				max_supply_amount_underlying,
+				target_amount_underlying,
+			)?;
+
+			if Self::balancing_mode_storage(supply_pool_id) == BalancingMode::Auction {
+				Self::start_auction(
+					supply_pool_id,
+					target_pool_id,
+					required_max_supply_amount,
+					required_target_amount,
+				)?;
+				return Ok(().into());
+			}
+
+			// The bounds of the swap don't depend on the call parameters and the trading path is
+			// checked when the transaction is validated, so a failure is recorded only if the sale
+			// required for balancing can't be made at the current DEX prices.
+			match Self::do_balancing_swap(
+				supply_pool_id,
+				target_pool_id,
+				&path,
+				required_max_supply_amount,
+				required_target_amount,
+			) {
+				Ok(_) => {
+					BalancingBackoffStorage::<T>::remove(supply_pool_id, target_pool_id);
+					Self::deposit_event(Event::LiquidationPoolsBalanced);
+				}
+				Err(error) if Self::is_balancing_backoff_error(error) => {
+					let backoff = Self::next_balancing_backoff(
+						Self::balancing_backoff_storage(supply_pool_id, target_pool_id),
+						<frame_system::Pallet<T>>::block_number(),
+					);
+					BalancingBackoffStorage::<T>::insert(supply_pool_id, target_pool_id, backoff.clone());
+					Self::deposit_event(Event::BalancingSwapFailed(
+						supply_pool_id,
+						target_pool_id,
+						backoff.retry_block,
+					));
+				}
+				Err(error) => return Err(error.into()),
+			}
+			Ok(().into())
+		}
+
+		/// Seed the liquidation pool
+		///
+		/// Parameters:
+		/// - `underlying_asset_id`: currency of transfer
+		/// - `underlying_amount`: amount to transfer to liquidation pool
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT liquidation_pools")]
+		#[pallet::weight(T::LiquidationPoolsWeightInfo::transfer_to_liquidation_pool())]
+		#[transactional]
+		pub fn transfer_to_liquidation_pool(
+			origin: OriginFor<T>,
+			underlying_asset_id: CurrencyId,
+			underlying_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				underlying_asset_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&underlying_asset_id),
+				Error::<T>::PoolNotFound
+			);
+			ensure!(underlying_amount > Balance::zero(), Error::<T>::ZeroBalanceTransaction);
+			ensure!(
+				underlying_amount <= T::MultiCurrency::free_balance(underlying_asset_id, &who),
+				Error::<T>::NotEnoughLiquidityAvailable
+			);
+
+			T::MultiCurrency::transfer(underlying_asset_id, &who, &Self::pools_account_id(), underlying_amount)?;
+
+			Self::deposit_event(Event::TransferToLiquidationPool(
+				underlying_asset_id,
+				underlying_amount,
+				who,
+			));
+			Ok(().into())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			match call {
+				Call::balance_liquidation_pools(
+					supply_pool_id,
+					target_pool_id,
+					max_supply_amount,
+					target_amount,
+					path,
+				) => {
+					// The trading path found off-chain must be good enough for the sale at the
+					// moment of validation.
+					let is_valid_sale = Self::ensure_required_sale(
+						*supply_pool_id,
+						*target_pool_id,
+						*max_supply_amount,
+						*target_amount,
+					)
+					.and_then(|(required_max_supply_amount, required_target_amount)| {
+						if Self::balancing_mode_storage(*supply_pool_id) == BalancingMode::Auction {
+							return Ok(());
+						}
+						Self::ensure_balancing_path(
+							*supply_pool_id,
+							*target_pool_id,
+							path,
+							required_max_supply_amount,
+							required_target_amount,
+						)
+					});
+					if is_valid_sale.is_err() {
+						return InvalidTransaction::Call.into();
+					}
+					// Each sale of the list is provided by a separate transaction, so the whole
+					// list can be processed in one block.
+					ValidTransaction::with_tag_prefix("LiquidationPoolsOffchainWorker")
+						.priority(T::UnsignedPriority::get())
+						.and_provides((
+							<frame_system::Pallet<T>>::block_number(),
+							supply_pool_id,
+							target_pool_id,
+						))
+						.longevity(64_u64)
+						.propagate(true)
+						.build()
+				}
+				_ => InvalidTransaction::Call.into(),
+			}
+		}
+	}
+}
 
 /// Used in the liquidation pools balancing algorithm.
-#[derive(Debug, Clone)]
-struct LiquidationInformation {
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LiquidationInformation {
 	/// CurrencyId
-	pool_id: CurrencyId,
+	pub pool_id: CurrencyId,
 	/// Pool current balance in USD.
-	balance_usd: Balance,
+	pub balance_usd: Balance,
+	/// Pool ideal balance in USD.
+	pub ideal_balance_usd: Balance,
 	/// Pool balance above ideal value (USD).
-	oversupply_usd: Balance,
+	pub oversupply_usd: Balance,
 	/// Pool balance below ideal value (USD).
-	shortfall_usd: Balance,
+	pub shortfall_usd: Balance,
+	/// The pool is in shortfall if its balance is below this value (USD).
+	pub left_border_usd: Balance,
+	/// The pool is in oversupply if its balance is above this value (USD).
+	pub right_border_usd: Balance,
 }
 
 /// Information about the operations required for balancing Liquidation Pools.
@@ -498,8 +1367,24 @@ pub struct Sales {
 	pub amount_usd: Balance,
 }
 
+/// The status of a sale required for balancing of the liquidation pools, as seen by the
+/// offchain worker.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SaleStatus {
+	/// The offchain worker submits the balancing transaction.
+	Ready,
+	/// Balancing of the pools is postponed after failed swaps.
+	Postponed,
+	/// One of the pools takes part in the auction that is not settled yet.
+	AuctionInProgress,
+	/// There is no trading path between the pools on DEX.
+	TradingPathNotFound,
+	/// DEX requires more than the maximum supply amount of the sale.
+	ExcessiveSlippage,
+}
+
 impl<T: Config> Pallet<T> {
-	fn _offchain_worker(_now: T::BlockNumber) -> Result<(), OffchainErr> {
+	fn _offchain_worker(now: T::BlockNumber) -> Result<(), OffchainErr> {
 		// Check if we are a potential validator and balancing is enabled.
 		ensure!(sp_io::offchain::is_validator(), OffchainErr::NotValidator);
 		// Check if pool balansing is switched ON.
@@ -509,41 +1394,336 @@ impl<T: Config> Pallet<T> {
 		// If pools balancing procedure already started should be returned OffchainLock error.
 		// To prevent any race condition sutiations.
 		let _guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
-		Self::pools_balancing().map_err(|_| OffchainErr::PoolsBalancingError)?;
+		Self::pools_balancing(now).map_err(|_| OffchainErr::PoolsBalancingError)?;
 		Ok(())
 	}
 
 	/// Makes balancing of liquidation pools if it necessary.
-	fn pools_balancing() -> DispatchResult {
+	fn pools_balancing(now: T::BlockNumber) -> DispatchResult {
 		// If balancing of pools isn't required then collects_sales_list returns empty list
 		// and next steps won't be processed.
 		Self::collects_sales_list()?
 			.iter()
 			.try_for_each(|sale: &Sales| -> DispatchResult {
-				let (max_supply_amount_underlying, target_amount_underlying) =
-					Self::get_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount_usd)?;
-				Self::submit_unsigned_tx(
-					sale.supply_pool_id,
-					sale.target_pool_id,
-					max_supply_amount_underlying,
-					target_amount_underlying,
-				);
+				let is_postponed = Self::is_balancing_backed_off(sale.supply_pool_id, sale.target_pool_id, now);
+				let (status, max_supply_amount_underlying, target_amount_underlying, path) =
+					Self::get_sale_status(sale, is_postponed)?;
+
+				let backoff_key = Self::offchain_backoff_key(sale.supply_pool_id, sale.target_pool_id);
+				let mut backoff_storage = StorageValueRef::persistent(&backoff_key);
+				match status {
+					SaleStatus::Ready => {
+						backoff_storage.clear();
+						Self::submit_unsigned_tx(
+							sale.supply_pool_id,
+							sale.target_pool_id,
+							max_supply_amount_underlying,
+							target_amount_underlying,
+							path,
+						);
+					}
+					SaleStatus::TradingPathNotFound | SaleStatus::ExcessiveSlippage => {
+						let backoff = Self::next_balancing_backoff(backoff_storage.get().ok().flatten(), now);
+						log::info!(
+							target: "liquidation-pools offchain worker",
+							"balancing of CurrencyId {:?} and CurrencyId {:?} is postponed until block {:?}: {:?}",
+							sale.supply_pool_id, sale.target_pool_id, backoff.retry_block, status,
+						);
+						backoff_storage.set(&backoff);
+					}
+					SaleStatus::Postponed | SaleStatus::AuctionInProgress => {}
+				}
 				Ok(())
 			})?;
 		Ok(())
 	}
 
+	/// Checks whether the offchain worker submits the sale.
+	/// - `sale`: the sale required for balancing of the pools.
+	/// - `is_postponed`: balancing of the pools is postponed after failed swaps.
+	///
+	/// Returns (`status`, `max_supply_amount_underlying`, `target_amount_underlying`, `path`),
+	/// where `path` is the trading path with the least supply amount on DEX. The path is empty
+	/// unless the sale is ready to be swapped on DEX.
+	fn get_sale_status(
+		sale: &Sales,
+		is_postponed: bool,
+	) -> sp_std::result::Result<(SaleStatus, Balance, Balance, Vec<CurrencyId>), DispatchError> {
+		let (max_supply_amount_underlying, target_amount_underlying) =
+			Self::get_swap_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount_usd)?;
+		let (status, path) = if is_postponed {
+			(SaleStatus::Postponed, Vec::new())
+		} else if Self::is_pool_in_auction(sale.supply_pool_id) || Self::is_pool_in_auction(sale.target_pool_id) {
+			(SaleStatus::AuctionInProgress, Vec::new())
+		} else if Self::balancing_mode_storage(sale.supply_pool_id) == BalancingMode::Auction {
+			// Auction bids are not known in advance, so there is nothing to quote.
+			(SaleStatus::Ready, Vec::new())
+		} else {
+			// The search of the trading path isn't bounded, so it is made off-chain only.
+			match T::Dex::get_best_path_for_exact_target(
+				sale.supply_pool_id,
+				sale.target_pool_id,
+				target_amount_underlying,
+			) {
+				Some((path, supply_amount)) if supply_amount <= max_supply_amount_underlying => {
+					(SaleStatus::Ready, path)
+				}
+				Some(_) => (SaleStatus::ExcessiveSlippage, Vec::new()),
+				None => (SaleStatus::TradingPathNotFound, Vec::new()),
+			}
+		};
+		Ok((status, max_supply_amount_underlying, target_amount_underlying, path))
+	}
+
+	/// Checks that the balancing swap is required by the sales list computed on-chain and
+	/// balancing of the pools isn't postponed.
+	/// - `supply_pool_id`: the pool from which tokens are sent for sale on DEX.
+	/// - `target_pool_id`: the pool for which tokens are bought on DEX.
+	/// - `max_supply_amount_underlying`: must be within the tolerance of the maximum supply amount
+	/// of the sale.
+	/// - `target_amount_underlying`: must be within the tolerance of the target amount of the sale.
+	///
+	/// Returns (`max_supply_amount_underlying`, `target_amount_underlying`) of the sale.
+	fn ensure_required_sale(
+		supply_pool_id: CurrencyId,
+		target_pool_id: CurrencyId,
+		max_supply_amount_underlying: Balance,
+		target_amount_underlying: Balance,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(
+			Self::balancing_backoff_storage(supply_pool_id, target_pool_id)
+				.map_or(true, |backoff| backoff.retry_block <= now),
+			Error::<T>::BalancingPostponed
+		);
+		ensure!(
+			!Self::is_pool_in_auction(supply_pool_id) && !Self::is_pool_in_auction(target_pool_id),
+			Error::<T>::AuctionInProgress
+		);
+
+		let sale = Self::collects_sales_list()?
+			.into_iter()
+			.find(|sale| sale.supply_pool_id == supply_pool_id && sale.target_pool_id == target_pool_id)
+			.ok_or(Error::<T>::SaleNotRequired)?;
+		let (required_max_supply_amount, required_target_amount) =
+			Self::get_swap_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount_usd)?;
+		ensure!(
+			Self::is_within_sale_tolerance(max_supply_amount_underlying, required_max_supply_amount)
+				&& Self::is_within_sale_tolerance(target_amount_underlying, required_target_amount),
+			Error::<T>::SaleNotRequired
+		);
+		Ok((required_max_supply_amount, required_target_amount))
+	}
+
+	/// Checks that `amount` differs from `required_amount` by at most
+	/// `required_amount / SALE_AMOUNT_TOLERANCE_DIVISOR`.
+	fn is_within_sale_tolerance(amount: Balance, required_amount: Balance) -> bool {
+		let tolerance = required_amount / SALE_AMOUNT_TOLERANCE_DIVISOR;
+		required_amount.saturating_sub(tolerance) <= amount && amount <= required_amount.saturating_add(tolerance)
+	}
+
+	/// Checks that the trading path leads from the supply pool to the target pool and DEX
+	/// requires at most `max_supply_amount_underlying` along the path.
+	/// - `supply_pool_id`: the pool from which tokens are sent for sale on DEX.
+	/// - `target_pool_id`: the pool for which tokens are bought on DEX.
+	/// - `path`: the trading path found off-chain.
+	/// - `max_supply_amount_underlying`: the maximum supply amount of the sale.
+	/// - `target_amount_underlying`: the target amount of the sale.
+	fn ensure_balancing_path(
+		supply_pool_id: CurrencyId,
+		target_pool_id: CurrencyId,
+		path: &[CurrencyId],
+		max_supply_amount_underlying: Balance,
+		target_amount_underlying: Balance,
+	) -> DispatchResult {
+		ensure!(
+			path.first() == Some(&supply_pool_id) && path.last() == Some(&target_pool_id),
+			Error::<T>::InvalidTradingPath
+		);
+		let supply_amount_underlying =
+			T::Dex::get_supply_amount_by_path(path, target_amount_underlying).ok_or(Error::<T>::TradingPathNotFound)?;
+		ensure!(
+			supply_amount_underlying <= max_supply_amount_underlying,
+			Error::<T>::ExcessiveSlippage
+		);
+		Ok(())
+	}
+
+	/// Swaps tokens of the supply pool for the target pool tokens on DEX along the trading path.
+	/// Fails without changing the state if DEX requires more than `max_supply_amount_underlying`.
+	#[transactional]
+	fn do_balancing_swap(
+		supply_pool_id: CurrencyId,
+		target_pool_id: CurrencyId,
+		path: &[CurrencyId],
+		max_supply_amount_underlying: Balance,
+		target_amount_underlying: Balance,
+	) -> DispatchResult {
+		Self::ensure_balancing_path(
+			supply_pool_id,
+			target_pool_id,
+			path,
+			max_supply_amount_underlying,
+			target_amount_underlying,
+		)?;
+		T::Dex::swap_with_exact_target_by_path(
+			&Self::pools_account_id(),
+			path,
+			max_supply_amount_underlying,
+			target_amount_underlying,
+		)?;
+		Ok(())
+	}
+
+	/// Returns true if the error of the balancing swap means that the sale can't be made at the
+	/// current DEX prices, so balancing of the pools is postponed.
+	fn is_balancing_backoff_error(error: DispatchError) -> bool {
+		error == Error::<T>::TradingPathNotFound.into() || error == Error::<T>::ExcessiveSlippage.into()
+	}
+
+	/// Starts the auction of the oversupplied asset. The lot is transferred from the liquidation
+	/// pool to the auctions account.
+	/// - `supply_pool_id`: the pool which asset is offered.
+	/// - `target_pool_id`: the pool which asset is bid.
+	/// - `lot`: the amount of `supply_pool_id` asset offered.
+	/// - `min_bid`: the minimum amount of `target_pool_id` asset to bid.
+	fn start_auction(
+		supply_pool_id: CurrencyId,
+		target_pool_id: CurrencyId,
+		lot: Balance,
+		min_bid: Balance,
+	) -> DispatchResult {
+		let auction_id = Self::next_auction_id_storage();
+		let end_block = <frame_system::Pallet<T>>::block_number().saturating_add(T::AuctionDuration::get());
+
+		T::MultiCurrency::transfer(
+			supply_pool_id,
+			&Self::pools_account_id(),
+			&Self::auctions_account_id(),
+			lot,
+		)?;
+		AuctionsStorage::<T>::insert(
+			auction_id,
+			AuctionInfo {
+				supply_pool_id,
+				target_pool_id,
+				lot,
+				min_bid,
+				bid: None,
+				end_block,
+			},
+		);
+		PoolAuctionStorage::<T>::insert(supply_pool_id, auction_id);
+		PoolAuctionStorage::<T>::insert(target_pool_id, auction_id);
+		NextAuctionIdStorage::<T>::put(auction_id.checked_add(1).ok_or(Error::<T>::NumOverflow)?);
+
+		Self::deposit_event(Event::AuctionStarted(
+			auction_id,
+			supply_pool_id,
+			target_pool_id,
+			lot,
+			min_bid,
+			end_block,
+		));
+		Ok(())
+	}
+
+	/// Transfers the lot to the highest bidder and the bid to the liquidation pool. If there are
+	/// no bids, the lot is returned to the liquidation pool and balancing of the pools is
+	/// postponed. The settled auction is removed from storage.
+	#[transactional]
+	fn settle_auction(
+		auction_id: AuctionId,
+		auction: &AuctionInfo<T::AccountId, T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> DispatchResult {
+		let auctions_account_id = Self::auctions_account_id();
+		let pools_account_id = Self::pools_account_id();
+		match &auction.bid {
+			Some((bidder, amount)) => {
+				T::MultiCurrency::transfer(auction.supply_pool_id, &auctions_account_id, bidder, auction.lot)?;
+				T::MultiCurrency::transfer(auction.target_pool_id, &auctions_account_id, &pools_account_id, *amount)?;
+				BalancingBackoffStorage::<T>::remove(auction.supply_pool_id, auction.target_pool_id);
+				Self::deposit_event(Event::AuctionSettled(auction_id, bidder.clone(), *amount));
+			}
+			None => {
+				T::MultiCurrency::transfer(
+					auction.supply_pool_id,
+					&auctions_account_id,
+					&pools_account_id,
+					auction.lot,
+				)?;
+				let backoff = Self::next_balancing_backoff(
+					Self::balancing_backoff_storage(auction.supply_pool_id, auction.target_pool_id),
+					now,
+				);
+				BalancingBackoffStorage::<T>::insert(auction.supply_pool_id, auction.target_pool_id, backoff);
+				Self::deposit_event(Event::AuctionCancelled(auction_id));
+			}
+		}
+		AuctionsStorage::<T>::remove(auction_id);
+		PoolAuctionStorage::<T>::remove(auction.supply_pool_id);
+		PoolAuctionStorage::<T>::remove(auction.target_pool_id);
+		Ok(())
+	}
+
+	/// Checks if the pool takes part in the auction that is not settled yet.
+	fn is_pool_in_auction(pool_id: CurrencyId) -> bool {
+		PoolAuctionStorage::<T>::contains_key(pool_id)
+	}
+
+	/// Gets the account holding lots and bids of the auctions.
+	pub fn auctions_account_id() -> T::AccountId {
+		T::LiquidationPoolsPalletId::get().into_sub_account("auctions")
+	}
+
+	/// Calculates the backoff after one more failure of the balancing swap.
+	/// - `previous`: the backoff after the previous failures, if any.
+	/// - `now`: current block number.
+	fn next_balancing_backoff(
+		previous: Option<BalancingBackoff<T::BlockNumber>>,
+		now: T::BlockNumber,
+	) -> BalancingBackoff<T::BlockNumber> {
+		let failures = previous.map_or(0, |backoff| backoff.failures).saturating_add(1);
+		let multiplier = 1_u32 << (failures - 1).min(MAX_BACKOFF_EXPONENT);
+		BalancingBackoff {
+			failures,
+			retry_block: now.saturating_add(T::BalancingBackoffPeriod::get().saturating_mul(multiplier.into())),
+		}
+	}
+
+	/// Checks if balancing of the pools is postponed either on-chain (failed swap) or by the
+	/// offchain worker (excessive slippage of the quote).
+	fn is_balancing_backed_off(supply_pool_id: CurrencyId, target_pool_id: CurrencyId, now: T::BlockNumber) -> bool {
+		let offchain_backoff = StorageValueRef::persistent(&Self::offchain_backoff_key(supply_pool_id, target_pool_id))
+			.get::<BalancingBackoff<T::BlockNumber>>()
+			.ok()
+			.flatten();
+		Self::balancing_backoff_storage(supply_pool_id, target_pool_id)
+			.into_iter()
+			.chain(offchain_backoff)
+			.any(|backoff| backoff.retry_block > now)
+	}
+
+	fn offchain_backoff_key(supply_pool_id: CurrencyId, target_pool_id: CurrencyId) -> Vec<u8> {
+		let mut key = OFFCHAIN_BALANCING_BACKOFF_PREFIX.to_vec();
+		key.extend((supply_pool_id, target_pool_id).encode());
+		key
+	}
+
 	fn submit_unsigned_tx(
 		supply_pool_id: CurrencyId,
 		target_pool_id: CurrencyId,
 		max_supply_amount_underlying: Balance,
 		target_amount_underlying: Balance,
+		path: Vec<CurrencyId>,
 	) {
 		let call = Call::<T>::balance_liquidation_pools(
 			supply_pool_id,
 			target_pool_id,
 			max_supply_amount_underlying,
 			target_amount_underlying,
+			path,
 		);
 		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
 			log::info!(
@@ -554,10 +1734,16 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	/// Collects information about required transactions on DEX.
-	fn collects_sales_list() -> sp_std::result::Result<Vec<Sales>, DispatchError> {
+	/// Collects information about the current state of liquidation pools.
+	///
+	/// Returns (`information_vec`, `sum_oversupply_usd`, `sum_shortfall_usd`), where
+	/// `sum_oversupply_usd` and `sum_shortfall_usd` are the sums of oversupply and shortfall of
+	/// the pools whose balance is outside the deviation borders. The shortfall of the pools whose
+	/// balance is below the left border is covered by their protocol-owned supply first.
+	fn collect_pools_information(
+	) -> sp_std::result::Result<(Vec<LiquidationInformation>, Balance, Balance), DispatchError> {
 		// Collecting information about the current state of liquidation pools.
-		let (mut information_vec, mut sum_oversupply_usd, mut sum_shortfall_usd) =
+		let (information_vec, sum_oversupply_usd, sum_shortfall_usd) =
 			CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
 				.iter()
 				.filter(|&underlying_id| T::LiquidityPoolsManager::pool_exists(underlying_id))
@@ -569,12 +1755,44 @@ impl<T: Config> Pallet<T> {
 						T::ControllerManager::accrue_interest_rate(*pool_id)?;
 						let oracle_price =
 							T::PriceSource::get_underlying_price(*pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+						let pool_ideal_balance_usd = Self::calculate_pool_ideal_balance_usd(*pool_id)?;
+
+						// Calculate sum_extra and sum_shortfall for all pools.
+						let deviation_threshold = Self::liquidation_pool_data_storage(*pool_id).deviation_threshold;
+						// right_border = pool_ideal_balance_usd + pool_ideal_balance_usd * deviation_threshold
+						let right_border =
+							sum_with_mult_result(pool_ideal_balance_usd, pool_ideal_balance_usd, deviation_threshold)
+								.map_err(|_| Error::<T>::BalanceOverflow)?;
+
+						// left_border = pool_ideal_balance_usd - pool_ideal_balance_usd * deviation_threshold
+						let left_border = pool_ideal_balance_usd
+							.checked_sub(
+								Rate::from_inner(pool_ideal_balance_usd)
+									.checked_mul(&deviation_threshold)
+									.map(|x| x.into_inner())
+									.ok_or(Error::<T>::NumOverflow)?,
+							)
+							.ok_or(Error::<T>::NumOverflow)?;
+
 						let liquidation_pool_supply_underlying = Self::get_pool_available_liquidity(*pool_id);
-						let liquidation_pool_supply_usd = T::LiquidityPoolsManager::underlying_to_usd(
+						let mut liquidation_pool_supply_usd = T::LiquidityPoolsManager::underlying_to_usd(
 							liquidation_pool_supply_underlying,
 							oracle_price,
 						)?;
-						let pool_ideal_balance_usd = Self::calculate_pool_ideal_balance_usd(*pool_id)?;
+
+						// The shortfall of the pool is covered by the protocol-owned supply before
+						// selling the assets of other pools.
+						if liquidation_pool_supply_usd < left_border {
+							let redeemed_underlying =
+								<Self as LiquidationPoolsManager<T::AccountId>>::redeem_protocol_owned_liquidity(
+									*pool_id,
+									Balance::zero(),
+								)?;
+							liquidation_pool_supply_usd = T::LiquidityPoolsManager::underlying_to_usd(
+								liquidation_pool_supply_underlying.saturating_add(redeemed_underlying),
+								oracle_price,
+							)?;
+						}
 
 						// If the pool is not balanced:
 						// oversupply_usd = liquidation_pool_balance - pool_ideal_balance_usd
@@ -592,27 +1810,13 @@ impl<T: Config> Pallet<T> {
 						current_vec.push(LiquidationInformation {
 							pool_id: *pool_id,
 							balance_usd: liquidation_pool_supply_usd,
+							ideal_balance_usd: pool_ideal_balance_usd,
 							oversupply_usd,
 							shortfall_usd,
+							left_border_usd: left_border,
+							right_border_usd: right_border,
 						});
 
-						// Calculate sum_extra and sum_shortfall for all pools.
-						let deviation_threshold = Self::liquidation_pool_data_storage(*pool_id).deviation_threshold;
-						// right_border = pool_ideal_balance_usd + pool_ideal_balance_usd * deviation_threshold
-						let right_border =
-							sum_with_mult_result(pool_ideal_balance_usd, pool_ideal_balance_usd, deviation_threshold)
-								.map_err(|_| Error::<T>::BalanceOverflow)?;
-
-						// left_border = pool_ideal_balance_usd - pool_ideal_balance_usd * deviation_threshold
-						let left_border = pool_ideal_balance_usd
-							.checked_sub(
-								Rate::from_inner(pool_ideal_balance_usd)
-									.checked_mul(&deviation_threshold)
-									.map(|x| x.into_inner())
-									.ok_or(Error::<T>::NumOverflow)?,
-							)
-							.ok_or(Error::<T>::NumOverflow)?;
-
 						if liquidation_pool_supply_usd > right_border {
 							current_sum_oversupply_usd = current_sum_oversupply_usd
 								.checked_add(oversupply_usd)
@@ -628,6 +1832,45 @@ impl<T: Config> Pallet<T> {
 					},
 				)?;
 
+		Ok((information_vec, sum_oversupply_usd, sum_shortfall_usd))
+	}
+
+	/// Returns the current state of liquidation pools and the sales required for balancing of the
+	/// pools with their status, as seen by the offchain worker. Used in the runtime API.
+	///
+	/// The offchain backoff of the worker isn't available on-chain, so only the backoff after
+	/// failed balancing transactions is taken into account.
+	#[allow(clippy::type_complexity)]
+	pub fn get_balancing_plan(
+	) -> sp_std::result::Result<(Vec<LiquidationInformation>, Vec<(Sales, SaleStatus)>), DispatchError> {
+		let pools_information = Self::collect_pools_information()?;
+		let information_vec = pools_information.0.clone();
+		let now = <frame_system::Pallet<T>>::block_number();
+		let sales = Self::calculate_sales_list(pools_information)?
+			.into_iter()
+			.map(|sale| {
+				let is_postponed = Self::balancing_backoff_storage(sale.supply_pool_id, sale.target_pool_id)
+					.map_or(false, |backoff| backoff.retry_block > now);
+				let (status, _, _, _) = Self::get_sale_status(&sale, is_postponed)?;
+				Ok((sale, status))
+			})
+			.collect::<sp_std::result::Result<Vec<_>, DispatchError>>()?;
+		Ok((information_vec, sales))
+	}
+
+	/// Collects information about required transactions on DEX.
+	fn collects_sales_list() -> sp_std::result::Result<Vec<Sales>, DispatchError> {
+		Self::calculate_sales_list(Self::collect_pools_information()?)
+	}
+
+	/// Calculates the transactions on DEX required for balancing of the pools.
+	/// - `pools_information`: (`information_vec`, `sum_oversupply_usd`, `sum_shortfall_usd`) as
+	/// returned by `collect_pools_information`.
+	fn calculate_sales_list(
+		pools_information: (Vec<LiquidationInformation>, Balance, Balance),
+	) -> sp_std::result::Result<Vec<Sales>, DispatchError> {
+		let (mut information_vec, mut sum_oversupply_usd, mut sum_shortfall_usd) = pools_information;
+
 		// Contains information about the necessary transactions on the DEX.
 		let mut to_sell_list: Vec<Sales> = Vec::new();
 
@@ -688,8 +1931,15 @@ impl<T: Config> Pallet<T> {
 		Ok(to_sell_list)
 	}
 
-	/// Temporary function
-	fn get_amounts(
+	/// Converts the USD amount of the sale to the swap amounts.
+	/// - `supply_pool_id`: the pool from which tokens are sent for sale on DEX.
+	/// - `target_pool_id`: the pool for which tokens are bought on DEX.
+	/// - `amount_usd`: the amount of the sale in USD.
+	///
+	/// Returns (`max_supply_amount_underlying`, `target_amount_underlying`), where
+	/// `max_supply_amount_underlying` is the oracle equivalent of `amount_usd` increased by the
+	/// maximum slippage of the pair of pools.
+	fn get_swap_amounts(
 		supply_pool_id: CurrencyId,
 		target_pool_id: CurrencyId,
 		amount_usd: Balance,
@@ -698,8 +1948,14 @@ impl<T: Config> Pallet<T> {
 			T::PriceSource::get_underlying_price(supply_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
 		let target_oracle_price =
 			T::PriceSource::get_underlying_price(target_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
-		let max_supply_amount_underlying =
-			T::LiquidityPoolsManager::usd_to_underlying(amount_usd, supply_oracle_price)?;
+		let supply_amount_underlying = T::LiquidityPoolsManager::usd_to_underlying(amount_usd, supply_oracle_price)?;
+		// max_supply_amount = supply_amount + supply_amount * max_slippage
+		let max_supply_amount_underlying = sum_with_mult_result(
+			supply_amount_underlying,
+			supply_amount_underlying,
+			Self::max_slippage_storage(supply_pool_id, target_pool_id),
+		)
+		.map_err(|_| Error::<T>::BalanceOverflow)?;
 		let target_amount_underlying = T::LiquidityPoolsManager::usd_to_underlying(amount_usd, target_oracle_price)?;
 		Ok((max_supply_amount_underlying, target_amount_underlying))
 	}
@@ -711,28 +1967,152 @@ impl<T: Config> Pallet<T> {
 	/// max_ideal_balance_usd
 	fn calculate_pool_ideal_balance_usd(pool_id: CurrencyId) -> BalanceResult {
 		let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let ideal_balance_usd = match Self::ideal_balance_mode_storage(pool_id) {
+			IdealBalanceMode::BorrowRatio => Self::calculate_pool_borrow_ratio_balance_usd(pool_id, oracle_price)?,
+			IdealBalanceMode::AtRiskDebt => match Self::at_risk_debt_storage(pool_id) {
+				Some(at_risk_debt) => T::LiquidityPoolsManager::underlying_to_usd(at_risk_debt, oracle_price)?,
+				// The at-risk debt is not computed yet.
+				None => Self::calculate_pool_borrow_ratio_balance_usd(pool_id, oracle_price)?,
+			},
+		};
+
+		match Self::liquidation_pool_data_storage(pool_id).max_ideal_balance_usd {
+			Some(max_ideal_balance_usd) => Ok(ideal_balance_usd.min(max_ideal_balance_usd)),
+			None => Ok(ideal_balance_usd),
+		}
+	}
+
+	/// Calculates `balance_ratio` of the pool borrows in USD.
+	/// - `pool_id`: PoolID for which the ideal balance is calculated.
+	/// - `oracle_price`: the price of the pool underlying asset.
+	fn calculate_pool_borrow_ratio_balance_usd(pool_id: CurrencyId, oracle_price: Price) -> BalanceResult {
 		let balance_ratio = Self::liquidation_pool_data_storage(pool_id).balance_ratio;
 		// Liquidation pool ideal balance in USD: liquidity_pool_total_borrow * balance_ratio *
 		// oracle_price
-		let ideal_balance_usd = Rate::from_inner(T::LiquidityPoolsManager::get_pool_borrow_underlying(pool_id))
+		Rate::from_inner(T::LiquidityPoolsManager::get_pool_borrow_underlying(pool_id))
 			.checked_mul(&balance_ratio)
 			.and_then(|v| v.checked_mul(&oracle_price))
 			.map(|x| x.into_inner())
-			.ok_or(Error::<T>::BalanceOverflow)?;
+			.ok_or_else(|| Error::<T>::BalanceOverflow.into())
+	}
 
-		match Self::liquidation_pool_data_storage(pool_id).max_ideal_balance_usd {
-			Some(max_ideal_balance_usd) => Ok(ideal_balance_usd.min(max_ideal_balance_usd)),
-			None => Ok(ideal_balance_usd),
+	/// Continues the passes over the members of the pools in `IdealBalanceMode::AtRiskDebt`.
+	///
+	/// Returns: the weight of the update.
+	fn update_pools_at_risk_debt() -> Weight {
+		let modes: Vec<(CurrencyId, IdealBalanceMode)> = IdealBalanceModeStorage::<T>::iter().collect();
+		let at_risk_debt_pools: Vec<CurrencyId> = modes
+			.iter()
+			.filter(|(_, mode)| *mode == IdealBalanceMode::AtRiskDebt)
+			.map(|(pool_id, _)| *pool_id)
+			.collect();
+		at_risk_debt_pools.iter().for_each(|pool_id| {
+			if let Err(error) = Self::update_pool_at_risk_debt(*pool_id) {
+				log::error!(
+					target: "liquidation-pools",
+					"failed to update at-risk debt of pool {:?}: {:?}",
+					pool_id,
+					error,
+				);
+			}
+		});
+		T::DbWeight::get().reads(modes.len() as Weight).saturating_add(
+			T::LiquidationPoolsWeightInfo::update_pool_at_risk_debt(T::AtRiskDebtAccountsPerBlock::get())
+				.saturating_mul(at_risk_debt_pools.len() as Weight),
+		)
+	}
+
+	/// Checks at most `AtRiskDebtAccountsPerBlock` members of the pool following the last member
+	/// checked by the current pass. Once all members are checked, the at-risk debt of the pool is
+	/// updated and the next pass starts.
+	///
+	/// The at-risk debt is the sum of borrows in the pool of the accounts whose health factor is
+	/// below `at_risk_health_threshold`. Health factor is the ratio of the account total collateral
+	/// to the account total borrow value.
+	/// - `pool_id`: PoolID for which the at-risk debt is calculated.
+	fn update_pool_at_risk_debt(pool_id: CurrencyId) -> DispatchResult {
+		let (start_after, checked_at_risk_debt) = Self::at_risk_debt_pass_storage(pool_id);
+		let (members, last_checked) = T::LiquidityPoolsManager::get_pool_members_with_loan_page(
+			pool_id,
+			start_after,
+			T::AtRiskDebtAccountsPerBlock::get(),
+		);
+		let health_threshold = Self::at_risk_health_threshold_storage();
+		let at_risk_debt = members
+			.into_iter()
+			.try_fold(checked_at_risk_debt, |acc, who| -> BalanceResult {
+				let total_borrow_usd = T::ControllerManager::get_user_total_borrow_usd(&who)?;
+				let total_collateral_usd = T::ControllerManager::get_user_total_collateral(who.clone())?;
+				let is_at_risk = Rate::checked_from_rational(total_collateral_usd, total_borrow_usd)
+					.map_or(false, |health_factor| health_factor < health_threshold);
+				if !is_at_risk {
+					return Ok(acc);
+				}
+				let borrow_underlying = T::ControllerManager::get_user_borrow_underlying_balance(&who, pool_id)?;
+				acc.checked_add(borrow_underlying)
+					.ok_or_else(|| Error::<T>::BalanceOverflow.into())
+			})?;
+
+		match last_checked {
+			Some(_) => AtRiskDebtPassStorage::<T>::insert(pool_id, (last_checked, at_risk_debt)),
+			None => {
+				AtRiskDebtPassStorage::<T>::remove(pool_id);
+				AtRiskDebtStorage::<T>::insert(pool_id, at_risk_debt);
+			}
 		}
+		Ok(())
+	}
+
+	/// Calculates the liquidation pool balance in USD above
+	/// `max_ideal_balance_usd * (1 + deviation_threshold)`. There is no surplus if the maximum
+	/// ideal balance of the pool is not set.
+	/// - `pool_id`: PoolID for which the surplus is calculated.
+	fn calculate_pool_surplus_usd(pool_id: CurrencyId) -> BalanceResult {
+		let pool_data = Self::liquidation_pool_data_storage(pool_id);
+		let max_ideal_balance_usd = match pool_data.max_ideal_balance_usd {
+			Some(max_ideal_balance_usd) => max_ideal_balance_usd,
+			None => return Ok(Balance::zero()),
+		};
+		// surplus_border = max_ideal_balance_usd + max_ideal_balance_usd * deviation_threshold
+		let surplus_border = sum_with_mult_result(
+			max_ideal_balance_usd,
+			max_ideal_balance_usd,
+			pool_data.deviation_threshold,
+		)
+		.map_err(|_| Error::<T>::BalanceOverflow)?;
+		let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let pool_balance_usd =
+			T::LiquidityPoolsManager::underlying_to_usd(Self::get_pool_available_liquidity(pool_id), oracle_price)?;
+		Ok(pool_balance_usd.saturating_sub(surplus_border))
+	}
+
+	/// Calculates ideal balance of the pool in the underlying asset.
+	/// - `pool_id`: PoolID for which the ideal balance is calculated.
+	fn calculate_pool_ideal_balance_underlying(pool_id: CurrencyId) -> BalanceResult {
+		let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let ideal_balance_usd = Self::calculate_pool_ideal_balance_usd(pool_id)?;
+		T::LiquidityPoolsManager::usd_to_underlying(ideal_balance_usd, oracle_price)
 	}
 
 	fn is_valid_deviation_threshold(deviation_threshold: Rate) -> bool {
 		Rate::zero() <= deviation_threshold && deviation_threshold <= Rate::one()
 	}
 
+	fn is_valid_health_threshold(health_threshold: Rate) -> bool {
+		health_threshold >= Rate::one()
+	}
+
 	fn is_valid_balance_ratio(balance_ratio: Rate) -> bool {
 		Rate::zero() <= balance_ratio && balance_ratio <= Rate::one()
 	}
+
+	fn is_valid_protocol_owned_liquidity_factor(factor: Rate) -> bool {
+		Rate::zero() <= factor && factor <= Rate::one()
+	}
+
+	fn is_valid_max_slippage(max_slippage: Rate) -> bool {
+		Rate::zero() <= max_slippage && max_slippage <= Rate::one()
+	}
 }
 
 impl<T: Config> PoolsManager<T::AccountId> for Pallet<T> {
@@ -774,4 +2154,112 @@ impl<T: Config> LiquidationPoolsManager<T::AccountId> for Pallet<T> {
 		);
 		Ok(())
 	}
+
+	/// Deposits `protocol_owned_liquidity_factor` of the liquidation pool balance above its ideal
+	/// balance into the liquidity pool. Wrapped tokens are minted to the liquidation pools account,
+	/// which accrues MNT as any other supplier.
+	///
+	/// Follows the deposit flow of `minterest-protocol` (interest accrual, MNT distribution and the
+	/// `Deposit` pause check), which can't be called from this pallet. The following checks of that
+	/// flow are skipped:
+	/// - whitelist membership: the liquidation pools account is not a user;
+	/// - zero deposit and free balance: the deposit is computed from the liquidation pool balance,
+	/// and a zero deposit is a no-op;
+	/// - reset of the liquidation attempts: the liquidation pools account has no loans.
+	fn deposit_protocol_owned_liquidity(pool_id: CurrencyId) -> BalanceResult {
+		let factor = Self::protocol_owned_liquidity_factor_storage(pool_id);
+		if factor.is_zero() {
+			return Ok(Balance::zero());
+		}
+
+		let module_account_id = Self::pools_account_id();
+		T::ControllerManager::accrue_interest_rate(pool_id)?;
+		T::MntManager::update_pool_mnt_supply_index(pool_id)?;
+		T::MntManager::distribute_supplier_mnt(pool_id, &module_account_id, false)?;
+		ensure!(
+			T::ControllerManager::is_operation_allowed(pool_id, Operation::Deposit),
+			Error::<T>::OperationPaused
+		);
+
+		// deposit_underlying = (liquidation_pool_balance - pool_ideal_balance) * factor
+		let pool_ideal_balance = Self::calculate_pool_ideal_balance_underlying(pool_id)?;
+		let idle_balance = Self::get_pool_available_liquidity(pool_id).saturating_sub(pool_ideal_balance);
+		let deposit_underlying = Rate::from_inner(idle_balance)
+			.checked_mul(&factor)
+			.map(|x| x.into_inner())
+			.ok_or(Error::<T>::NumOverflow)?;
+		if deposit_underlying.is_zero() {
+			return Ok(Balance::zero());
+		}
+
+		let wrapped_id = pool_id.wrapped_asset().ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
+		let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(pool_id)?;
+		let deposit_wrapped = T::LiquidityPoolsManager::underlying_to_wrapped(deposit_underlying, exchange_rate)?;
+
+		T::MultiCurrency::transfer(
+			pool_id,
+			&module_account_id,
+			&T::LiquidityPoolsManager::pools_account_id(),
+			deposit_underlying,
+		)?;
+		T::MultiCurrency::deposit(wrapped_id, &module_account_id, deposit_wrapped)?;
+
+		Self::deposit_event(Event::ProtocolOwnedLiquidityDeposited(
+			pool_id,
+			deposit_underlying,
+			deposit_wrapped,
+		));
+		Ok(deposit_underlying)
+	}
+
+	/// Redeems protocol-owned supply to cover the liquidation pool shortfall:
+	/// `pool_ideal_balance + required_underlying - liquidation_pool_balance`. The redeemed amount
+	/// is limited by protocol-owned supply and available liquidity of the liquidity pool.
+	fn redeem_protocol_owned_liquidity(pool_id: CurrencyId, required_underlying: Balance) -> BalanceResult {
+		let wrapped_id = pool_id.wrapped_asset().ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
+		let module_account_id = Self::pools_account_id();
+		let protocol_owned_wrapped = T::MultiCurrency::free_balance(wrapped_id, &module_account_id);
+		if protocol_owned_wrapped.is_zero() {
+			return Ok(Balance::zero());
+		}
+
+		T::ControllerManager::accrue_interest_rate(pool_id)?;
+		T::MntManager::update_pool_mnt_supply_index(pool_id)?;
+		T::MntManager::distribute_supplier_mnt(pool_id, &module_account_id, false)?;
+
+		let pool_ideal_balance = Self::calculate_pool_ideal_balance_underlying(pool_id)?;
+		let shortfall = pool_ideal_balance
+			.checked_add(required_underlying)
+			.ok_or(Error::<T>::BalanceOverflow)?
+			.saturating_sub(Self::get_pool_available_liquidity(pool_id));
+
+		let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(pool_id)?;
+		let protocol_owned_underlying =
+			T::LiquidityPoolsManager::wrapped_to_underlying(protocol_owned_wrapped, exchange_rate)?;
+		let redeem_underlying = shortfall
+			.min(protocol_owned_underlying)
+			.min(T::LiquidityPoolsManager::get_pool_available_liquidity(pool_id));
+		if redeem_underlying.is_zero() {
+			return Ok(Balance::zero());
+		}
+		let redeem_wrapped = match redeem_underlying == protocol_owned_underlying {
+			true => protocol_owned_wrapped,
+			false => T::LiquidityPoolsManager::underlying_to_wrapped(redeem_underlying, exchange_rate)?,
+		};
+
+		T::MultiCurrency::withdraw(wrapped_id, &module_account_id, redeem_wrapped)?;
+		T::MultiCurrency::transfer(
+			pool_id,
+			&T::LiquidityPoolsManager::pools_account_id(),
+			&module_account_id,
+			redeem_underlying,
+		)?;
+
+		Self::deposit_event(Event::ProtocolOwnedLiquidityRedeemed(
+			pool_id,
+			redeem_underlying,
+			redeem_wrapped,
+		));
+		Ok(redeem_underlying)
+	}
 }
@@ -3,10 +3,11 @@ use super::*;
 use crate as liquidation_pools;
 use frame_support::{ord_parameter_types, parameter_types, PalletId};
 use frame_system::EnsureSignedBy;
+use liquidity_pools::PoolUserData;
 use minterest_primitives::Price;
 pub use minterest_primitives::{currency::CurrencyType::WrappedToken, Balance, CurrencyId, Rate};
 use orml_traits::parameter_type_with_key;
-use pallet_traits::PricesManager;
+use pallet_traits::PriceSource;
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::testing::TestXt;
@@ -36,7 +37,7 @@ frame_support::construct_runtime!(
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
 		// Minterest pallets
 		TestLiquidationPools: liquidation_pools::{Pallet, Storage, Call, Event<T>, ValidateUnsigned},
-		TestLiquidityPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+		TestLiquidityPools: liquidity_pools::{Pallet, Storage, Call, Event, Config<T>},
 		TestDex: dex::{Pallet, Storage, Call, Event<T>},
 		Controller: controller::{Pallet, Storage, Call, Event, Config<T>},
 		MntToken: mnt_token::{Pallet, Storage, Call, Event<T>, Config<T>},
@@ -87,20 +88,23 @@ impl MockPriceSource {
 	}
 }
 
-impl PricesManager<CurrencyId> for MockPriceSource {
+impl PriceSource<CurrencyId> for MockPriceSource {
 	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
 		UNDERLYING_PRICE.with(|v| v.borrow().get(&currency_id).copied())
 	}
-
-	fn lock_price(_currency_id: CurrencyId) {}
-
-	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
 parameter_types! {
 	pub const LiquidationPoolsPalletId: PalletId = PalletId(*b"lqdn/min");
 	pub LiquidationPoolAccountId: AccountId = LiquidationPoolsPalletId::get().into_account();
 	pub const LiquidityPoolsPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const BalancingBackoffPeriod: u64 = 10;
+	pub const AuctionDuration: u64 = 20;
+	pub const MaxTreasuryWithdrawalPerCall: Balance = 10_000 * DOLLARS;
+	pub const MaxTreasuryWithdrawalPerPeriod: Balance = 15_000 * DOLLARS;
+	pub const TreasuryWithdrawalPeriod: u64 = 100;
+	pub const AtRiskDebtAccountsPerBlock: u32 = 1;
+	pub const DefaultMaxSlippage: Rate = Rate::from_inner(50_000_000_000_000_000); // 5%
 }
 
 ord_parameter_types! {
@@ -119,6 +123,15 @@ impl Config for Test {
 	type Dex = dex::Pallet<Test>;
 	type LiquidationPoolsWeightInfo = ();
 	type ControllerManager = Controller;
+	type MntManager = MntToken;
+	type BalancingBackoffPeriod = BalancingBackoffPeriod;
+	type AuctionDuration = AuctionDuration;
+	type MaxTreasuryWithdrawalPerCall = MaxTreasuryWithdrawalPerCall;
+	type MaxTreasuryWithdrawalPerPeriod = MaxTreasuryWithdrawalPerPeriod;
+	type TreasuryWithdrawalPeriod = TreasuryWithdrawalPeriod;
+	type AtRiskDebtAccountsPerBlock = AtRiskDebtAccountsPerBlock;
+	type DefaultMaxSlippage = DefaultMaxSlippage;
+	type TradingPathLimit = DexTradingPathLimit;
 }
 
 /// An extrinsic type used for tests.
@@ -132,6 +145,8 @@ where
 	type Extrinsic = Extrinsic;
 }
 
+pub const TREASURY: AccountId = 100;
+
 pub fn admin() -> Origin {
 	Origin::signed(ADMIN)
 }
@@ -139,7 +154,9 @@ pub fn admin() -> Origin {
 pub struct ExternalityBuilder {
 	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
 	liquidity_pools: Vec<(CurrencyId, PoolData)>,
+	pool_user_data: Vec<(CurrencyId, AccountId, PoolUserData)>,
 	liquidation_pools: Vec<(CurrencyId, LiquidationPoolData)>,
+	dex_liquidity_pools: Vec<(AccountId, CurrencyId, CurrencyId, Balance, Balance)>,
 }
 
 impl Default for ExternalityBuilder {
@@ -172,6 +189,7 @@ impl Default for ExternalityBuilder {
 					},
 				),
 			],
+			pool_user_data: vec![],
 			liquidation_pools: vec![
 				(
 					DOT,
@@ -206,6 +224,7 @@ impl Default for ExternalityBuilder {
 					},
 				),
 			],
+			dex_liquidity_pools: vec![],
 		}
 	}
 }
@@ -228,15 +247,41 @@ impl ExternalityBuilder {
 		self
 	}
 
+	pub fn pool_user_data(
+		mut self,
+		pool_id: CurrencyId,
+		user: AccountId,
+		borrowed: Balance,
+		is_collateral: bool,
+	) -> Self {
+		self.pool_user_data.push((
+			pool_id,
+			user,
+			PoolUserData {
+				borrowed,
+				interest_index: Rate::one(),
+				is_collateral,
+			},
+		));
+		self
+	}
+
 	pub fn liquidation_pool_balance(mut self, currency_id: CurrencyId, balance: Balance) -> Self {
 		self.endowed_accounts
 			.push((TestLiquidationPools::pools_account_id(), currency_id, balance));
 		self
 	}
 
-	pub fn dex_balance(mut self, currency_id: CurrencyId, balance: Balance) -> Self {
-		self.endowed_accounts
-			.push((TestDex::dex_account_id(), currency_id, balance));
+	pub fn dex_liquidity_pool(
+		mut self,
+		provider: AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		amount_a: Balance,
+		amount_b: Balance,
+	) -> Self {
+		self.dex_liquidity_pools
+			.push((provider, currency_id_a, currency_id_b, amount_a, amount_b));
 		self
 	}
 
@@ -251,7 +296,7 @@ impl ExternalityBuilder {
 
 		liquidity_pools::GenesisConfig::<Test> {
 			pools: self.liquidity_pools,
-			pool_user_data: vec![],
+			pool_user_data: self.pool_user_data,
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -264,7 +309,24 @@ impl ExternalityBuilder {
 		.unwrap();
 
 		let mut ext = TestExternalities::new(t);
-		ext.execute_with(|| System::set_block_number(1));
+		ext.execute_with(|| {
+			self.dex_liquidity_pools.into_iter().for_each(
+				|(provider, currency_id_a, currency_id_b, amount_a, amount_b)| {
+					Currencies::deposit(currency_id_a, &provider, amount_a).unwrap();
+					Currencies::deposit(currency_id_b, &provider, amount_b).unwrap();
+					TestDex::add_liquidity(
+						Origin::signed(provider),
+						currency_id_a,
+						currency_id_b,
+						amount_a,
+						amount_b,
+						Balance::zero(),
+					)
+					.expect("liquidity pool should be created");
+				},
+			);
+			System::set_block_number(1)
+		});
 		ext
 	}
 }
@@ -15,12 +15,13 @@ use minterest_primitives::Price;
 fn offchain_worker_balancing_test() {
 	// balance ratio = 0.2 for two pools. Price the same.
 	// The offchain worker must send transaction for balancing.
-	// It must change 10_000 ETH to 10_000 DOT
+	// It must change ETH to 10_000 DOT with max slippage 5%.
 	let mut ext = ExternalityBuilder::default()
 		.liquidation_pool_balance(DOT, 10_000 * DOLLARS)
 		.liquidation_pool_balance(ETH, 30_000 * DOLLARS)
 		.set_pool_borrow_underlying(DOT, 100_000 * DOLLARS)
 		.set_pool_borrow_underlying(ETH, 100_000 * DOLLARS)
+		.dex_liquidity_pool(CHARLIE, DOT, ETH, 1_000_000 * DOLLARS, 1_000_000 * DOLLARS)
 		.build();
 	let (offchain, _) = TestOffchainExt::new();
 
@@ -30,26 +31,80 @@ fn offchain_worker_balancing_test() {
 	ext.register_extension(TransactionPoolExt::new(pool));
 
 	ext.execute_with(|| {
-		assert_ok!(TestLiquidationPools::_offchain_worker(0));
+		assert_ok!(TestLiquidationPools::set_max_slippage(
+			admin(),
+			ETH,
+			DOT,
+			Rate::saturating_from_rational(5, 100).into_inner()
+		));
+		assert_ok!(TestLiquidationPools::_offchain_worker(1));
 
 		// 1 balancing transcation in transactions pool
 		assert_eq!(trans_pool_state.read().transactions.len(), 1);
 		let transaction = trans_pool_state.write().transactions.pop().unwrap();
 		let ex: Extrinsic = Decode::decode(&mut &*transaction).unwrap();
 		// Called extrinsic input params
-		let (supply_pool_id, target_pool_id, max_supply_amount, target_supply_amount) = match ex.call {
+		let (supply_pool_id, target_pool_id, max_supply_amount, target_supply_amount, path) = match ex.call {
 			crate::mock::Call::TestLiquidationPools(crate::Call::balance_liquidation_pools(
 				supply_pool_id,
 				target_pool_id,
 				max_supply_amount,
 				target_supply_amount,
-			)) => (supply_pool_id, target_pool_id, max_supply_amount, target_supply_amount),
+				path,
+			)) => (
+				supply_pool_id,
+				target_pool_id,
+				max_supply_amount,
+				target_supply_amount,
+				path,
+			),
 			e => panic!("Unexpected call: {:?}", e),
 		};
 		assert_eq!(supply_pool_id, ETH);
 		assert_eq!(target_pool_id, DOT);
-		assert_eq!(max_supply_amount, 10_000 * DOLLARS);
+		// 10_000 ETH + 5% of max slippage, DEX quote is ~10_131 ETH.
+		assert_eq!(max_supply_amount, 10_500 * DOLLARS);
 		assert_eq!(target_supply_amount, 10_000 * DOLLARS);
+		// The trading path is found off-chain.
+		assert_eq!(path, vec![ETH, DOT]);
+	});
+}
+
+#[test]
+fn offchain_worker_should_back_off_excessive_slippage() {
+	let mut ext = ExternalityBuilder::default()
+		.liquidation_pool_balance(DOT, 10_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, 30_000 * DOLLARS)
+		.set_pool_borrow_underlying(DOT, 100_000 * DOLLARS)
+		.set_pool_borrow_underlying(ETH, 100_000 * DOLLARS)
+		.dex_liquidity_pool(CHARLIE, DOT, ETH, 1_000_000 * DOLLARS, 1_000_000 * DOLLARS)
+		.build();
+	let (offchain, _) = TestOffchainExt::new();
+
+	let (pool, trans_pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		// Max slippage is zero, DEX quote (~10_131 ETH) exceeds 10_000 ETH.
+		assert_ok!(TestLiquidationPools::set_max_slippage(admin(), ETH, DOT, 0u128));
+		assert_ok!(TestLiquidationPools::_offchain_worker(1));
+		assert_eq!(trans_pool_state.read().transactions.len(), 0);
+
+		assert_ok!(TestLiquidationPools::set_max_slippage(
+			admin(),
+			ETH,
+			DOT,
+			Rate::saturating_from_rational(5, 100).into_inner()
+		));
+
+		// Balancing of ETH and DOT is postponed until block 11.
+		assert_ok!(TestLiquidationPools::_offchain_worker(10));
+		assert_eq!(trans_pool_state.read().transactions.len(), 0);
+
+		assert_ok!(TestLiquidationPools::_offchain_worker(11));
+		assert_eq!(trans_pool_state.read().transactions.len(), 1);
 	});
 }
 
@@ -118,7 +173,14 @@ fn protocol_operations_not_working_for_nonexisting_pool() {
 		);
 
 		assert_noop!(
-			TestLiquidationPools::balance_liquidation_pools(Origin::none(), KSM, DOT, Balance::zero(), Balance::zero()),
+			TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				KSM,
+				DOT,
+				Balance::zero(),
+				Balance::zero(),
+				vec![KSM, DOT]
+			),
 			Error::<Test>::PoolNotFound
 		);
 
@@ -259,6 +321,195 @@ fn set_max_ideal_balance_should_work() {
 	});
 }
 
+#[test]
+fn set_protocol_owned_liquidity_factor_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_ok!(TestLiquidationPools::set_protocol_owned_liquidity_factor(
+			admin(),
+			DOT,
+			Rate::saturating_from_rational(5, 10).into_inner()
+		));
+		assert_eq!(
+			TestLiquidationPools::protocol_owned_liquidity_factor_storage(DOT),
+			Rate::saturating_from_rational(5, 10)
+		);
+		let expected_event = Event::TestLiquidationPools(crate::Event::ProtocolOwnedLiquidityFactorChanged(
+			DOT,
+			Rate::saturating_from_rational(5, 10),
+		));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// Factor can't be greater than one.
+		assert_noop!(
+			TestLiquidationPools::set_protocol_owned_liquidity_factor(admin(), DOT, Rate::one().into_inner() + 1),
+			Error::<Test>::NotValidProtocolOwnedLiquidityFactorValue
+		);
+
+		// The dispatch origin of this call must be Root or half MinterestCouncil.
+		assert_noop!(
+			TestLiquidationPools::set_protocol_owned_liquidity_factor(alice_origin(), DOT, 10u128),
+			BadOrigin
+		);
+
+		// MDOT is wrong CurrencyId for underlying assets.
+		assert_noop!(
+			TestLiquidationPools::set_protocol_owned_liquidity_factor(admin(), MDOT, 10u128),
+			Error::<Test>::NotValidUnderlyingAssetId
+		);
+	});
+}
+
+#[test]
+fn deposit_and_redeem_protocol_owned_liquidity_should_work() {
+	ExternalityBuilder::default()
+		.liquidation_pool_balance(DOT, 10_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let liquidity_pool_balance = || Currencies::free_balance(DOT, &TestLiquidityPools::pools_account_id());
+			let protocol_owned_wrapped = || Currencies::free_balance(MDOT, &TestLiquidationPools::pools_account_id());
+
+			// Deposits are disabled by default.
+			assert_ok!(TestLiquidationPools::deposit_protocol_owned_liquidity(admin(), DOT));
+			assert_eq!(liquidation_pool_balance(DOT), 10_000 * DOLLARS);
+
+			assert_ok!(TestLiquidationPools::set_protocol_owned_liquidity_factor(
+				admin(),
+				DOT,
+				Rate::saturating_from_rational(5, 10).into_inner()
+			));
+
+			// Pool has no borrows, so its ideal balance is zero.
+			// deposit_underlying = 10_000 * 0.5 = 5_000
+			assert_ok!(TestLiquidationPools::deposit_protocol_owned_liquidity(admin(), DOT));
+			assert_eq!(liquidation_pool_balance(DOT), 5_000 * DOLLARS);
+			assert_eq!(liquidity_pool_balance(), 5_000 * DOLLARS);
+			assert_eq!(protocol_owned_wrapped(), 5_000 * DOLLARS);
+			let expected_event = Event::TestLiquidationPools(crate::Event::ProtocolOwnedLiquidityDeposited(
+				DOT,
+				5_000 * DOLLARS,
+				5_000 * DOLLARS,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// Liquidation pool balance covers the required amount, nothing is redeemed.
+			assert_eq!(
+				TestLiquidationPools::redeem_protocol_owned_liquidity(DOT, 5_000 * DOLLARS),
+				Ok(Balance::zero())
+			);
+
+			// redeem_underlying = 8_000 - 5_000 = 3_000
+			assert_eq!(
+				TestLiquidationPools::redeem_protocol_owned_liquidity(DOT, 8_000 * DOLLARS),
+				Ok(3_000 * DOLLARS)
+			);
+			assert_eq!(liquidation_pool_balance(DOT), 8_000 * DOLLARS);
+			assert_eq!(liquidity_pool_balance(), 2_000 * DOLLARS);
+			assert_eq!(protocol_owned_wrapped(), 2_000 * DOLLARS);
+			let expected_event = Event::TestLiquidationPools(crate::Event::ProtocolOwnedLiquidityRedeemed(
+				DOT,
+				3_000 * DOLLARS,
+				3_000 * DOLLARS,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// Redeemed amount is limited by protocol-owned supply.
+			assert_eq!(
+				TestLiquidationPools::redeem_protocol_owned_liquidity(DOT, 20_000 * DOLLARS),
+				Ok(2_000 * DOLLARS)
+			);
+			assert_eq!(liquidation_pool_balance(DOT), 10_000 * DOLLARS);
+			assert_eq!(liquidity_pool_balance(), Balance::zero());
+			assert_eq!(protocol_owned_wrapped(), Balance::zero());
+
+			// The dispatch origin of this call must be Root or half MinterestCouncil.
+			assert_noop!(
+				TestLiquidationPools::deposit_protocol_owned_liquidity(alice_origin(), DOT),
+				BadOrigin
+			);
+		});
+}
+
+#[test]
+fn collects_sales_list_should_redeem_protocol_owned_liquidity_first() {
+	ExternalityBuilder::default()
+		.set_pool_borrow_underlying(DOT, 500_000 * DOLLARS)
+		.liquidation_pool_balance(DOT, 400_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, 100_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			set_prices_for_assets(vec![
+				(DOT, Price::saturating_from_integer(1)),
+				(KSM, Price::saturating_from_integer(2)),
+				(ETH, Price::saturating_from_integer(5)),
+				(BTC, Price::saturating_from_integer(10)),
+			]);
+			assert_ok!(TestLiquidationPools::set_protocol_owned_liquidity_factor(
+				admin(),
+				DOT,
+				Rate::one().into_inner()
+			));
+
+			// Ideal balance of the DOT pool: 500_000 * 0.2 = 100_000
+			// deposit_underlying = 400_000 - 100_000 = 300_000
+			assert_ok!(TestLiquidationPools::deposit_protocol_owned_liquidity(admin(), DOT));
+			assert_eq!(liquidation_pool_balance(DOT), 100_000 * DOLLARS);
+
+			// The DOT pool balance falls below its left border: 100_000 - 100_000 * 0.1 = 90_000.
+			assert_ok!(Currencies::withdraw(
+				DOT,
+				&TestLiquidationPools::pools_account_id(),
+				50_000 * DOLLARS
+			));
+
+			// The shortfall is covered by the protocol-owned supply, so the ETH pool oversupply
+			// is not sold.
+			assert_eq!(TestLiquidationPools::collects_sales_list(), Ok(vec![]));
+			assert_eq!(liquidation_pool_balance(DOT), 100_000 * DOLLARS);
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestLiquidityPools::pools_account_id()),
+				250_000 * DOLLARS
+			);
+		});
+}
+
+#[test]
+fn protocol_owned_liquidity_should_accrue_mnt() {
+	ExternalityBuilder::default()
+		.liquidation_pool_balance(DOT, 10_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::deposit(MNT, &MntToken::get_account_id(), 1_000 * DOLLARS));
+			assert_ok!(MntToken::set_speed(admin(), DOT, 10 * DOLLARS));
+			assert_ok!(TestLiquidationPools::set_protocol_owned_liquidity_factor(
+				admin(),
+				DOT,
+				Rate::saturating_from_rational(5, 10).into_inner()
+			));
+			assert_ok!(TestLiquidationPools::deposit_protocol_owned_liquidity(admin(), DOT));
+			assert!(Currencies::free_balance(MNT, &TestLiquidationPools::pools_account_id()).is_zero());
+
+			// Protocol-owned supply is the only supply of the pool:
+			// 10 blocks * 10 MNT = 100 MNT are distributed to the liquidation pools account.
+			System::set_block_number(11);
+			assert_eq!(
+				TestLiquidationPools::redeem_protocol_owned_liquidity(DOT, 8_000 * DOLLARS),
+				Ok(3_000 * DOLLARS)
+			);
+			assert_eq!(
+				Currencies::free_balance(MNT, &TestLiquidationPools::pools_account_id()),
+				100 * DOLLARS
+			);
+			assert_eq!(
+				TestLiquidationPools::redeem_protocol_owned_liquidity(DOT, 8_000 * DOLLARS),
+				Ok(Balance::zero())
+			);
+			assert_eq!(
+				Currencies::free_balance(MNT, &TestLiquidationPools::pools_account_id()),
+				100 * DOLLARS
+			);
+		});
+}
+
 #[test]
 fn calculate_pool_ideal_balance_usd_should_work() {
 	ExternalityBuilder::default()
@@ -306,6 +557,109 @@ fn calculate_pool_ideal_balance_usd_should_work() {
 			);
 		});
 }
+#[test]
+fn calculate_pool_ideal_balance_usd_should_use_at_risk_debt() {
+	ExternalityBuilder::default()
+		.user_balance(ALICE, MDOT, 100 * DOLLARS)
+		.user_balance(BOB, MDOT, 100 * DOLLARS)
+		.user_balance(TestLiquidityPools::pools_account_id(), DOT, 200 * DOLLARS)
+		.set_pool_borrow_underlying(ETH, 70 * DOLLARS)
+		.pool_user_data(DOT, ALICE, Balance::zero(), true)
+		.pool_user_data(DOT, BOB, Balance::zero(), true)
+		.pool_user_data(ETH, ALICE, 30 * DOLLARS, false)
+		.pool_user_data(ETH, BOB, 40 * DOLLARS, false)
+		.build()
+		.execute_with(|| {
+			set_prices_for_assets(vec![(ETH, Price::saturating_from_integer(2))]);
+			assert_ok!(Controller::set_collateral_factor(
+				admin(),
+				DOT,
+				Rate::saturating_from_rational(9, 10)
+			));
+			// ALICE health factor: 100 DOT * 0.9 / (30 ETH * 2.0) = 1.5
+			// BOB health factor: 100 DOT * 0.9 / (40 ETH * 2.0) = 1.125
+
+			// Expected ideal balance: 70 ETH * 0.2 * 2.0 = 28$
+			assert_eq!(
+				TestLiquidationPools::calculate_pool_ideal_balance_usd(ETH),
+				Ok(28 * DOLLARS)
+			);
+
+			assert_ok!(TestLiquidationPools::set_ideal_balance_mode(
+				admin(),
+				ETH,
+				IdealBalanceMode::AtRiskDebt
+			));
+			// The ideal balance is calculated from the borrow ratio until the at-risk debt is computed.
+			assert_eq!(
+				TestLiquidationPools::calculate_pool_ideal_balance_usd(ETH),
+				Ok(28 * DOLLARS)
+			);
+			// A single pool member is checked per block.
+			TestLiquidationPools::on_initialize(1);
+			TestLiquidationPools::on_initialize(2);
+			assert_eq!(TestLiquidationPools::at_risk_debt_storage(ETH), None);
+			TestLiquidationPools::on_initialize(3);
+			// No accounts are at risk with the default health threshold 1.1.
+			assert_eq!(TestLiquidationPools::at_risk_debt_storage(ETH), Some(Balance::zero()));
+			assert_eq!(
+				TestLiquidationPools::calculate_pool_ideal_balance_usd(ETH),
+				Ok(Balance::zero())
+			);
+
+			assert_ok!(TestLiquidationPools::set_at_risk_health_threshold(
+				admin(),
+				Rate::saturating_from_rational(12, 10).into_inner()
+			));
+			// The at-risk debt is updated by the next pass.
+			(4..7).for_each(|block| {
+				TestLiquidationPools::on_initialize(block);
+			});
+			// Only BOB is at risk. Expected ideal balance: 40 ETH * 2.0 = 80$
+			assert_eq!(
+				TestLiquidationPools::calculate_pool_ideal_balance_usd(ETH),
+				Ok(80 * DOLLARS)
+			);
+			// BOB has no borrows in DOT pool.
+			assert_ok!(TestLiquidationPools::set_ideal_balance_mode(
+				admin(),
+				DOT,
+				IdealBalanceMode::AtRiskDebt
+			));
+			(7..10).for_each(|block| {
+				TestLiquidationPools::on_initialize(block);
+			});
+			assert_eq!(
+				TestLiquidationPools::calculate_pool_ideal_balance_usd(DOT),
+				Ok(Balance::zero())
+			);
+
+			assert_ok!(TestLiquidationPools::set_at_risk_health_threshold(
+				admin(),
+				Rate::saturating_from_integer(2).into_inner()
+			));
+			(10..13).for_each(|block| {
+				TestLiquidationPools::on_initialize(block);
+			});
+			// Both accounts are at risk. Expected ideal balance: 70 ETH * 2.0 = 140$
+			assert_eq!(
+				TestLiquidationPools::calculate_pool_ideal_balance_usd(ETH),
+				Ok(140 * DOLLARS)
+			);
+
+			assert_ok!(TestLiquidationPools::set_max_ideal_balance(
+				admin(),
+				ETH,
+				Some(100 * DOLLARS)
+			));
+			// Expected ideal balance: min(140, 100) = 100$
+			assert_eq!(
+				TestLiquidationPools::calculate_pool_ideal_balance_usd(ETH),
+				Ok(100 * DOLLARS)
+			);
+		});
+}
+
 #[test]
 fn transfer_to_liquidation_pool_should_work() {
 	ExternalityBuilder::default()
@@ -413,10 +767,9 @@ fn balance_liquidation_pools_should_work() {
 		.liquidation_pool_balance(KSM, 300_000 * DOLLARS)
 		.liquidation_pool_balance(ETH, 200_000 * DOLLARS)
 		.liquidation_pool_balance(BTC, 100_000 * DOLLARS)
-		.dex_balance(DOT, 500_000 * DOLLARS)
-		.dex_balance(KSM, 500_000 * DOLLARS)
-		.dex_balance(ETH, 500_000 * DOLLARS)
-		.dex_balance(BTC, 500_000 * DOLLARS)
+		// BTC is traded on the DEX at half of its oracle price.
+		.dex_liquidity_pool(CHARLIE, DOT, BTC, 1_000_000 * DOLLARS, 200_000 * DOLLARS)
+		.dex_liquidity_pool(CHARLIE, KSM, BTC, 1_000_000 * DOLLARS, 400_000 * DOLLARS)
 		.build()
 		.execute_with(|| {
 			set_prices_for_assets(vec![
@@ -457,7 +810,8 @@ fn balance_liquidation_pools_should_work() {
 
 			expected_sales_list.iter().for_each(|sale| {
 				if let Some((max_supply_amount, target_amount)) =
-					TestLiquidationPools::get_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount_usd).ok()
+					TestLiquidationPools::get_swap_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount_usd)
+						.ok()
 				{
 					let _ = TestLiquidationPools::balance_liquidation_pools(
 						Origin::none(),
@@ -465,6 +819,7 @@ fn balance_liquidation_pools_should_work() {
 						sale.target_pool_id,
 						max_supply_amount,
 						target_amount,
+						vec![sale.supply_pool_id, sale.target_pool_id],
 					);
 				};
 			});
@@ -486,22 +841,22 @@ fn balance_liquidation_pools_should_work() {
 					TestLiquidationPools::pools_account_id(),
 					DOT,
 					BTC,
-					300_000 * DOLLARS, // max_supply_amount = 300_000 DOT
-					30_000 * DOLLARS,  // target_amount = 30_000 BTC
+					177_001_593_014_337_129_034_162, // ~177_001 DOT, max_supply_amount = 315_000 DOT
+					30_000 * DOLLARS,                // target_amount = 30_000 BTC
 				),
 				dex::Event::Swap(
 					TestLiquidationPools::pools_account_id(),
 					KSM,
 					BTC,
-					100_000 * DOLLARS, // max_supply_amount = 100_000 DOT
-					20_000 * DOLLARS,  // target_amount = 20_000 BTC
+					52_789_948_793_749_670_062_821, // ~52_789 KSM, max_supply_amount = 105_000 KSM
+					20_000 * DOLLARS,               // target_amount = 20_000 BTC
 				),
 			];
 			assert_eq!(our_events, expected_events);
 
 			// Liquidation Pool balances
-			assert_eq!(liquidation_pool_balance(DOT), 100_000 * DOLLARS);
-			assert_eq!(liquidation_pool_balance(KSM), 200_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(DOT), 222_998_406_985_662_870_965_838);
+			assert_eq!(liquidation_pool_balance(KSM), 247_210_051_206_250_329_937_179);
 			assert_eq!(liquidation_pool_balance(ETH), 200_000 * DOLLARS);
 			assert_eq!(liquidation_pool_balance(BTC), 150_000 * DOLLARS);
 		});
@@ -514,8 +869,8 @@ fn balance_liquidation_pools_two_pools_should_work_test() {
 		.set_pool_borrow_underlying(ETH, 300_000 * DOLLARS)
 		.liquidation_pool_balance(DOT, 170_000 * DOLLARS) // + 140_000$
 		.liquidation_pool_balance(ETH, 30_000 * DOLLARS) //- 120_000$
-		.dex_balance(DOT, 500_000 * DOLLARS)
-		.dex_balance(ETH, 500_000 * DOLLARS)
+		// ETH is traded on the DEX at half of its oracle price.
+		.dex_liquidity_pool(CHARLIE, DOT, ETH, 500_000 * DOLLARS, 500_000 * DOLLARS)
 		.build()
 		.execute_with(|| {
 			set_prices_for_assets(vec![
@@ -544,7 +899,8 @@ fn balance_liquidation_pools_two_pools_should_work_test() {
 
 			expected_sales_list.iter().for_each(|sale| {
 				if let Some((max_supply_amount, target_amount)) =
-					TestLiquidationPools::get_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount_usd).ok()
+					TestLiquidationPools::get_swap_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount_usd)
+						.ok()
 				{
 					let _ = TestLiquidationPools::balance_liquidation_pools(
 						Origin::none(),
@@ -552,6 +908,7 @@ fn balance_liquidation_pools_two_pools_should_work_test() {
 						sale.target_pool_id,
 						max_supply_amount,
 						target_amount,
+						vec![sale.supply_pool_id, sale.target_pool_id],
 					);
 				};
 			});
@@ -572,14 +929,886 @@ fn balance_liquidation_pools_two_pools_should_work_test() {
 				TestLiquidationPools::pools_account_id(),
 				DOT,
 				ETH,
-				60_000 * DOLLARS, // max_supply_amount = 60_000 DOT
-				30_000 * DOLLARS, // target_amount = 30_000 ETH
+				32_010_926_396_209_906_314_690, // ~32_010 DOT, max_supply_amount = 63_000 DOT
+				30_000 * DOLLARS,               // target_amount = 30_000 ETH
 			)];
 
 			assert_eq!(our_events, expected_events);
 
 			// Liquidation Pool balances
-			assert_eq!(liquidation_pool_balance(DOT), 110_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(DOT), 137_989_073_603_790_093_685_310);
 			assert_eq!(liquidation_pool_balance(ETH), 60_000 * DOLLARS);
 		});
 }
+
+#[test]
+fn balance_liquidation_pools_should_swap_through_trading_path() {
+	ExternalityBuilder::default()
+		.set_pool_borrow_underlying(KSM, 1_000_000 * DOLLARS)
+		.set_pool_borrow_underlying(BTC, 2_000_000 * DOLLARS)
+		.liquidation_pool_balance(KSM, 300_000 * DOLLARS)
+		.liquidation_pool_balance(BTC, 100_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			// Sales list: [(KSM, BTC, 100_000$)]
+			set_prices_for_assets(vec![
+				(KSM, Price::saturating_from_integer(1)),
+				(BTC, Price::saturating_from_integer(5)),
+			]);
+
+			// There are no liquidity pools on DEX yet.
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				KSM,
+				BTC,
+				105_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![KSM, BTC]
+			));
+			let expected_event = Event::TestLiquidationPools(crate::Event::BalancingSwapFailed(KSM, BTC, 11));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(liquidation_pool_balance(KSM), 300_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(BTC), 100_000 * DOLLARS);
+		});
+
+	ExternalityBuilder::default()
+		.set_pool_borrow_underlying(KSM, 1_000_000 * DOLLARS)
+		.set_pool_borrow_underlying(BTC, 2_000_000 * DOLLARS)
+		.liquidation_pool_balance(KSM, 300_000 * DOLLARS)
+		.liquidation_pool_balance(BTC, 100_000 * DOLLARS)
+		// KSM and BTC have no common liquidity pool, they are traded through DOT.
+		.dex_liquidity_pool(CHARLIE, KSM, DOT, 1_000_000 * DOLLARS, 4_000_000 * DOLLARS)
+		.dex_liquidity_pool(CHARLIE, DOT, BTC, 4_000_000 * DOLLARS, 800_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			set_prices_for_assets(vec![
+				(KSM, Price::saturating_from_integer(1)),
+				(BTC, Price::saturating_from_integer(5)),
+			]);
+
+			// The trading path must lead from the supply pool to the target pool.
+			let invalid_path_call = crate::Call::<Test>::balance_liquidation_pools(
+				KSM,
+				BTC,
+				105_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![DOT, BTC],
+			);
+			assert_eq!(
+				TestLiquidationPools::validate_unsigned(TransactionSource::Local, &invalid_path_call),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					KSM,
+					BTC,
+					105_000 * DOLLARS,
+					20_000 * DOLLARS,
+					vec![DOT, BTC]
+				),
+				Error::<Test>::InvalidTradingPath
+			);
+			// There is no direct liquidity pool of KSM and BTC.
+			let unavailable_path_call = crate::Call::<Test>::balance_liquidation_pools(
+				KSM,
+				BTC,
+				105_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![KSM, BTC],
+			);
+			assert_eq!(
+				TestLiquidationPools::validate_unsigned(TransactionSource::Local, &unavailable_path_call),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+
+			let path_call = crate::Call::<Test>::balance_liquidation_pools(
+				KSM,
+				BTC,
+				105_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![KSM, DOT, BTC],
+			);
+			assert!(TestLiquidationPools::validate_unsigned(TransactionSource::Local, &path_call).is_ok());
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				KSM,
+				BTC,
+				105_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![KSM, DOT, BTC]
+			));
+
+			// 20_000 BTC <- ~102_872 DOT <- ~26_476 KSM
+			let expected_event = Event::TestDex(dex::Event::Swap(
+				TestLiquidationPools::pools_account_id(),
+				KSM,
+				BTC,
+				26_476_494_128_797_712_197_915,
+				20_000 * DOLLARS,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			assert_eq!(liquidation_pool_balance(KSM), 273_523_505_871_202_287_802_085);
+			assert_eq!(liquidation_pool_balance(DOT), Balance::zero());
+			assert_eq!(liquidation_pool_balance(BTC), 120_000 * DOLLARS);
+		});
+}
+
+#[test]
+fn balance_liquidation_pools_should_back_off_failed_swaps() {
+	ExternalityBuilder::default()
+		.set_pool_borrow_underlying(ETH, 100_000 * DOLLARS)
+		.liquidation_pool_balance(DOT, 100_000 * DOLLARS)
+		.dex_liquidity_pool(CHARLIE, DOT, ETH, 1_000_000 * DOLLARS, 1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			// Sales list: [(DOT, ETH, 20_000$)]
+			// DEX requires ~20_469 DOT for 20_000 ETH, max slippage is zero.
+			assert_ok!(TestLiquidationPools::set_max_slippage(admin(), DOT, ETH, 0u128));
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				DOT,
+				ETH,
+				20_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![DOT, ETH]
+			));
+			let expected_event = Event::TestLiquidationPools(crate::Event::BalancingSwapFailed(DOT, ETH, 11));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(
+				TestLiquidationPools::balancing_backoff_storage(DOT, ETH),
+				Some(BalancingBackoff {
+					failures: 1,
+					retry_block: 11,
+				})
+			);
+			assert_eq!(liquidation_pool_balance(DOT), 100_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(ETH), Balance::zero());
+
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					DOT,
+					ETH,
+					20_000 * DOLLARS,
+					20_000 * DOLLARS,
+					vec![DOT, ETH]
+				),
+				Error::<Test>::BalancingPostponed
+			);
+
+			// The backoff period doubles with each consecutive failure.
+			System::set_block_number(11);
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				DOT,
+				ETH,
+				20_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![DOT, ETH]
+			));
+			assert_eq!(
+				TestLiquidationPools::balancing_backoff_storage(DOT, ETH),
+				Some(BalancingBackoff {
+					failures: 2,
+					retry_block: 31,
+				})
+			);
+
+			// Successful swap resets the backoff.
+			System::set_block_number(31);
+			assert_ok!(TestLiquidationPools::set_max_slippage(
+				admin(),
+				DOT,
+				ETH,
+				Rate::saturating_from_rational(3, 100).into_inner()
+			));
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				DOT,
+				ETH,
+				20_600 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![DOT, ETH]
+			));
+			let expected_event = Event::TestLiquidationPools(crate::Event::LiquidationPoolsBalanced);
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestLiquidationPools::balancing_backoff_storage(DOT, ETH), None);
+			assert_eq!(liquidation_pool_balance(DOT), 79_530_428_018_750_127_934_824);
+			assert_eq!(liquidation_pool_balance(ETH), 20_000 * DOLLARS);
+		});
+}
+
+#[test]
+fn get_balancing_plan_should_report_sale_status() {
+	ExternalityBuilder::default()
+		.set_pool_borrow_underlying(ETH, 100_000 * DOLLARS)
+		.liquidation_pool_balance(DOT, 100_000 * DOLLARS)
+		.user_balance(CHARLIE, DOT, 1_000_000 * DOLLARS)
+		.user_balance(CHARLIE, ETH, 1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let sale = Sales {
+				supply_pool_id: DOT,
+				target_pool_id: ETH,
+				amount_usd: 20_000 * DOLLARS,
+			};
+			let sales_with_status = || TestLiquidationPools::get_balancing_plan().map(|(_, sales)| sales);
+
+			assert_eq!(
+				sales_with_status(),
+				Ok(vec![(sale.clone(), SaleStatus::TradingPathNotFound)])
+			);
+
+			// DEX requires ~20_469 DOT for 20_000 ETH.
+			assert_ok!(TestDex::add_liquidity(
+				Origin::signed(CHARLIE),
+				DOT,
+				ETH,
+				1_000_000 * DOLLARS,
+				1_000_000 * DOLLARS,
+				0
+			));
+			assert_eq!(sales_with_status(), Ok(vec![(sale.clone(), SaleStatus::Ready)]));
+			assert_ok!(TestLiquidationPools::set_max_slippage(admin(), DOT, ETH, 0u128));
+			assert_eq!(
+				sales_with_status(),
+				Ok(vec![(sale.clone(), SaleStatus::ExcessiveSlippage)])
+			);
+
+			BalancingBackoffStorage::<Test>::insert(
+				DOT,
+				ETH,
+				BalancingBackoff {
+					failures: 1,
+					retry_block: 11,
+				},
+			);
+			assert_eq!(sales_with_status(), Ok(vec![(sale.clone(), SaleStatus::Postponed)]));
+			System::set_block_number(11);
+			PoolAuctionStorage::<Test>::insert(ETH, 0);
+			assert_eq!(sales_with_status(), Ok(vec![(sale, SaleStatus::AuctionInProgress)]));
+		});
+}
+
+#[test]
+fn balancing_should_back_off_only_unavailable_sales() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert!(TestLiquidationPools::is_balancing_backoff_error(
+			Error::<Test>::TradingPathNotFound.into()
+		));
+		assert!(TestLiquidationPools::is_balancing_backoff_error(
+			Error::<Test>::ExcessiveSlippage.into()
+		));
+		// Other errors of the swap fail the call and are not recorded.
+		assert!(!TestLiquidationPools::is_balancing_backoff_error(
+			dex::Error::<Test>::InsufficientLiquidity.into()
+		));
+		assert!(!TestLiquidationPools::is_balancing_backoff_error(
+			Error::<Test>::BalanceOverflow.into()
+		));
+	});
+}
+
+#[test]
+fn set_max_slippage_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_ok!(TestLiquidationPools::set_max_slippage(
+			admin(),
+			DOT,
+			ETH,
+			Rate::saturating_from_rational(3, 100).into_inner()
+		));
+		assert_eq!(
+			TestLiquidationPools::max_slippage_storage(DOT, ETH),
+			Rate::saturating_from_rational(3, 100)
+		);
+		// Max slippage is set for the direction of the swap.
+		// The maximum slippage of the pairs of pools that is not set is `DefaultMaxSlippage`.
+		assert_eq!(
+			TestLiquidationPools::max_slippage_storage(ETH, DOT),
+			Rate::saturating_from_rational(5, 100)
+		);
+		let expected_event = Event::TestLiquidationPools(crate::Event::MaxSlippageChanged(
+			DOT,
+			ETH,
+			Rate::saturating_from_rational(3, 100),
+		));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// Max slippage can't be greater than one.
+		assert_noop!(
+			TestLiquidationPools::set_max_slippage(admin(), DOT, ETH, Rate::one().into_inner() + 1),
+			Error::<Test>::NotValidMaxSlippageValue
+		);
+
+		assert_noop!(
+			TestLiquidationPools::set_max_slippage(admin(), DOT, DOT, 10u128),
+			Error::<Test>::IdenticalPools
+		);
+
+		// The dispatch origin of this call must be Root or half MinterestCouncil.
+		assert_noop!(
+			TestLiquidationPools::set_max_slippage(alice_origin(), DOT, ETH, 10u128),
+			BadOrigin
+		);
+
+		// MDOT is wrong CurrencyId for underlying assets.
+		assert_noop!(
+			TestLiquidationPools::set_max_slippage(admin(), MDOT, ETH, 10u128),
+			Error::<Test>::NotValidUnderlyingAssetId
+		);
+	});
+}
+
+#[test]
+fn balance_liquidation_pools_should_match_required_sales() {
+	ExternalityBuilder::default()
+		.set_pool_borrow_underlying(DOT, 500_000 * DOLLARS)
+		.set_pool_borrow_underlying(KSM, 1_000_000 * DOLLARS)
+		.set_pool_borrow_underlying(ETH, 1_500_000 * DOLLARS)
+		.set_pool_borrow_underlying(BTC, 2_000_000 * DOLLARS)
+		.liquidation_pool_balance(DOT, 400_000 * DOLLARS)
+		.liquidation_pool_balance(KSM, 300_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, 200_000 * DOLLARS)
+		.liquidation_pool_balance(BTC, 100_000 * DOLLARS)
+		.dex_liquidity_pool(CHARLIE, DOT, BTC, 1_000_000 * DOLLARS, 200_000 * DOLLARS)
+		.dex_liquidity_pool(CHARLIE, KSM, BTC, 1_000_000 * DOLLARS, 400_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			set_prices_for_assets(vec![
+				(DOT, Price::saturating_from_integer(1)),
+				(KSM, Price::saturating_from_integer(2)),
+				(ETH, Price::saturating_from_integer(5)),
+				(BTC, Price::saturating_from_integer(10)),
+			]);
+
+			// Sales list: [(DOT, BTC, 300_000$), (KSM, BTC, 200_000$)]
+			let dot_call = crate::Call::<Test>::balance_liquidation_pools(
+				DOT,
+				BTC,
+				315_000 * DOLLARS,
+				30_000 * DOLLARS,
+				vec![DOT, BTC],
+			);
+			let ksm_call = crate::Call::<Test>::balance_liquidation_pools(
+				KSM,
+				BTC,
+				105_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![KSM, BTC],
+			);
+			let dot_validity = TestLiquidationPools::validate_unsigned(TransactionSource::Local, &dot_call);
+			let ksm_validity = TestLiquidationPools::validate_unsigned(TransactionSource::Local, &ksm_call);
+			assert!(dot_validity.is_ok() && ksm_validity.is_ok());
+			// Both sales can be included in the same block.
+			assert_ne!(dot_validity.unwrap().provides, ksm_validity.unwrap().provides);
+
+			// Pools are not in the sales list.
+			let invalid_call = crate::Call::<Test>::balance_liquidation_pools(
+				ETH,
+				BTC,
+				10_000 * DOLLARS,
+				5_000 * DOLLARS,
+				vec![ETH, BTC],
+			);
+			assert_eq!(
+				TestLiquidationPools::validate_unsigned(TransactionSource::Local, &invalid_call),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					ETH,
+					BTC,
+					10_000 * DOLLARS,
+					5_000 * DOLLARS,
+					vec![ETH, BTC]
+				),
+				Error::<Test>::SaleNotRequired
+			);
+
+			// Target amount exceeds the amount required for the sale by more than 1%.
+			let invalid_call = crate::Call::<Test>::balance_liquidation_pools(
+				DOT,
+				BTC,
+				315_000 * DOLLARS,
+				30_301 * DOLLARS,
+				vec![DOT, BTC],
+			);
+			assert_eq!(
+				TestLiquidationPools::validate_unsigned(TransactionSource::Local, &invalid_call),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					DOT,
+					BTC,
+					315_000 * DOLLARS,
+					30_301 * DOLLARS,
+					vec![DOT, BTC]
+				),
+				Error::<Test>::SaleNotRequired
+			);
+
+			// Maximum supply amount exceeds the amount required for the sale by more than 1%.
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					KSM,
+					BTC,
+					106_051 * DOLLARS,
+					20_000 * DOLLARS,
+					vec![KSM, BTC]
+				),
+				Error::<Test>::SaleNotRequired
+			);
+
+			// Lowered maximum supply amount or target amount doesn't match the sale, so the swap
+			// can't be made to fail and postpone balancing.
+			let lowered_max_supply_call =
+				crate::Call::<Test>::balance_liquidation_pools(KSM, BTC, 1, 20_000 * DOLLARS, vec![KSM, BTC]);
+			assert_eq!(
+				TestLiquidationPools::validate_unsigned(TransactionSource::Local, &lowered_max_supply_call),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					KSM,
+					BTC,
+					1,
+					20_000 * DOLLARS,
+					vec![KSM, BTC]
+				),
+				Error::<Test>::SaleNotRequired
+			);
+			let lowered_target_call = crate::Call::<Test>::balance_liquidation_pools(
+				KSM,
+				BTC,
+				105_000 * DOLLARS,
+				19_000 * DOLLARS,
+				vec![KSM, BTC],
+			);
+			assert_eq!(
+				TestLiquidationPools::validate_unsigned(TransactionSource::Local, &lowered_target_call),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					KSM,
+					BTC,
+					105_000 * DOLLARS,
+					19_000 * DOLLARS,
+					vec![KSM, BTC]
+				),
+				Error::<Test>::SaleNotRequired
+			);
+
+			// Amounts within 1% of the sale are accepted, the swap is made with the amounts of
+			// the sale.
+			let tolerated_call = crate::Call::<Test>::balance_liquidation_pools(
+				KSM,
+				BTC,
+				104_500 * DOLLARS,
+				19_900 * DOLLARS,
+				vec![KSM, BTC],
+			);
+			assert!(TestLiquidationPools::validate_unsigned(TransactionSource::Local, &tolerated_call).is_ok());
+
+			// The rest of the sales list is still required after the first sale.
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				DOT,
+				BTC,
+				315_000 * DOLLARS,
+				30_000 * DOLLARS,
+				vec![DOT, BTC]
+			));
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				KSM,
+				BTC,
+				105_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![KSM, BTC]
+			));
+			assert_eq!(liquidation_pool_balance(BTC), 150_000 * DOLLARS);
+		});
+}
+
+#[test]
+fn set_balancing_mode_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_eq!(TestLiquidationPools::balancing_mode_storage(DOT), BalancingMode::Dex);
+		assert_ok!(TestLiquidationPools::set_balancing_mode(
+			admin(),
+			DOT,
+			BalancingMode::Auction
+		));
+		assert_eq!(
+			TestLiquidationPools::balancing_mode_storage(DOT),
+			BalancingMode::Auction
+		);
+		let expected_event =
+			Event::TestLiquidationPools(crate::Event::BalancingModeChanged(DOT, BalancingMode::Auction));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The dispatch origin of this call must be Root or half MinterestCouncil.
+		assert_noop!(
+			TestLiquidationPools::set_balancing_mode(alice_origin(), DOT, BalancingMode::Dex),
+			BadOrigin
+		);
+
+		// MDOT is wrong CurrencyId for underlying assets.
+		assert_noop!(
+			TestLiquidationPools::set_balancing_mode(admin(), MDOT, BalancingMode::Dex),
+			Error::<Test>::NotValidUnderlyingAssetId
+		);
+	});
+}
+
+#[test]
+fn auction_should_work() {
+	ExternalityBuilder::default()
+		.set_pool_borrow_underlying(ETH, 100_000 * DOLLARS)
+		.liquidation_pool_balance(DOT, 100_000 * DOLLARS)
+		.user_balance(ALICE, ETH, 30_000 * DOLLARS)
+		.user_balance(BOB, ETH, 30_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestLiquidationPools::set_balancing_mode(
+				admin(),
+				DOT,
+				BalancingMode::Auction
+			));
+
+			// Sales list: [(DOT, ETH, 20_000$)]
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				DOT,
+				ETH,
+				21_000 * DOLLARS,
+				20_000 * DOLLARS,
+				vec![DOT, ETH]
+			));
+			let expected_event = Event::TestLiquidationPools(crate::Event::AuctionStarted(
+				0,
+				DOT,
+				ETH,
+				21_000 * DOLLARS,
+				20_000 * DOLLARS,
+				21,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(liquidation_pool_balance(DOT), 79_000 * DOLLARS);
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestLiquidationPools::auctions_account_id()),
+				21_000 * DOLLARS
+			);
+			assert_eq!(TestLiquidationPools::pool_auction_storage(DOT), Some(0));
+			assert_eq!(TestLiquidationPools::pool_auction_storage(ETH), Some(0));
+
+			// The pools take part in the auction.
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					DOT,
+					ETH,
+					21_000 * DOLLARS,
+					20_000 * DOLLARS,
+					vec![DOT, ETH]
+				),
+				Error::<Test>::AuctionInProgress
+			);
+
+			assert_noop!(
+				TestLiquidationPools::bid(Origin::signed(ALICE), 0, 19_999 * DOLLARS),
+				Error::<Test>::BidTooLow
+			);
+			assert_noop!(
+				TestLiquidationPools::bid(Origin::signed(ALICE), 1, 20_000 * DOLLARS),
+				Error::<Test>::AuctionNotFound
+			);
+			assert_ok!(TestLiquidationPools::bid(Origin::signed(ALICE), 0, 20_000 * DOLLARS));
+			assert_eq!(Currencies::free_balance(ETH, &ALICE), 10_000 * DOLLARS);
+			assert_noop!(
+				TestLiquidationPools::bid(Origin::signed(BOB), 0, 20_000 * DOLLARS),
+				Error::<Test>::BidTooLow
+			);
+
+			// The previous highest bid is returned to ALICE.
+			assert_ok!(TestLiquidationPools::bid(Origin::signed(BOB), 0, 21_000 * DOLLARS));
+			let expected_event = Event::TestLiquidationPools(crate::Event::AuctionBid(0, BOB, 21_000 * DOLLARS));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(Currencies::free_balance(ETH, &ALICE), 30_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &BOB), 9_000 * DOLLARS);
+
+			System::set_block_number(21);
+			assert_noop!(
+				TestLiquidationPools::bid(Origin::signed(ALICE), 0, 22_000 * DOLLARS),
+				Error::<Test>::AuctionEnded
+			);
+
+			TestLiquidationPools::on_initialize(21);
+			let expected_event = Event::TestLiquidationPools(crate::Event::AuctionSettled(0, BOB, 21_000 * DOLLARS));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestLiquidationPools::auctions_storage(0), None);
+			assert_eq!(TestLiquidationPools::pool_auction_storage(DOT), None);
+			assert_eq!(TestLiquidationPools::pool_auction_storage(ETH), None);
+			assert_eq!(Currencies::free_balance(DOT, &BOB), 21_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(DOT), 79_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(ETH), 21_000 * DOLLARS);
+			assert!(Currencies::free_balance(DOT, &TestLiquidationPools::auctions_account_id()).is_zero());
+			assert!(Currencies::free_balance(ETH, &TestLiquidationPools::auctions_account_id()).is_zero());
+		});
+}
+
+#[test]
+fn auction_without_bids_should_return_lot() {
+	ExternalityBuilder::default()
+		.set_pool_borrow_underlying(ETH, 100_000 * DOLLARS)
+		.liquidation_pool_balance(DOT, 100_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestLiquidationPools::set_balancing_mode(
+				admin(),
+				DOT,
+				BalancingMode::Auction
+			));
+			// Sales list: [(DOT, ETH, 20_000$)]
+			// The lot and the minimum bid are the amounts of the sale, not the call parameters.
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				DOT,
+				ETH,
+				20_800 * DOLLARS,
+				19_950 * DOLLARS,
+				vec![DOT, ETH]
+			));
+			let expected_event = Event::TestLiquidationPools(crate::Event::AuctionStarted(
+				0,
+				DOT,
+				ETH,
+				21_000 * DOLLARS,
+				20_000 * DOLLARS,
+				21,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// The auction is not ended yet.
+			TestLiquidationPools::on_initialize(20);
+			assert!(TestLiquidationPools::auctions_storage(0).is_some());
+
+			System::set_block_number(21);
+			TestLiquidationPools::on_initialize(21);
+			let expected_event = Event::TestLiquidationPools(crate::Event::AuctionCancelled(0));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestLiquidationPools::auctions_storage(0), None);
+			assert_eq!(TestLiquidationPools::pool_auction_storage(DOT), None);
+			assert_eq!(liquidation_pool_balance(DOT), 100_000 * DOLLARS);
+			assert_eq!(
+				TestLiquidationPools::balancing_backoff_storage(DOT, ETH),
+				Some(BalancingBackoff {
+					failures: 1,
+					retry_block: 31,
+				})
+			);
+		});
+}
+
+#[test]
+fn set_ideal_balance_mode_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			TestLiquidationPools::ideal_balance_mode_storage(DOT),
+			IdealBalanceMode::BorrowRatio
+		);
+		assert_ok!(TestLiquidationPools::set_ideal_balance_mode(
+			admin(),
+			DOT,
+			IdealBalanceMode::AtRiskDebt
+		));
+		assert_eq!(
+			TestLiquidationPools::ideal_balance_mode_storage(DOT),
+			IdealBalanceMode::AtRiskDebt
+		);
+		let expected_event =
+			Event::TestLiquidationPools(crate::Event::IdealBalanceModeChanged(DOT, IdealBalanceMode::AtRiskDebt));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The dispatch origin of this call must be Root or half MinterestCouncil.
+		assert_noop!(
+			TestLiquidationPools::set_ideal_balance_mode(alice_origin(), DOT, IdealBalanceMode::BorrowRatio),
+			BadOrigin
+		);
+
+		// MDOT is wrong CurrencyId for underlying assets.
+		assert_noop!(
+			TestLiquidationPools::set_ideal_balance_mode(admin(), MDOT, IdealBalanceMode::BorrowRatio),
+			Error::<Test>::NotValidUnderlyingAssetId
+		);
+
+		// There is no liquidity pool for KSM.
+		assert_noop!(
+			TestLiquidationPools::set_ideal_balance_mode(admin(), KSM, IdealBalanceMode::BorrowRatio),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn set_at_risk_health_threshold_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			TestLiquidationPools::at_risk_health_threshold_storage(),
+			Rate::saturating_from_rational(11, 10)
+		);
+		let new_threshold = Rate::saturating_from_rational(12, 10);
+		assert_ok!(TestLiquidationPools::set_at_risk_health_threshold(
+			admin(),
+			new_threshold.into_inner()
+		));
+		assert_eq!(TestLiquidationPools::at_risk_health_threshold_storage(), new_threshold);
+		let expected_event = Event::TestLiquidationPools(crate::Event::AtRiskHealthThresholdChanged(new_threshold));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The dispatch origin of this call must be Root or half MinterestCouncil.
+		assert_noop!(
+			TestLiquidationPools::set_at_risk_health_threshold(alice_origin(), new_threshold.into_inner()),
+			BadOrigin
+		);
+
+		// Accounts with health factor below 1 can be liquidated, the threshold can't be less than 1.
+		assert_noop!(
+			TestLiquidationPools::set_at_risk_health_threshold(
+				admin(),
+				Rate::saturating_from_rational(9, 10).into_inner()
+			),
+			Error::<Test>::NotValidHealthThresholdValue
+		);
+	});
+}
+
+#[test]
+fn set_treasury_account_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_eq!(TestLiquidationPools::treasury_account_storage(), None);
+		assert_ok!(TestLiquidationPools::set_treasury_account(admin(), TREASURY));
+		assert_eq!(TestLiquidationPools::treasury_account_storage(), Some(TREASURY));
+		let expected_event = Event::TestLiquidationPools(crate::Event::TreasuryAccountChanged(TREASURY));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The dispatch origin of this call must be Root or half MinterestCouncil.
+		assert_noop!(
+			TestLiquidationPools::set_treasury_account(alice_origin(), ALICE),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn withdraw_to_treasury_should_work() {
+	ExternalityBuilder::default()
+		.liquidation_pool_balance(DOT, 50_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			// There is no surplus while the maximum ideal balance is not set.
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(admin(), DOT, DOLLARS),
+				Error::<Test>::WithdrawalExceedsSurplus
+			);
+
+			// Surplus: 50_000 - 10_000 * (1 + 0.1) = 39_000
+			assert_ok!(TestLiquidationPools::set_max_ideal_balance(
+				admin(),
+				DOT,
+				Some(10_000 * DOLLARS)
+			));
+
+			// There are no withdrawals until the treasury account is set.
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(admin(), DOT, DOLLARS),
+				Error::<Test>::TreasuryAccountNotSet
+			);
+			assert_ok!(TestLiquidationPools::set_treasury_account(admin(), TREASURY));
+
+			// The dispatch origin of this call must be Root or half MinterestCouncil.
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(alice_origin(), DOT, DOLLARS),
+				BadOrigin
+			);
+			// MDOT is wrong CurrencyId for underlying assets.
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(admin(), MDOT, DOLLARS),
+				Error::<Test>::NotValidUnderlyingAssetId
+			);
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(admin(), KSM, DOLLARS),
+				Error::<Test>::PoolNotFound
+			);
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(admin(), DOT, Balance::zero()),
+				Error::<Test>::ZeroBalanceTransaction
+			);
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(admin(), DOT, 39_001 * DOLLARS),
+				Error::<Test>::WithdrawalExceedsSurplus
+			);
+			// Limit per call: 10_000$
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(admin(), DOT, 10_001 * DOLLARS),
+				Error::<Test>::WithdrawalLimitExceeded
+			);
+
+			assert_ok!(TestLiquidationPools::withdraw_to_treasury(
+				admin(),
+				DOT,
+				10_000 * DOLLARS
+			));
+			assert_eq!(liquidation_pool_balance(DOT), 40_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(DOT, &TREASURY), 10_000 * DOLLARS);
+			assert_eq!(
+				TestLiquidationPools::treasury_withdrawals_storage(),
+				(0, 10_000 * DOLLARS)
+			);
+			let expected_event = Event::TestLiquidationPools(crate::Event::WithdrawnToTreasury(
+				DOT,
+				10_000 * DOLLARS,
+				10_000 * DOLLARS,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// Limit per period: 15_000$
+			assert_noop!(
+				TestLiquidationPools::withdraw_to_treasury(admin(), DOT, 5_001 * DOLLARS),
+				Error::<Test>::PeriodWithdrawalLimitExceeded
+			);
+			assert_ok!(TestLiquidationPools::withdraw_to_treasury(
+				admin(),
+				DOT,
+				5_000 * DOLLARS
+			));
+			assert_eq!(
+				TestLiquidationPools::treasury_withdrawals_storage(),
+				(0, 15_000 * DOLLARS)
+			);
+
+			// The next period starts with the first withdrawal after the end of the previous one.
+			System::set_block_number(100);
+			assert_ok!(TestLiquidationPools::withdraw_to_treasury(
+				admin(),
+				DOT,
+				10_000 * DOLLARS
+			));
+			assert_eq!(liquidation_pool_balance(DOT), 25_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(DOT, &TREASURY), 25_000 * DOLLARS);
+			assert_eq!(
+				TestLiquidationPools::treasury_withdrawals_storage(),
+				(100, 10_000 * DOLLARS)
+			);
+		});
+}
@@ -49,7 +49,18 @@ pub trait WeightInfo {
 	fn set_balance_ratio() -> Weight;
 	fn set_max_ideal_balance() -> Weight;
 	fn transfer_to_liquidation_pool() -> Weight;
-	fn balance_liquidation_pools() -> Weight;
+	fn balance_liquidation_pools(p: u32, t: u32, ) -> Weight;
+	fn set_protocol_owned_liquidity_factor() -> Weight;
+	fn deposit_protocol_owned_liquidity() -> Weight;
+	fn set_max_slippage() -> Weight;
+	fn set_balancing_mode() -> Weight;
+	fn bid() -> Weight;
+	fn settle_auction() -> Weight;
+	fn set_ideal_balance_mode() -> Weight;
+	fn set_at_risk_health_threshold() -> Weight;
+	fn withdraw_to_treasury() -> Weight;
+	fn set_treasury_account() -> Weight;
+	fn update_pool_at_risk_debt(n: u32, ) -> Weight;
 }
 
 /// Weights for liquidation_pools using the Minterest node and recommended hardware.
@@ -75,10 +86,74 @@ impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
-	fn balance_liquidation_pools() -> Weight {
-		(149_441_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	// Estimated as the sales list collected over `p` pools and the swap along the trading path of
+	// `t` currencies. Regenerate with the `balance_liquidation_pools` benchmark.
+	fn balance_liquidation_pools(p: u32, t: u32, ) -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add((22_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add((15_000_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(t as Weight)))
+	}
+	fn set_protocol_owned_liquidity_factor() -> Weight {
+		(32_118_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn deposit_protocol_owned_liquidity() -> Weight {
+		(254_730_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	fn set_max_slippage() -> Weight {
+		(33_012_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_balancing_mode() -> Weight {
+		(31_873_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn bid() -> Weight {
+		(118_305_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn settle_auction() -> Weight {
+		(112_640_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn set_ideal_balance_mode() -> Weight {
+		(31_964_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_at_risk_health_threshold() -> Weight {
+		(22_517_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn withdraw_to_treasury() -> Weight {
+		(79_316_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_treasury_account() -> Weight {
+		(21_843_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn update_pool_at_risk_debt(n: u32, ) -> Weight {
+		(9_412_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((61_307_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((20 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 }
 
@@ -104,9 +179,73 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
-	fn balance_liquidation_pools() -> Weight {
-		(149_441_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	// Estimated as the sales list collected over `p` pools and the swap along the trading path of
+	// `t` currencies. Regenerate with the `balance_liquidation_pools` benchmark.
+	fn balance_liquidation_pools(p: u32, t: u32, ) -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add((22_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add((15_000_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(t as Weight)))
+	}
+	fn set_protocol_owned_liquidity_factor() -> Weight {
+		(32_118_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn deposit_protocol_owned_liquidity() -> Weight {
+		(254_730_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn set_max_slippage() -> Weight {
+		(33_012_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_balancing_mode() -> Weight {
+		(31_873_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn bid() -> Weight {
+		(118_305_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn settle_auction() -> Weight {
+		(112_640_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn set_ideal_balance_mode() -> Weight {
+		(31_964_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_at_risk_health_threshold() -> Weight {
+		(22_517_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn withdraw_to_treasury() -> Weight {
+		(79_316_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_treasury_account() -> Weight {
+		(21_843_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn update_pool_at_risk_debt(n: u32, ) -> Weight {
+		(9_412_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((61_307_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((20 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 }
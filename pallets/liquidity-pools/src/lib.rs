@@ -17,20 +17,31 @@
 //! -`CurrencyConverter`: used to get the exchange rate between underlying assets and wrapped
 //! tokens. This trait also provides functionality for converting between mTokens, underlying
 //! assets and USD.
+//!
+//! Also provides `RebasingBalanceView`: a read-only `MultiCurrency` adapter that reports the
+//! balances of wrapped tokens in underlying assets terms using the current exchange rate.
+//!
+//! ### Bad debt
+//!
+//! If a part of an insolvent loan cannot be covered by the liquidation pools, it is written off
+//! as bad debt: the pool `borrowed` value is reduced, the loss is absorbed by the pool
+//! `protocol_interest` first and any remainder is socialised among suppliers through a lower
+//! exchange rate.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
 use codec::{Decode, Encode};
-use frame_support::{pallet_prelude::*, traits::Get, PalletId};
+use frame_support::{pallet_prelude::*, storage::StoragePrefixedMap, traits::Get, PalletId, StorageHasher};
 use minterest_primitives::{currency::CurrencyType::UnderlyingAsset, Balance, CurrencyId, Price, Rate};
 pub use module::*;
 use orml_traits::MultiCurrency;
 use pallet_traits::{
-	Borrowing, CurrencyConverter, LiquidityPoolStorageProvider, PoolsManager, PricesManager, UserCollateral,
+	Borrowing, CurrencyConverter, LiquidityPoolStorageProvider, PoolsManager, PriceSource, UserCollateral,
 	UserStorageProvider,
 };
+pub use rebasing::*;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
@@ -71,6 +82,8 @@ pub struct PoolUserData {
 type RateResult = result::Result<Rate, DispatchError>;
 type BalanceResult = result::Result<Balance, DispatchError>;
 
+mod rebasing;
+
 #[cfg(test)]
 mod tests;
 
@@ -83,6 +96,9 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+
 		/// The `MultiCurrency` implementation.
 		type MultiCurrency: MultiCurrency<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
 
@@ -90,7 +106,7 @@ pub mod module {
 		type InitialExchangeRate: Get<Rate>;
 
 		/// The price source of currencies
-		type PriceSource: PricesManager<CurrencyId>;
+		type PriceSource: PriceSource<CurrencyId>;
 
 		#[pallet::constant]
 		/// The Liquidity Pool's module id, keep all assets in Pools.
@@ -127,6 +143,18 @@ pub mod module {
 		PoolNotFound,
 		/// Pool is already created
 		PoolAlreadyCreated,
+		/// Bad debt amount exceeds the user borrow balance.
+		BadDebtAmountTooBig,
+		/// Balances can't be changed through the read-only balance view.
+		BalanceViewIsReadOnly,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event {
+		/// Bad debt has been written off in the pool:
+		/// \[pool_id, bad_debt_amount, covered_by_protocol_interest, socialised_amount\]
+		BadDebtWrittenOff(CurrencyId, Balance, Balance, Balance),
 	}
 
 	/// Return liquidity pools information: (borrowed, borrow_index, protocol_interest)
@@ -147,6 +175,20 @@ pub mod module {
 	#[pallet::getter(fn pool_data_storage)]
 	pub(crate) type PoolDataStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, PoolData, ValueQuery>;
 
+	/// Total amount of bad debt written off in the pool since its creation.
+	///
+	/// Bad debt is the part of an insolvent loan that could not be covered during liquidation.
+	/// It is written off against `protocol_interest` first, the remainder is socialised among
+	/// suppliers through the exchange rate.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=liquidity_pools::module::Pallet::pool_bad_debt_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT liquidity_pools")]
+	#[pallet::storage]
+	#[pallet::getter(fn pool_bad_debt_storage)]
+	pub(crate) type PoolBadDebtStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	/// Return information about the user of the liquidity pool: (borrowed, interest_index,
 	/// is_collateral,)
 	///
@@ -339,6 +381,66 @@ impl<T: Config> Borrowing<T::AccountId> for Pallet<T> {
 
 		Ok(())
 	}
+
+	/// Writes off the part of the user loan which cannot be covered during liquidation.
+	/// The loss is covered by the pool protocol interest first, the remainder is socialised
+	/// among suppliers through the exchange rate.
+	/// - `who`: The AccountId whose loan should be written off.
+	/// - `pool_id`: PoolID in which the bad debt occurred.
+	/// - `bad_debt_amount`: The amount of the underlying asset to write off.
+	/// - `account_borrows`: The borrow balance of account.
+	///
+	/// calculates: `account_borrows_new = account_borrows - bad_debt_amount`,
+	///             `total_borrows_new = total_borrows - bad_debt_amount`,
+	///             `protocol_interest_new = protocol_interest - min(protocol_interest,
+	/// bad_debt_amount)`.
+	fn update_state_on_bad_debt(
+		who: &T::AccountId,
+		pool_id: CurrencyId,
+		bad_debt_amount: Balance,
+		account_borrows: Balance,
+	) -> DispatchResult {
+		if bad_debt_amount.is_zero() {
+			return Ok(());
+		}
+		let pool_data = Self::get_pool_data(pool_id);
+
+		let account_borrow_new = account_borrows
+			.checked_sub(bad_debt_amount)
+			.ok_or(Error::<T>::BadDebtAmountTooBig)?;
+		let total_borrows_new = pool_data
+			.borrowed
+			.checked_sub(bad_debt_amount)
+			.ok_or(Error::<T>::BadDebtAmountTooBig)?;
+		let total_bad_debt_new = Self::pool_bad_debt_storage(pool_id)
+			.checked_add(bad_debt_amount)
+			.ok_or(Error::<T>::BorrowBalanceOverflow)?;
+
+		// Protocol interest is used to cover the loss first. Since the exchange rate is
+		// calculated as `(supply + borrowed - protocol_interest) / supply_wrap`, only the
+		// remaining part of the bad debt decreases the exchange rate.
+		let covered_by_protocol_interest = bad_debt_amount.min(pool_data.protocol_interest);
+		let socialised_amount = bad_debt_amount - covered_by_protocol_interest;
+
+		PoolDataStorage::<T>::insert(
+			pool_id,
+			PoolData {
+				borrowed: total_borrows_new,
+				protocol_interest: pool_data.protocol_interest - covered_by_protocol_interest,
+				..pool_data
+			},
+		);
+		PoolBadDebtStorage::<T>::insert(pool_id, total_bad_debt_new);
+		Self::set_user_borrow_and_interest_index(&who, pool_id, account_borrow_new, pool_data.borrow_index);
+
+		Self::deposit_event(Event::BadDebtWrittenOff(
+			pool_id,
+			bad_debt_amount,
+			covered_by_protocol_interest,
+			socialised_amount,
+		));
+		Ok(())
+	}
 }
 
 impl<T: Config> PoolsManager<T::AccountId> for Pallet<T> {
@@ -378,6 +480,43 @@ impl<T: Config> LiquidityPoolStorageProvider<T::AccountId, PoolData> for Pallet<
 			.collect()
 	}
 
+	fn get_pool_members_with_loan_page(
+		underlying_asset: CurrencyId,
+		start_after: Option<T::AccountId>,
+		limit: u32,
+	) -> (Vec<T::AccountId>, Option<T::AccountId>) {
+		let prefix = [
+			&PoolUserDataStorage::<T>::final_prefix()[..],
+			&Blake2_128Concat::hash(&underlying_asset.encode())[..],
+		]
+		.concat();
+		let mut previous_key = match start_after {
+			Some(ref who) => PoolUserDataStorage::<T>::hashed_key_for(underlying_asset, who),
+			None => prefix.clone(),
+		};
+		let mut members = Vec::new();
+		let mut last_visited = None;
+		for _ in 0..limit {
+			// The second key is hashed with `Twox64Concat`: 8 bytes of the hash are followed by the
+			// encoded account.
+			let who = match sp_io::storage::next_key(&previous_key)
+				.filter(|key| key.starts_with(&prefix))
+				.and_then(|key| {
+					let who = T::AccountId::decode(&mut key.get(prefix.len().saturating_add(8)..)?).ok()?;
+					previous_key = key;
+					Some(who)
+				}) {
+				Some(who) => who,
+				None => return (members, None),
+			};
+			if !Self::pool_user_data_storage(underlying_asset, &who).borrowed.is_zero() {
+				members.push(who.clone());
+			}
+			last_visited = Some(who);
+		}
+		(members, last_visited)
+	}
+
 	fn get_pool_borrow_underlying(pool_id: CurrencyId) -> Balance {
 		Self::pool_data_storage(pool_id).borrowed
 	}
@@ -390,6 +529,10 @@ impl<T: Config> LiquidityPoolStorageProvider<T::AccountId, PoolData> for Pallet<
 		Self::pool_data_storage(pool_id).protocol_interest
 	}
 
+	fn get_pool_bad_debt(pool_id: CurrencyId) -> Balance {
+		Self::pool_bad_debt_storage(pool_id)
+	}
+
 	fn pool_exists(underlying_asset: &CurrencyId) -> bool {
 		PoolDataStorage::<T>::contains_key(underlying_asset)
 	}
@@ -409,7 +552,8 @@ impl<T: Config> LiquidityPoolStorageProvider<T::AccountId, PoolData> for Pallet<
 	}
 
 	fn remove_pool_data(pool_id: CurrencyId) {
-		PoolDataStorage::<T>::remove(pool_id)
+		PoolDataStorage::<T>::remove(pool_id);
+		PoolBadDebtStorage::<T>::remove(pool_id)
 	}
 }
 
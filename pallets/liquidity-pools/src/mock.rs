@@ -10,7 +10,7 @@ pub use minterest_primitives::currency::CurrencyType::WrappedToken;
 use minterest_primitives::Price;
 pub use minterest_primitives::{Balance, CurrencyId};
 use orml_traits::parameter_type_with_key;
-use pallet_traits::PricesManager;
+use pallet_traits::PriceSource;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -37,7 +37,7 @@ construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Call, Event<T>, Config<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
-		TestPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+		TestPools: liquidity_pools::{Pallet, Storage, Call, Event, Config<T>},
 	}
 );
 
@@ -69,12 +69,10 @@ impl MockPriceSource {
 	}
 }
 
-impl PricesManager<CurrencyId> for MockPriceSource {
+impl PriceSource<CurrencyId> for MockPriceSource {
 	fn get_underlying_price(_currency_id: CurrencyId) -> Option<Price> {
 		UNDERLYING_PRICE.with(|v| *v.borrow_mut())
 	}
-	fn lock_price(_currency_id: CurrencyId) {}
-	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
 // -----------------------------------------------------------------------------------------
@@ -0,0 +1,102 @@
+use super::*;
+
+/// Read-only `MultiCurrency` view of the balances in which wrapped tokens are reported in
+/// underlying assets terms, using the current exchange rate of the corresponding pool.
+///
+/// Balances of currencies that are not wrapped tokens are reported as is. Any attempt to change
+/// balances through this adapter fails with `BalanceViewIsReadOnly` error.
+pub struct RebasingBalanceView<T>(PhantomData<T>);
+
+impl<T: Config> RebasingBalanceView<T> {
+	/// Converts the amount of `currency_id` into underlying assets terms.
+	/// If `currency_id` is not a wrapped token, `amount` is returned unchanged.
+	///
+	/// - `currency_id`: currency of the `amount`.
+	/// - `amount`: the amount to be converted.
+	pub fn to_underlying(currency_id: CurrencyId, amount: Balance) -> BalanceResult {
+		match currency_id.underlying_asset() {
+			Some(underlying_asset) => {
+				let exchange_rate = Pallet::<T>::get_exchange_rate(underlying_asset)?;
+				Pallet::<T>::wrapped_to_underlying(amount, exchange_rate)
+			}
+			None => Ok(amount),
+		}
+	}
+
+	/// Returns the balances of all wrapped tokens of the account with existing pools:
+	/// (wrapped_id, wrapped_amount, underlying_amount).
+	///
+	/// - `who`: the account whose balances are requested.
+	pub fn get_user_wrapped_balances(
+		who: &T::AccountId,
+	) -> result::Result<Vec<(CurrencyId, Balance, Balance)>, DispatchError> {
+		T::EnabledWrappedTokensId::get()
+			.into_iter()
+			.filter(|wrapped_id| {
+				wrapped_id
+					.underlying_asset()
+					.map_or(false, |pool_id| Pallet::<T>::pool_exists(&pool_id))
+			})
+			.map(|wrapped_id| {
+				let wrapped_amount = T::MultiCurrency::free_balance(wrapped_id, who);
+				let underlying_amount = Self::to_underlying(wrapped_id, wrapped_amount)?;
+				Ok((wrapped_id, wrapped_amount, underlying_amount))
+			})
+			.collect()
+	}
+}
+
+impl<T: Config> MultiCurrency<T::AccountId> for RebasingBalanceView<T> {
+	type CurrencyId = CurrencyId;
+	type Balance = Balance;
+
+	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
+		Self::to_underlying(currency_id, T::MultiCurrency::minimum_balance(currency_id)).unwrap_or_default()
+	}
+
+	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
+		Self::to_underlying(currency_id, T::MultiCurrency::total_issuance(currency_id)).unwrap_or_default()
+	}
+
+	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		Self::to_underlying(currency_id, T::MultiCurrency::total_balance(currency_id, who)).unwrap_or_default()
+	}
+
+	fn free_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		Self::to_underlying(currency_id, T::MultiCurrency::free_balance(currency_id, who)).unwrap_or_default()
+	}
+
+	fn ensure_can_withdraw(
+		_currency_id: Self::CurrencyId,
+		_who: &T::AccountId,
+		_amount: Self::Balance,
+	) -> DispatchResult {
+		Err(Error::<T>::BalanceViewIsReadOnly.into())
+	}
+
+	fn transfer(
+		_currency_id: Self::CurrencyId,
+		_from: &T::AccountId,
+		_to: &T::AccountId,
+		_amount: Self::Balance,
+	) -> DispatchResult {
+		Err(Error::<T>::BalanceViewIsReadOnly.into())
+	}
+
+	fn deposit(_currency_id: Self::CurrencyId, _who: &T::AccountId, _amount: Self::Balance) -> DispatchResult {
+		Err(Error::<T>::BalanceViewIsReadOnly.into())
+	}
+
+	fn withdraw(_currency_id: Self::CurrencyId, _who: &T::AccountId, _amount: Self::Balance) -> DispatchResult {
+		Err(Error::<T>::BalanceViewIsReadOnly.into())
+	}
+
+	fn can_slash(_currency_id: Self::CurrencyId, _who: &T::AccountId, _value: Self::Balance) -> bool {
+		false
+	}
+
+	/// Nothing can be slashed through the read-only view, so the whole `amount` is returned.
+	fn slash(_currency_id: Self::CurrencyId, _who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		amount
+	}
+}
@@ -3,7 +3,7 @@ use super::*;
 use crate::mock::*;
 use frame_support::{assert_err, assert_noop, assert_ok};
 use pallet_traits::{
-	Borrowing, CurrencyConverter, LiquidityPoolStorageProvider, PoolsManager, PricesManager, UserCollateral,
+	Borrowing, CurrencyConverter, LiquidityPoolStorageProvider, PoolsManager, PriceSource, UserCollateral,
 	UserStorageProvider,
 };
 use sp_arithmetic::FixedPointNumber;
@@ -265,6 +265,85 @@ fn update_state_on_repay_should_work() {
 		});
 }
 
+#[test]
+fn update_state_on_bad_debt_should_work() {
+	ExtBuilder::default()
+		.init_pool(DOT, dollars(100), Rate::one(), dollars(30))
+		.set_pool_user_data(DOT, ALICE, dollars(100), Rate::one(), true)
+		.build()
+		.execute_with(|| {
+			// Bad debt is covered by protocol interest.
+			assert_ok!(TestPools::update_state_on_bad_debt(
+				&ALICE,
+				DOT,
+				dollars(20),
+				dollars(100)
+			));
+			assert_eq!(TestPools::get_pool_borrow_underlying(DOT), dollars(80));
+			assert_eq!(TestPools::get_pool_protocol_interest(DOT), dollars(10));
+			assert_eq!(TestPools::get_pool_bad_debt(DOT), dollars(20));
+			assert_eq!(TestPools::get_user_borrow_balance(&ALICE, DOT), dollars(80));
+			let expected_event = crate::mock::Event::TestPools(crate::Event::BadDebtWrittenOff(
+				DOT,
+				dollars(20),
+				dollars(20),
+				Balance::zero(),
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// Remaining protocol interest is not enough, the rest of the bad debt is socialised.
+			assert_ok!(TestPools::update_state_on_bad_debt(
+				&ALICE,
+				DOT,
+				dollars(50),
+				dollars(80)
+			));
+			assert_eq!(TestPools::get_pool_borrow_underlying(DOT), dollars(30));
+			assert_eq!(TestPools::get_pool_protocol_interest(DOT), Balance::zero());
+			assert_eq!(TestPools::get_pool_bad_debt(DOT), dollars(70));
+			assert_eq!(TestPools::get_user_borrow_balance(&ALICE, DOT), dollars(30));
+			let expected_event = crate::mock::Event::TestPools(crate::Event::BadDebtWrittenOff(
+				DOT,
+				dollars(50),
+				dollars(10),
+				dollars(40),
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			assert_noop!(
+				TestPools::update_state_on_bad_debt(&ALICE, DOT, dollars(31), dollars(30)),
+				Error::<TestRuntime>::BadDebtAmountTooBig
+			);
+		});
+}
+
+#[test]
+fn bad_debt_should_decrease_exchange_rate_only_by_socialised_amount() {
+	ExtBuilder::default()
+		.init_pool(DOT, dollars(100), Rate::one(), dollars(20))
+		.set_pool_user_data(DOT, ALICE, dollars(100), Rate::one(), true)
+		.set_pool_balance(TestPools::pools_account_id(), DOT, dollars(20))
+		.set_user_balance(BOB, MDOT, dollars(100))
+		.build()
+		.execute_with(|| {
+			// exchange_rate = (20 + 100 - 20) / 100 = 1
+			assert_eq!(TestPools::get_exchange_rate(DOT), Ok(Rate::one()));
+
+			assert_ok!(TestPools::update_state_on_bad_debt(
+				&ALICE,
+				DOT,
+				dollars(60),
+				dollars(100)
+			));
+
+			// exchange_rate = (20 + 40 - 0) / 100 = 0.6
+			assert_eq!(
+				TestPools::get_exchange_rate(DOT),
+				Ok(Rate::saturating_from_rational(6, 10))
+			);
+		});
+}
+
 #[test]
 fn get_pool_members_with_loan_should_work() {
 	ExtBuilder::default()
@@ -281,6 +360,41 @@ fn get_pool_members_with_loan_should_work() {
 		});
 }
 
+#[test]
+fn get_pool_members_with_loan_page_should_work() {
+	ExtBuilder::default()
+		.set_pool_user_data(DOT, ALICE, ONE_HUNDRED, Rate::default(), true)
+		.set_pool_user_data(DOT, BOB, 0, Rate::default(), true)
+		.set_pool_user_data(DOT, CHARLIE, 100, Rate::default(), true)
+		.set_pool_user_data(BTC, CHARLIE, ONE_HUNDRED, Rate::default(), true)
+		.build()
+		.execute_with(|| {
+			// All pool members are visited by a single page.
+			assert_eq!(
+				TestPools::get_pool_members_with_loan_page(DOT, None, 10),
+				(vec![CHARLIE, ALICE], None)
+			);
+
+			// Pages of a single member visit the members of DOT pool only.
+			let mut members = vec![];
+			let mut visited = 0;
+			let mut start_after = None;
+			loop {
+				let (page, last_visited) = TestPools::get_pool_members_with_loan_page(DOT, start_after, 1);
+				members.extend(page);
+				match last_visited {
+					Some(who) => {
+						visited += 1;
+						start_after = Some(who);
+					}
+					None => break,
+				}
+			}
+			assert_eq!(visited, 3);
+			assert_eq!(members, vec![CHARLIE, ALICE]);
+		});
+}
+
 #[test]
 fn check_user_has_collateral_should_work() {
 	ExtBuilder::default()
@@ -482,3 +596,64 @@ fn wrapped_to_underlying_and_usd_should_work() {
 			assert_eq!(TestPools::usd_to_wrapped(20, exchange_rate_dot, oracle_price), Ok(25));
 		});
 }
+
+#[test]
+fn rebasing_balance_view_should_work() {
+	ExtBuilder::default()
+		.init_pool(
+			DOT,               // pool_id
+			dollars(300_u128), // borrowed
+			Rate::one(),       // borrow_index
+			Balance::zero(),   // protocol_interest
+		)
+		.set_pool_balance(TestPools::pools_account_id(), DOT, dollars(100_u128))
+		.set_user_balance(ALICE, MDOT, dollars(125_u128))
+		.set_user_balance(ALICE, DOT, dollars(50_u128))
+		.build()
+		.execute_with(|| {
+			// exchange_rate = (100 - 0 + 300) / 125 = 3.2
+			// Wrapped tokens are reported in underlying assets terms: 125 * 3.2 = 400
+			assert_eq!(
+				RebasingBalanceView::<TestRuntime>::free_balance(MDOT, &ALICE),
+				dollars(400_u128)
+			);
+			assert_eq!(
+				RebasingBalanceView::<TestRuntime>::total_balance(MDOT, &ALICE),
+				dollars(400_u128)
+			);
+			assert_eq!(
+				RebasingBalanceView::<TestRuntime>::total_issuance(MDOT),
+				dollars(400_u128)
+			);
+			// Other currencies are reported as is.
+			assert_eq!(
+				RebasingBalanceView::<TestRuntime>::free_balance(DOT, &ALICE),
+				dollars(50_u128)
+			);
+
+			// Only wrapped tokens with existing pools are returned.
+			assert_eq!(
+				RebasingBalanceView::<TestRuntime>::get_user_wrapped_balances(&ALICE),
+				Ok(vec![(MDOT, dollars(125_u128), dollars(400_u128))])
+			);
+
+			// The view is read-only.
+			assert_noop!(
+				RebasingBalanceView::<TestRuntime>::transfer(MDOT, &ALICE, &BOB, ONE_HUNDRED),
+				Error::<TestRuntime>::BalanceViewIsReadOnly
+			);
+			assert_noop!(
+				RebasingBalanceView::<TestRuntime>::deposit(MDOT, &ALICE, ONE_HUNDRED),
+				Error::<TestRuntime>::BalanceViewIsReadOnly
+			);
+			assert_noop!(
+				RebasingBalanceView::<TestRuntime>::withdraw(MDOT, &ALICE, ONE_HUNDRED),
+				Error::<TestRuntime>::BalanceViewIsReadOnly
+			);
+			assert_eq!(
+				RebasingBalanceView::<TestRuntime>::slash(MDOT, &ALICE, ONE_HUNDRED),
+				ONE_HUNDRED
+			);
+			assert_eq!(Currencies::free_balance(MDOT, &ALICE), dollars(125_u128));
+		});
+}
@@ -6,7 +6,7 @@ use frame_system::EnsureSignedBy;
 use minterest_primitives::currency::CurrencyType::{UnderlyingAsset, WrappedToken};
 pub use minterest_primitives::{Balance, CurrencyId, Price, Rate};
 use orml_traits::parameter_type_with_key;
-use pallet_traits::PricesManager;
+use pallet_traits::PriceSource;
 use sp_core::H256;
 use sp_runtime::traits::AccountIdConversion;
 use sp_runtime::{
@@ -57,12 +57,10 @@ parameter_types! {
 // -----------------------------------------------------------------------------------------
 pub struct MockPriceSource;
 
-impl PricesManager<CurrencyId> for MockPriceSource {
+impl PriceSource<CurrencyId> for MockPriceSource {
 	fn get_underlying_price(_currency_id: CurrencyId) -> Option<Price> {
 		Some(Price::one())
 	}
-	fn lock_price(_currency_id: CurrencyId) {}
-	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
 // -----------------------------------------------------------------------------------------
@@ -10,7 +10,7 @@ use minterest_model::MinterestModelData;
 pub use minterest_primitives::currency::CurrencyType::{UnderlyingAsset, WrappedToken};
 use minterest_primitives::{Balance, CurrencyId, Price, Rate};
 use orml_traits::parameter_type_with_key;
-use pallet_traits::PricesManager;
+use pallet_traits::PriceSource;
 use sp_core::H256;
 use sp_runtime::{
 	testing::{Header, TestXt},
@@ -36,7 +36,7 @@ frame_support::construct_runtime!(
 		Controller: controller::{Pallet, Storage, Call, Event, Config<T>},
 		TestMinterestModel: minterest_model::{Pallet, Storage, Call, Event, Config<T>},
 		TestMinterestProtocol: minterest_protocol::{Pallet, Storage, Call, Event<T>},
-		TestPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+		TestPools: liquidity_pools::{Pallet, Storage, Call, Event, Config<T>},
 		TestLiquidationPools: liquidation_pools::{Pallet, Storage, Call, Event<T>, Config<T>},
 		TestDex: dex::{Pallet, Storage, Call, Event<T>},
 		TestMntToken: mnt_token::{Pallet, Storage, Call, Event<T>, Config<T>},
@@ -77,14 +77,10 @@ mock_impl_whitelist_module_config!(Test, OneAlice);
 mock_impl_risk_manager_config!(Test, OneAlice);
 
 pub struct MockPriceSource;
-impl PricesManager<CurrencyId> for MockPriceSource {
+impl PriceSource<CurrencyId> for MockPriceSource {
 	fn get_underlying_price(_currency_id: CurrencyId) -> Option<Price> {
 		Some(Price::one())
 	}
-
-	fn lock_price(_currency_id: CurrencyId) {}
-
-	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
 pub struct ExtBuilder {
@@ -1,6 +1,13 @@
 //! # MNT token Module
 //!
 //! Provides functionality for minting MNT tokens.
+//!
+//! MNT is distributed to reward targets, each with its own speed, distribution index state and
+//! claim path:
+//! - lending pools: MNT is split between the suppliers and the borrowers of the pool and is
+//!   claimed through the minterest-protocol pallet;
+//! - DEX trading pairs: MNT goes to the liquidity providers of the pair proportionally to their
+//!   liquidity shares and is claimed through the dex pallet.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -8,17 +15,19 @@
 use frame_support::{pallet_prelude::*, sp_std::cmp::Ordering, transactional};
 use frame_system::pallet_prelude::*;
 use liquidity_pools::PoolData;
-use minterest_primitives::{currency::MNT, Balance, CurrencyId, Price, Rate};
+use minterest_primitives::{currency::MNT, Balance, CurrencyId, Price, Rate, TradingPair};
 pub use module::*;
 use orml_traits::MultiCurrency;
 use pallet_traits::{
-	ControllerManager, CurrencyConverter, LiquidityPoolStorageProvider, MntManager, PoolsManager, PricesManager,
+	ControllerManager, CurrencyConverter, DexSharesProvider, LiquidityPoolStorageProvider, MntManager, PoolsManager,
+	PriceSource,
 };
 use sp_runtime::{
 	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero},
 	DispatchResult, FixedPointNumber, FixedU128,
 };
 use sp_std::{convert::TryInto, result};
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -73,6 +82,31 @@ impl<T: Config> Default for MntPoolState<T> {
 	}
 }
 
+/// A target of MNT distribution.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum RewardTarget {
+	/// Suppliers and borrowers of the lending pool.
+	Pool(CurrencyId),
+	/// Liquidity providers of the DEX trading pair.
+	DexPair(TradingPair),
+}
+
+/// Storage version of the pallet.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum Releases {
+	/// MNT speeds are keyed by the lending pools.
+	V0,
+	/// MNT speeds are keyed by the reward targets.
+	V1,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0
+	}
+}
+
 type BalanceResult = result::Result<Balance, DispatchError>;
 
 #[frame_support::pallet]
@@ -93,7 +127,7 @@ pub mod module {
 		type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
 		/// The price source of currencies
-		type PriceSource: PricesManager<CurrencyId>;
+		type PriceSource: PriceSource<CurrencyId>;
 
 		/// The `MultiCurrency` implementation for wrapped.
 		type MultiCurrency: MultiCurrency<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
@@ -101,6 +135,9 @@ pub mod module {
 		/// Public API of controller pallet
 		type ControllerManager: ControllerManager<Self::AccountId>;
 
+		/// Provides the liquidity shares of the DEX trading pairs
+		type DexSharesProvider: DexSharesProvider<Self::AccountId>;
+
 		#[pallet::constant]
 		/// The Mnt-token's account id, keep assets that should be distributed to users
 		type MntTokenAccountId: Get<Self::AccountId>;
@@ -123,14 +160,16 @@ pub mod module {
 		InternalError,
 		/// Pool not forund in liquidity-pools storage
 		PoolNotFound,
+		/// The currencies don't form a trading pair, that can be traded on the DEX
+		NotValidTradingPair,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// MNT speed had been changed for a pool
-		/// (pool_id, new_speed)
-		MntSpeedChanged(CurrencyId, Balance),
+		/// MNT speed had been changed for a reward target
+		/// (reward_target, new_speed)
+		MntSpeedChanged(RewardTarget, Balance),
 
 		/// Emitted when MNT is distributed to a supplier
 		/// (pool id, receiver, amount of distributed tokens, supply index)
@@ -139,8 +178,16 @@ pub mod module {
 		/// Emitted when MNT is distributed to a borrower
 		/// (pool id, receiver, amount of distributed tokens, index)
 		MntDistributedToBorrower(CurrencyId, T::AccountId, Balance, Rate),
+
+		/// Emitted when MNT is distributed to a liquidity provider of a DEX trading pair
+		/// (trading pair, receiver, amount of distributed tokens, index)
+		MntDistributedToLiquidityProvider(TradingPair, T::AccountId, Balance, Rate),
 	}
 
+	/// Storage version of the pallet.
+	#[pallet::storage]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
 	/// The threshold above which the flywheel transfers MNT
 	///
 	/// Storage location:
@@ -151,9 +198,10 @@ pub mod module {
 	#[pallet::getter(fn mnt_claim_threshold_storage)]
 	pub(crate) type MntClaimThresholdStorage<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
-	/// MNT minting speed for each pool
-	/// Doubling this number shows how much MNT goes to all suppliers and borrowers of a particular
-	/// pool.
+	/// MNT minting speed for each reward target
+	/// For a pool, doubling this number shows how much MNT goes to all suppliers and borrowers of
+	/// a particular pool. For a DEX trading pair, it shows how much MNT goes to all liquidity
+	/// providers of the pair.
 	///
 	/// Storage location:
 	/// [`MNT Storage`](?search=mnt_token::module::Pallet::mnt_speed_storage)
@@ -161,7 +209,7 @@ pub mod module {
 	#[doc(alias = "MNT mnt_token")]
 	#[pallet::storage]
 	#[pallet::getter(fn mnt_speed_storage)]
-	pub type MntSpeedStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+	pub type MntSpeedStorage<T: Config> = StorageMap<_, Twox64Concat, RewardTarget, Balance, ValueQuery>;
 
 	/// Index + block_number need for generating and distributing new MNT tokens for pool
 	///
@@ -196,6 +244,29 @@ pub mod module {
 	pub(crate) type MntBorrowerIndexStorage<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, T::AccountId, Rate, ValueQuery>;
 
+	/// Index + block_number need for generating and distributing new MNT tokens for the
+	/// liquidity providers of a DEX trading pair
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=mnt_token::module::Pallet::mnt_dex_pair_state_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT mnt_token")]
+	#[pallet::storage]
+	#[pallet::getter(fn mnt_dex_pair_state_storage)]
+	pub(crate) type MntDexPairStateStorage<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, MntState<T>, OptionQuery>;
+
+	/// Use for accruing MNT tokens for liquidity provider
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=mnt_token::module::Pallet::mnt_liquidity_provider_index_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT mnt_token")]
+	#[pallet::storage]
+	#[pallet::getter(fn mnt_liquidity_provider_index_storage)]
+	pub(crate) type MntLiquidityProviderIndexStorage<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, T::AccountId, Rate, OptionQuery>;
+
 	/// Place where accrued MNT tokens are kept for each user
 	///
 	/// Storage location:
@@ -229,9 +300,10 @@ pub mod module {
 		fn build(&self) {
 			MntClaimThresholdStorage::<T>::put(&self.mnt_claim_threshold);
 			for (currency_id, speed) in &self.minted_pools {
-				MntSpeedStorage::<T>::insert(currency_id, speed);
+				MntSpeedStorage::<T>::insert(RewardTarget::Pool(*currency_id), speed);
 				MntPoolStateStorage::<T>::insert(currency_id, MntPoolState::new());
 			}
+			StorageVersion::<T>::put(Releases::V1);
 		}
 	}
 
@@ -239,7 +311,11 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate_to_v1::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -264,29 +340,36 @@ pub mod module {
 				T::LiquidityPoolsManager::pool_exists(&currency_id),
 				Error::<T>::PoolNotFound
 			);
-			Self::update_pool_mnt_supply_index(currency_id)?;
-			Self::update_pool_mnt_borrow_index(currency_id)?;
-
-			// New speed is zero. Disable distribution.
-			if speed.is_zero() {
-				ensure!(
-					MntSpeedStorage::<T>::contains_key(currency_id),
-					Error::<T>::MntMintingNotEnabled
-				);
-				MntSpeedStorage::<T>::remove(currency_id);
-			} else {
-				// Distribution is currently off.
-				// Update 'index_updated_at_block' and leave indices unchanged.
-				if !MntSpeedStorage::<T>::contains_key(currency_id) {
-					let current_block = frame_system::Pallet::<T>::block_number();
-					MntPoolStateStorage::<T>::mutate(currency_id, |pool_state| {
-						pool_state.borrow_state.index_updated_at_block = current_block;
-						pool_state.supply_state.index_updated_at_block = current_block;
-					});
-				}
-				MntSpeedStorage::<T>::insert(currency_id, speed);
-			}
-			Self::deposit_event(Event::MntSpeedChanged(currency_id, speed));
+			Self::do_set_speed(RewardTarget::Pool(currency_id), speed)?;
+			Ok(().into())
+		}
+
+		/// Set MNT minting speed for the liquidity providers of the DEX trading pair.
+		///
+		/// Parameters:
+		/// - `currency_id_a`: the first currency of the trading pair.
+		/// - `currency_id_b`: the second currency of the trading pair.
+		/// - `speed`: new minting speed. Zero speed means minting will be disabled.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT mnt_token")]
+		#[pallet::weight(T::MntTokenWeightInfo::set_dex_pair_speed())]
+		#[transactional]
+		pub fn set_dex_pair_speed(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			speed: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				!currency_id_a.is_supported_wrapped_asset() && !currency_id_b.is_supported_wrapped_asset(),
+				Error::<T>::NotValidTradingPair
+			);
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::NotValidTradingPair)?;
+			Self::do_set_speed(RewardTarget::DexPair(trading_pair), speed)?;
 			Ok(().into())
 		}
 	}
@@ -298,6 +381,56 @@ impl<T: Config> Pallet<T> {
 		T::MntTokenAccountId::get()
 	}
 
+	/// Sets MNT minting speed for the reward target. Accrues MNT with the old speed first.
+	///
+	/// - `target`: the reward target.
+	/// - `speed`: new minting speed. Zero speed means minting will be disabled.
+	fn do_set_speed(target: RewardTarget, speed: Balance) -> DispatchResult {
+		Self::update_target_mnt_indexes(target)?;
+
+		// New speed is zero. Disable distribution.
+		if speed.is_zero() {
+			ensure!(
+				MntSpeedStorage::<T>::contains_key(target),
+				Error::<T>::MntMintingNotEnabled
+			);
+			MntSpeedStorage::<T>::remove(target);
+		} else {
+			// Distribution is currently off.
+			// Update 'index_updated_at_block' and leave indices unchanged.
+			if !MntSpeedStorage::<T>::contains_key(target) {
+				let current_block = frame_system::Pallet::<T>::block_number();
+				match target {
+					RewardTarget::Pool(pool_id) => MntPoolStateStorage::<T>::mutate(pool_id, |pool_state| {
+						pool_state.borrow_state.index_updated_at_block = current_block;
+						pool_state.supply_state.index_updated_at_block = current_block;
+					}),
+					RewardTarget::DexPair(trading_pair) => {
+						MntDexPairStateStorage::<T>::mutate(trading_pair, |maybe_state| {
+							maybe_state.get_or_insert_with(MntState::new).index_updated_at_block = current_block;
+						})
+					}
+				}
+			}
+			MntSpeedStorage::<T>::insert(target, speed);
+		}
+		Self::deposit_event(Event::MntSpeedChanged(target, speed));
+		Ok(())
+	}
+
+	/// Updates all the MNT distribution indexes of the reward target.
+	///
+	/// - `target`: the reward target.
+	fn update_target_mnt_indexes(target: RewardTarget) -> DispatchResult {
+		match target {
+			RewardTarget::Pool(pool_id) => {
+				Self::update_pool_mnt_supply_index(pool_id)?;
+				Self::update_pool_mnt_borrow_index(pool_id)
+			}
+			RewardTarget::DexPair(trading_pair) => Self::update_dex_pair_mnt_index(trading_pair),
+		}
+	}
+
 	/// Transfer MNT tokens to user balance if they are above the threshold.
 	/// Otherwise, put them into internal storage.
 	///
@@ -334,12 +467,22 @@ impl<T: Config> Pallet<T> {
 	pub fn get_user_total_unclaimed_mnt_balance(account_id: &T::AccountId) -> BalanceResult {
 		let accrued_mnt = MntSpeedStorage::<T>::iter().try_fold(
 			Balance::zero(),
-			|current_accrued, (pool_id, _)| -> BalanceResult {
-				Self::update_pool_mnt_borrow_index(pool_id)?;
-				let accrued_borrow_mnt = Self::distribute_borrower_mnt(pool_id, account_id, true)?;
-				Self::update_pool_mnt_supply_index(pool_id)?;
-				let accrued_supply_mnt = Self::distribute_supplier_mnt(pool_id, account_id, true)?;
-				Ok(current_accrued + accrued_borrow_mnt + accrued_supply_mnt)
+			|current_accrued, (target, _)| -> BalanceResult {
+				match target {
+					RewardTarget::Pool(pool_id) => {
+						Self::update_pool_mnt_borrow_index(pool_id)?;
+						let accrued_borrow_mnt = Self::distribute_borrower_mnt(pool_id, account_id, true)?;
+						Self::update_pool_mnt_supply_index(pool_id)?;
+						let accrued_supply_mnt = Self::distribute_supplier_mnt(pool_id, account_id, true)?;
+						Ok(current_accrued + accrued_borrow_mnt + accrued_supply_mnt)
+					}
+					RewardTarget::DexPair(trading_pair) => {
+						Self::update_dex_pair_mnt_index(trading_pair)?;
+						let accrued_provider_mnt =
+							Self::distribute_liquidity_provider_mnt(trading_pair, account_id, true)?;
+						Ok(current_accrued + accrued_provider_mnt)
+					}
+				}
 			},
 		)?;
 		Ok(accrued_mnt)
@@ -358,7 +501,7 @@ impl<T: Config> MntManager<T::AccountId> for Pallet<T> {
 		// supply_state.mnt_distribution_index += ratio
 		// supply_state.index_updated_at_block = current_block_number
 
-		let mnt_speed = MntSpeedStorage::<T>::get(pool_id);
+		let mnt_speed = MntSpeedStorage::<T>::get(RewardTarget::Pool(pool_id));
 		if mnt_speed.is_zero() {
 			// Distribution is off
 			return Ok(());
@@ -414,7 +557,7 @@ impl<T: Config> MntManager<T::AccountId> for Pallet<T> {
 		// borrow_state.mnt_distribution_index(for current pool) += ratio
 		// borrow_state.index_updated_at_block = current_block_number
 
-		let mnt_speed = MntSpeedStorage::<T>::get(pool_id);
+		let mnt_speed = MntSpeedStorage::<T>::get(RewardTarget::Pool(pool_id));
 		if mnt_speed.is_zero() {
 			// Distribution is off
 			return Ok(());
@@ -566,6 +709,109 @@ impl<T: Config> MntManager<T::AccountId> for Pallet<T> {
 		Ok(borrower_mnt_accrued)
 	}
 
+	/// Update mnt index for the liquidity providers of the DEX trading pair
+	/// Do nothing in case if distribution is off or index have already been updated in this block.
+	///
+	/// - `trading_pair`: the trading pair to update index
+	fn update_dex_pair_mnt_index(trading_pair: TradingPair) -> DispatchResult {
+		// block_delta = current_block_number - dex_pair_state.index_updated_at_block
+		// mnt_accrued = block_delta * mnt_speed
+		// ratio = mnt_accrued / total_liquidity_shares
+		// dex_pair_state.mnt_distribution_index += ratio
+		// dex_pair_state.index_updated_at_block = current_block_number
+
+		let mnt_speed = MntSpeedStorage::<T>::get(RewardTarget::DexPair(trading_pair));
+		if mnt_speed.is_zero() {
+			// Distribution is off
+			return Ok(());
+		}
+
+		let current_block = frame_system::Pallet::<T>::block_number();
+		let mut pair_state = MntDexPairStateStorage::<T>::get(trading_pair).ok_or(Error::<T>::InternalError)?;
+		let block_delta = current_block
+			.checked_sub(&pair_state.index_updated_at_block)
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		if block_delta.is_zero() {
+			// Index for current block was already calculated
+			return Ok(());
+		}
+
+		let block_delta_as_u128 = TryInto::<u128>::try_into(block_delta).or(Err(Error::<T>::InternalError))?;
+
+		let mnt_accrued = mnt_speed
+			.checked_mul(block_delta_as_u128)
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		let total_shares = T::DexSharesProvider::get_total_shares(trading_pair);
+
+		let ratio = match total_shares.cmp(&Balance::zero()) {
+			Ordering::Greater => {
+				Rate::checked_from_rational(mnt_accrued, total_shares).ok_or(Error::<T>::NumOverflow)?
+			}
+			_ => Rate::zero(),
+		};
+
+		pair_state.mnt_distribution_index = pair_state
+			.mnt_distribution_index
+			.checked_add(&ratio)
+			.ok_or(Error::<T>::NumOverflow)?;
+		pair_state.index_updated_at_block = current_block;
+		MntDexPairStateStorage::<T>::insert(trading_pair, pair_state);
+		Ok(())
+	}
+
+	/// Distribute MNT token to liquidity provider. It should be called after
+	/// `update_dex_pair_mnt_index` and before the liquidity shares of the provider are changed.
+	///
+	/// - `trading_pair`: the trading pair the provider added liquidity to
+	/// - `provider`: The AccountId of the liquidity provider to distribute MNT to.
+	/// - `distribute_all`:  boolean, distribute all or part of accrued MNT tokens.
+	fn distribute_liquidity_provider_mnt(
+		trading_pair: TradingPair,
+		provider: &T::AccountId,
+		distribute_all: bool,
+	) -> BalanceResult {
+		// delta_index = mnt_distribution_index - mnt_liquidity_provider_index_storage
+		// provider_delta = provider_liquidity_shares * delta_index
+		// provider_mnt_balance += provider_delta
+		// mnt_liquidity_provider_index_storage = mnt_distribution_index
+		let pair_index = match MntDexPairStateStorage::<T>::get(trading_pair) {
+			Some(pair_state) => pair_state.mnt_distribution_index,
+			// MNT has never been distributed for the trading pair
+			None => return Ok(Balance::zero()),
+		};
+
+		let provider_index =
+			MntLiquidityProviderIndexStorage::<T>::get(trading_pair, provider).unwrap_or_else(Rate::one);
+
+		let delta_index = pair_index.checked_sub(&provider_index).ok_or(Error::<T>::NumOverflow)?;
+
+		let provider_shares = Rate::from_inner(T::DexSharesProvider::get_shares(trading_pair, provider));
+
+		let provider_delta = delta_index
+			.checked_mul(&provider_shares)
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		let mut provider_mnt_accrued = MntAccruedStorage::<T>::get(provider);
+
+		provider_mnt_accrued = provider_mnt_accrued
+			.checked_add(provider_delta.into_inner())
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		MntLiquidityProviderIndexStorage::<T>::insert(trading_pair, provider, pair_index);
+		Self::transfer_mnt(provider, provider_mnt_accrued, distribute_all)?;
+
+		Self::deposit_event(Event::MntDistributedToLiquidityProvider(
+			trading_pair,
+			provider.clone(),
+			provider_delta.into_inner(),
+			pair_index,
+		));
+
+		Ok(provider_mnt_accrued)
+	}
+
 	/// Return MNT Borrow Rate and MNT Supply Rate values per block for current pool.
 	/// - `pool_id` - the pool to calculate rates
 	///
@@ -594,7 +840,7 @@ impl<T: Config> MntManager<T::AccountId> for Pallet<T> {
 		let pool_tvl_in_usd =
 			T::LiquidityPoolsManager::wrapped_to_usd(pool_supply_wrapped, exchange_rate, oracle_price)?;
 
-		let mnt_speed = MntSpeedStorage::<T>::get(pool_id);
+		let mnt_speed = MntSpeedStorage::<T>::get(RewardTarget::Pool(pool_id));
 		let rate_calculation = |x: Balance| {
 			FixedU128::from_inner(mnt_speed)
 				.checked_mul(&mnt_price)
@@ -0,0 +1,27 @@
+//! Storage migrations for the mnt token pallet.
+
+use super::*;
+use frame_support::{storage::migration::storage_key_iter, traits::PalletInfo};
+use sp_std::vec::Vec;
+
+/// Moves the MNT speeds of the lending pools to the `RewardTarget::Pool` keys.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if StorageVersion::<T>::get() != Releases::V0 {
+		return 0;
+	}
+
+	let pallet_name = <T as frame_system::Config>::PalletInfo::name::<Pallet<T>>().unwrap_or("MntToken");
+	let speeds = storage_key_iter::<CurrencyId, Balance, Twox64Concat>(pallet_name.as_bytes(), b"MntSpeedStorage")
+		.drain()
+		.collect::<Vec<_>>();
+	for (pool_id, speed) in speeds.iter() {
+		MntSpeedStorage::<T>::insert(RewardTarget::Pool(*pool_id), speed);
+	}
+	StorageVersion::<T>::put(Releases::V1);
+
+	let migrated_speeds = speeds.len() as Weight;
+	T::DbWeight::get().reads_writes(
+		migrated_speeds.saturating_add(1),
+		migrated_speeds.saturating_mul(2).saturating_add(1),
+	)
+}
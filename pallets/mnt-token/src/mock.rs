@@ -5,14 +5,15 @@ use frame_support::{construct_runtime, ord_parameter_types, pallet_prelude::*, p
 use frame_system::EnsureSignedBy;
 use liquidity_pools::{PoolData, PoolUserData};
 pub use minterest_primitives::currency::CurrencyType::{UnderlyingAsset, WrappedToken};
-use minterest_primitives::{Balance, CurrencyId, Price, Rate};
+use minterest_primitives::{Balance, CurrencyId, Price, Rate, TradingPair};
 use orml_traits::parameter_type_with_key;
-use pallet_traits::PricesManager;
+use pallet_traits::{DexSharesProvider, PriceSource};
 use sp_runtime::{
 	testing::{Header, H256},
 	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup, One, Zero},
 	FixedPointNumber,
 };
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 pub use test_helper::*;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -29,7 +30,7 @@ construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
 		MntToken: mnt_token::{Pallet, Storage, Call, Event<T>, Config<T>},
-		TestPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+		TestPools: liquidity_pools::{Pallet, Storage, Call, Event, Config<T>},
 		MinterestModel: minterest_model::{Pallet, Storage, Call, Event, Config<T>},
 		Controller: controller::{Pallet, Storage, Call, Event, Config<T>},
 	}
@@ -52,7 +53,7 @@ mock_impl_liquidity_pools_config!(Runtime);
 mock_impl_minterest_model_config!(Runtime, ZeroAdmin);
 mock_impl_controller_config!(Runtime, ZeroAdmin);
 mock_impl_balances_config!(Runtime);
-mock_impl_mnt_token_config!(Runtime, ZeroAdmin);
+mock_impl_mnt_token_config!(Runtime, ZeroAdmin, MockDexSharesProvider);
 
 ord_parameter_types! {
 	pub const ZeroAdmin: AccountId = 0;
@@ -61,7 +62,7 @@ ord_parameter_types! {
 
 pub struct MockPriceSource;
 
-impl PricesManager<CurrencyId> for MockPriceSource {
+impl PriceSource<CurrencyId> for MockPriceSource {
 	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
 		match currency_id {
 			DOT => return Some(Price::saturating_from_rational(5, 10)), // 0.5 USD
@@ -71,10 +72,34 @@ impl PricesManager<CurrencyId> for MockPriceSource {
 			_ => return None,
 		}
 	}
+}
+
+thread_local! {
+	static DEX_SHARES: RefCell<BTreeMap<(TradingPair, AccountId), Balance>> = RefCell::new(BTreeMap::new());
+}
 
-	fn lock_price(_currency_id: CurrencyId) {}
+pub struct MockDexSharesProvider;
 
-	fn unlock_price(_currency_id: CurrencyId) {}
+impl MockDexSharesProvider {
+	pub fn set_shares(trading_pair: TradingPair, who: AccountId, shares: Balance) {
+		DEX_SHARES.with(|v| v.borrow_mut().insert((trading_pair, who), shares));
+	}
+}
+
+impl DexSharesProvider<AccountId> for MockDexSharesProvider {
+	fn get_total_shares(trading_pair: TradingPair) -> Balance {
+		DEX_SHARES.with(|v| {
+			v.borrow()
+				.iter()
+				.filter(|((pair, _), _)| *pair == trading_pair)
+				.map(|(_, shares)| shares)
+				.sum()
+		})
+	}
+
+	fn get_shares(trading_pair: TradingPair, who: &AccountId) -> Balance {
+		DEX_SHARES.with(|v| v.borrow().get(&(trading_pair, *who)).copied().unwrap_or_default())
+	}
 }
 
 pub struct ExtBuilder {
@@ -2,9 +2,10 @@
 
 use super::Error;
 use crate::mock::*;
-use crate::{MntPoolState, MntState};
-use frame_support::{assert_noop, assert_ok};
-use minterest_primitives::{Balance, CurrencyId, Rate};
+use crate::{MntPoolState, MntState, RewardTarget};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, StorageHasher, Twox64Concat};
+use minterest_primitives::{Balance, CurrencyId, Rate, TradingPair};
 use orml_traits::MultiCurrency;
 use pallet_traits::MntManager;
 use sp_arithmetic::FixedPointNumber;
@@ -72,7 +73,7 @@ fn distribute_mnt_to_borrower_with_threshold() {
 			// At the second it should be transferred to ALICE and so on.
 
 			let dot_speed = 10 * DOLLARS;
-			assert_eq!(MntToken::mnt_speed_storage(DOT), dot_speed);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(DOT)), dot_speed);
 			assert_ok!(MntToken::update_pool_mnt_borrow_index(DOT));
 			assert_ok!(MntToken::distribute_borrower_mnt(DOT, &ALICE, false));
 			check_borrower(DOT, ALICE, 0, 0);
@@ -115,7 +116,7 @@ fn distribute_mnt_to_supplier_with_threshold() {
 			// At the second it should be transferred to ALICE and so on.
 
 			let dot_speed = 10 * DOLLARS;
-			assert_eq!(MntToken::mnt_speed_storage(DOT), dot_speed);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(DOT)), dot_speed);
 
 			// set total issuance
 			Currencies::deposit(MDOT, &ALICE, 100 * DOLLARS).unwrap();
@@ -147,8 +148,8 @@ fn distribute_mnt_to_supplier_from_different_pools() {
 			// Check accruing mnt tokens from two pools for supplier
 			let dot_mnt_speed = 2 * DOLLARS;
 			let ksm_mnt_speed = 8 * DOLLARS;
-			assert_eq!(MntToken::mnt_speed_storage(DOT), dot_mnt_speed);
-			assert_eq!(MntToken::mnt_speed_storage(KSM), ksm_mnt_speed);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(DOT)), dot_mnt_speed);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(KSM)), ksm_mnt_speed);
 
 			// set total issuance
 			Currencies::deposit(MDOT, &ALICE, 100 * DOLLARS).unwrap();
@@ -552,7 +553,7 @@ fn test_update_pool_mnt_supply_index_simple() {
 #[test]
 fn test_minting_enable_disable() {
 	let check_mnt_storage = |pool_id, speed, borrow_index, supply_index, block_number| {
-		assert_eq!(MntToken::mnt_speed_storage(pool_id), speed);
+		assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(pool_id)), speed);
 		assert_eq!(
 			MntToken::mnt_pool_state_storage(pool_id),
 			MntPoolState {
@@ -592,7 +593,8 @@ fn test_minting_enable_disable() {
 			// Enable the distribution of MNT tokens in the DOT liquidity pool
 			let dot_speed = 2 * DOLLARS;
 			assert_ok!(MntToken::set_speed(admin_origin(), DOT, dot_speed));
-			let speed_changed_event = Event::MntToken(crate::Event::MntSpeedChanged(DOT, dot_speed));
+			let speed_changed_event =
+				Event::MntToken(crate::Event::MntSpeedChanged(RewardTarget::Pool(DOT), dot_speed));
 			assert!(System::events()
 				.iter()
 				.any(|record| record.event == speed_changed_event));
@@ -609,7 +611,8 @@ fn test_minting_enable_disable() {
 			// Enable the distribution of MNT tokens in the KSM liquidity pool
 			let ksm_speed = 2 * DOLLARS;
 			assert_ok!(MntToken::set_speed(admin_origin(), KSM, ksm_speed));
-			let speed_changed_event = Event::MntToken(crate::Event::MntSpeedChanged(KSM, ksm_speed));
+			let speed_changed_event =
+				Event::MntToken(crate::Event::MntSpeedChanged(RewardTarget::Pool(KSM), ksm_speed));
 			assert!(System::events()
 				.iter()
 				.any(|record| record.event == speed_changed_event));
@@ -619,11 +622,14 @@ fn test_minting_enable_disable() {
 
 			// Disable the distribution of MNT tokens in the DOT liquidity pool
 			assert_ok!(MntToken::set_speed(admin_origin(), DOT, Balance::zero()));
-			let speed_changed_event = Event::MntToken(crate::Event::MntSpeedChanged(DOT, Balance::zero()));
+			let speed_changed_event =
+				Event::MntToken(crate::Event::MntSpeedChanged(RewardTarget::Pool(DOT), Balance::zero()));
 			assert!(System::events()
 				.iter()
 				.any(|record| record.event == speed_changed_event));
-			assert!(!crate::MntSpeedStorage::<Runtime>::contains_key(DOT));
+			assert!(!crate::MntSpeedStorage::<Runtime>::contains_key(RewardTarget::Pool(
+				DOT
+			)));
 			check_mnt_storage(
 				DOT,
 				Balance::zero(),
@@ -748,3 +754,119 @@ fn transfer_mnt_should_work() {
 			assert_eq!(MntToken::mnt_accrued_storage(ALICE), Balance::zero());
 		});
 }
+
+#[test]
+fn set_dex_pair_speed_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let trading_pair = TradingPair::from_currency_ids(DOT, ETH).unwrap();
+
+		// The dispatch origin of this call must be Root or 2/3 MinterestCouncil.
+		assert_noop!(
+			MntToken::set_dex_pair_speed(alice_origin(), DOT, ETH, DOLLARS),
+			BadOrigin
+		);
+		assert_noop!(
+			MntToken::set_dex_pair_speed(admin_origin(), MDOT, ETH, DOLLARS),
+			Error::<Runtime>::NotValidTradingPair
+		);
+		assert_noop!(
+			MntToken::set_dex_pair_speed(admin_origin(), DOT, DOT, DOLLARS),
+			Error::<Runtime>::NotValidTradingPair
+		);
+		assert_noop!(
+			MntToken::set_dex_pair_speed(admin_origin(), DOT, ETH, Balance::zero()),
+			Error::<Runtime>::MntMintingNotEnabled
+		);
+
+		assert_ok!(MntToken::set_dex_pair_speed(admin_origin(), ETH, DOT, 2 * DOLLARS));
+		let target = RewardTarget::DexPair(trading_pair);
+		let speed_changed_event = Event::MntToken(crate::Event::MntSpeedChanged(target, 2 * DOLLARS));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == speed_changed_event));
+		assert_eq!(MntToken::mnt_speed_storage(target), 2 * DOLLARS);
+		assert_eq!(
+			MntToken::mnt_dex_pair_state_storage(trading_pair),
+			Some(MntState {
+				mnt_distribution_index: Rate::one(),
+				index_updated_at_block: 1,
+			})
+		);
+
+		// Lending pools speeds are independent of the DEX trading pairs speeds.
+		assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(DOT)), Balance::zero());
+
+		System::set_block_number(5);
+		assert_ok!(MntToken::set_dex_pair_speed(admin_origin(), DOT, ETH, Balance::zero()));
+		assert!(!crate::MntSpeedStorage::<Runtime>::contains_key(target));
+	});
+}
+
+#[test]
+fn distribute_liquidity_provider_mnt_should_work() {
+	ExtBuilder::default()
+		.mnt_account_balance(MNT_PALLET_START_BALANCE)
+		.build()
+		.execute_with(|| {
+			let trading_pair = TradingPair::from_currency_ids(DOT, ETH).unwrap();
+			MockDexSharesProvider::set_shares(trading_pair, ALICE, 30 * DOLLARS);
+			MockDexSharesProvider::set_shares(trading_pair, BOB, 10 * DOLLARS);
+			assert_ok!(MntToken::set_dex_pair_speed(admin_origin(), DOT, ETH, 10 * DOLLARS));
+
+			// 100 MNT accrued in 10 blocks for 40 liquidity shares: index = 1 + 100 / 40 = 3.5
+			System::set_block_number(11);
+			assert_ok!(MntToken::update_dex_pair_mnt_index(trading_pair));
+			assert_eq!(
+				MntToken::mnt_dex_pair_state_storage(trading_pair),
+				Some(MntState {
+					mnt_distribution_index: Rate::saturating_from_rational(35, 10),
+					index_updated_at_block: 11,
+				})
+			);
+
+			assert_ok!(MntToken::distribute_liquidity_provider_mnt(trading_pair, &ALICE, false));
+			assert_ok!(MntToken::distribute_liquidity_provider_mnt(trading_pair, &BOB, false));
+			assert_eq!(get_mnt_account_balance(ALICE), 75 * DOLLARS);
+			assert_eq!(get_mnt_account_balance(BOB), 25 * DOLLARS);
+			assert_eq!(
+				MntToken::mnt_liquidity_provider_index_storage(trading_pair, ALICE),
+				Some(Rate::saturating_from_rational(35, 10))
+			);
+			let distributed_event = Event::MntToken(crate::Event::MntDistributedToLiquidityProvider(
+				trading_pair,
+				ALICE,
+				75 * DOLLARS,
+				Rate::saturating_from_rational(35, 10),
+			));
+			assert!(System::events().iter().any(|record| record.event == distributed_event));
+
+			// Nothing is distributed twice.
+			assert_ok!(MntToken::distribute_liquidity_provider_mnt(trading_pair, &ALICE, false));
+			assert_eq!(get_mnt_account_balance(ALICE), 75 * DOLLARS);
+
+			// The accrued MNT are shown by the RPC.
+			System::set_block_number(15);
+			assert_eq!(MntToken::get_user_total_unclaimed_mnt_balance(&BOB), Ok(10 * DOLLARS));
+		});
+}
+
+#[test]
+fn migrate_to_v1_should_move_pool_speeds_to_reward_targets() {
+	ExtBuilder::default().build().execute_with(|| {
+		crate::StorageVersion::<Runtime>::put(crate::Releases::V0);
+		frame_support::storage::migration::put_storage_value(
+			b"MntToken",
+			b"MntSpeedStorage",
+			&Twox64Concat::hash(&DOT.encode()),
+			10 * DOLLARS,
+		);
+
+		assert!(crate::migrations::migrate_to_v1::<Runtime>() > 0);
+		assert_eq!(crate::StorageVersion::<Runtime>::get(), crate::Releases::V1);
+		assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(DOT)), 10 * DOLLARS);
+		assert_eq!(crate::MntSpeedStorage::<Runtime>::iter().count(), 1);
+
+		// The migration is applied only once.
+		assert_eq!(crate::migrations::migrate_to_v1::<Runtime>(), 0);
+	});
+}
@@ -46,6 +46,7 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for mnt_token.
 pub trait WeightInfo {
 	fn set_speed() -> Weight;
+	fn set_dex_pair_speed() -> Weight;
 }
 
 /// Weights for mnt_token using the Minterest node and recommended hardware.
@@ -56,6 +57,12 @@ impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Estimated as `set_speed` of a pool. Regenerate with the `set_dex_pair_speed` benchmark.
+	fn set_dex_pair_speed() -> Weight {
+		(54_153_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -65,4 +72,10 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Estimated as `set_speed` of a pool. Regenerate with the `set_dex_pair_speed` benchmark.
+	fn set_dex_pair_speed() -> Weight {
+		(54_153_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
@@ -10,14 +10,19 @@
 // The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
 #![allow(clippy::unnecessary_mut_passed)]
 
-use minterest_primitives::{CurrencyId, Price};
+use codec::Codec;
+use minterest_primitives::{BlockNumber, CurrencyId, Price, PriceSourceKind};
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-	pub trait PricesRuntimeApi
+	pub trait PricesRuntimeApi<AccountId>
+	where
+		AccountId: Codec,
 	{
 		fn  get_current_price(currency_id: CurrencyId) -> Option<Price>;
-		fn  get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>)>;
+		fn  get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>, Option<BlockNumber>)>;
 		fn  get_all_freshest_prices() -> Vec<(CurrencyId, Option<Price>)>;
+		fn  get_price_history(currency_id: CurrencyId, from: BlockNumber, to: BlockNumber) -> Vec<(BlockNumber, PriceSourceKind, Price)>;
+		fn  get_liquidation_prices(block_number: BlockNumber, borrower: AccountId) -> Option<Vec<(CurrencyId, Price)>>;
 	}
 }
@@ -5,9 +5,10 @@
 //! Corresponding runtime API declaration: `pallets/prices/rpc/run-time/src/lib.rs`
 //! Corresponding runtime API implementation: `runtime/src/lib.rs`
 
+use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use minterest_primitives::{CurrencyId, Price};
+use minterest_primitives::{BlockNumber, CurrencyId, Price, PriceSourceKind};
 pub use prices_rpc_runtime_api::PricesRuntimeApi;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -16,7 +17,7 @@ use std::sync::Arc;
 
 #[rpc]
 /// Base trait for RPC interface of prices
-pub trait PricesRpcApi<BlockHash> {
+pub trait PricesRpcApi<BlockHash, AccountId> {
 	/// This function returns a price for a currency in USD.
 	/// If currency price has been locked, locked value will be returned.
 	/// Otherwise the value from Oracle will be returned
@@ -39,7 +40,8 @@ pub trait PricesRpcApi<BlockHash> {
 	#[rpc(name = "prices_getCurrentPrice")]
 	fn get_current_price(&self, currency_id: CurrencyId, at: Option<BlockHash>) -> Result<Option<Price>>;
 
-	/// This function returns a Vector containing prices for all currencies been locked
+	/// This function returns a Vector containing prices for all currencies been locked and
+	/// the blocks at which the prices are unlocked automatically.
 	/// In case some currency prices were not locked, None will be returned for corresponding
 	/// currencies. Function read prices values from local storage.
 	///
@@ -49,10 +51,12 @@ pub trait PricesRpcApi<BlockHash> {
 	///
 	/// Return:
 	///
-	/// Vec<(currency_id, price)>: vector of (id, price) pairs for all locked currencies
+	/// Vec<(currency_id, price, expiry)>: vector of (id, price, expiry) for all locked currencies
 	///
 	/// - [`currency_id`](`minterest_primitives::CurrencyId`): currency type
 	/// - [`price`](`minterest_primitives::Price`): price for currency in USD
+	/// - `expiry`: the block at which the price is unlocked. None if the price is locked until
+	/// governance unlocks it.
 	///
 	/// # Example:
 	/// ``` ignore
@@ -62,7 +66,10 @@ pub trait PricesRpcApi<BlockHash> {
 	#[doc(alias = "MNT RPC")]
 	#[doc(alias = "MNT module_prices")]
 	#[rpc(name = "prices_getAllLockedPrices")]
-	fn get_all_locked_prices(&self, at: Option<BlockHash>) -> Result<Vec<(CurrencyId, Option<Price>)>>;
+	fn get_all_locked_prices(
+		&self,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(CurrencyId, Option<Price>, Option<BlockNumber>)>>;
 
 	/// This function returns a Vector containing prices for all currencies from Oracle
 	///
@@ -86,6 +93,73 @@ pub trait PricesRpcApi<BlockHash> {
 	#[doc(alias = "MNT module_prices")]
 	#[rpc(name = "prices_getAllFreshestPrices")]
 	fn get_all_freshest_prices(&self, at: Option<BlockHash>) -> Result<Vec<(CurrencyId, Option<Price>)>>;
+
+	/// This function returns the prices used by the protocol for a currency during the range
+	/// of blocks. The first price may be set before the start of the range.
+	///
+	/// Parameters:
+	///  - `&self` :  Self reference
+	///  - `currency_id`: currency type.
+	///  - `from`: the first block of the range.
+	///  - `to`: the last block of the range.
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	/// Return:
+	///
+	/// Vec<(block_number, source, price)>: vector of the prices and the blocks since which they
+	/// were used
+	///
+	/// - `block_number`: the block since which the price was used
+	/// - [`source`](`minterest_primitives::PriceSourceKind`): the way the price was obtained
+	/// - [`price`](`minterest_primitives::Price`): price for currency in USD
+	///
+	/// # Example:
+	/// ``` ignore
+	/// curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0",
+	/// "id":1, "method":"prices_getPriceHistory", "params": [{"UnderlyingAsset":"DOT"}, 100, 200]}'
+	/// ```
+	#[doc(alias = "MNT RPC")]
+	#[doc(alias = "MNT module_prices")]
+	#[rpc(name = "prices_getPriceHistory")]
+	fn get_price_history(
+		&self,
+		currency_id: CurrencyId,
+		from: BlockNumber,
+		to: BlockNumber,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(BlockNumber, PriceSourceKind, Price)>>;
+
+	/// This function returns a Vector containing prices of all currencies used by the
+	/// liquidation of the borrower performed at the block. In case there was no such liquidation
+	/// or its prices are older than the retention period, None will be returned.
+	///
+	/// Parameters:
+	///  - `&self` :  Self reference
+	///  - `block_number`: the block at which the liquidation was performed.
+	///  - `borrower`: the account which was liquidated.
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	/// Return:
+	///
+	/// Option<Vec<(currency_id, price)>>: vector of (id, price) pairs for all currencies
+	///
+	/// - [`currency_id`](`minterest_primitives::CurrencyId`): currency type
+	/// - [`price`](`minterest_primitives::Price`): price for currency in USD
+	///
+	/// # Example:
+	/// ``` ignore
+	/// curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0",
+	/// "id":1, "method":"prices_getLiquidationPrices", "params": [150, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]}'
+	/// ```
+	#[doc(alias = "MNT RPC")]
+	#[doc(alias = "MNT module_prices")]
+	#[rpc(name = "prices_getLiquidationPrices")]
+	fn get_liquidation_prices(
+		&self,
+		block_number: BlockNumber,
+		borrower: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Vec<(CurrencyId, Price)>>>;
 }
 
 /// Struct that implement 'PricesRpcApi'.
@@ -118,11 +192,12 @@ impl From<Error> for i64 {
 }
 
 /// Implementation of 'PricesRpcApi'
-impl<C, Block> PricesRpcApi<<Block as BlockT>::Hash> for PricesRpcImpl<C, Block>
+impl<C, Block, AccountId> PricesRpcApi<<Block as BlockT>::Hash, AccountId> for PricesRpcImpl<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: PricesRuntimeApi<Block>,
+	C::Api: PricesRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
 {
 	fn get_current_price(&self, currency_id: CurrencyId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Price>> {
 		let api = self.client.runtime_api();
@@ -137,7 +212,10 @@ where
 		})
 	}
 
-	fn get_all_locked_prices(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(CurrencyId, Option<Price>)>> {
+	fn get_all_locked_prices(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(CurrencyId, Option<Price>, Option<BlockNumber>)>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
                 // If the block hash is not supplied assume the best block.
@@ -162,4 +240,42 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn get_price_history(
+		&self,
+		currency_id: CurrencyId,
+		from: BlockNumber,
+		to: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(BlockNumber, PriceSourceKind, Price)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_price_history(&at, currency_id, from, to).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get price history for the currency.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_liquidation_prices(
+		&self,
+		block_number: BlockNumber,
+		borrower: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Vec<(CurrencyId, Price)>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_liquidation_prices(&at, block_number, borrower)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get liquidation prices info.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
 }
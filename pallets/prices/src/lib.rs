@@ -6,15 +6,58 @@
 //! process and feed prices for Minterest. Process include:
 //!   - specify a fixed price for stable currency;
 //!   - feed price in USD;
-//!   - lock/unlock the price data get from oracle.
+//!   - lock/unlock the price data get from oracle, locks expire after at most `MaxLockDuration`
+//!   blocks;
+//!   - cross-check the oracle price with the secondary source;
+//!   - stop the price at large moves until governance decision.
+//!
+//! ## Oracle guard
+//!
+//! For the currencies with the oracle guard, the price is provided only when the primary and
+//! the secondary sources agree within the maximum deviation. Depending on the guard settings
+//! either the median of the sources or the primary source price is returned. When the sources
+//! disagree, the price is locked at the last valid value or the borrow and redeem operations
+//! with the pool are paused. When the lock expires while the sources still disagree, the price
+//! is locked again.
+//!
+//! ## Circuit breaker
+//!
+//! For the currencies with the circuit breaker threshold, the oracle price is compared every
+//! block with the oracle price of the previous block. When the price moves more than the
+//! threshold, the price is locked at the previous value, the borrow and redeem operations with
+//! the pool are paused and the new price waits for the governance decision. A confirmed price
+//! is unlocked, a rejected price remains locked at the previous value for `MaxLockDuration`
+//! blocks. Without the decision the new price is unlocked when the lock expires. The operations
+//! paused by the circuit breaker are resumed in all cases, the operations paused before the price
+//! jump remain paused.
+//!
+//! ## Price history
+//!
+//! Every block the price used by the protocol and the way it is obtained are recorded when they
+//! change. At most `MaxPriceHistoryLength` records are stored per currency, which allows to find
+//! out the prices used by the protocol, e.g. by liquidations, at the past blocks.
+//!
+//! ## Time-weighted average price
+//!
+//! Every block the price used by the protocol is added to the cumulative price of the currency.
+//! Snapshots of the cumulative price are stored every `TwapObservationPeriod` blocks, which
+//! allows to calculate time-weighted average price over any window up to
+//! `TwapObservationPeriod * (MaxTwapObservations - 1)` blocks.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
-use frame_support::{pallet_prelude::*, transactional};
-use minterest_primitives::{currency::CurrencyType::UnderlyingAsset, CurrencyId, Price};
+use codec::{Decode, Encode};
+use frame_support::{log, pallet_prelude::*, transactional};
+use minterest_primitives::{
+	currency::CurrencyType::UnderlyingAsset, CurrencyId, Operation, Price, PriceSourceKind, Rate,
+};
 use orml_traits::{DataFeeder, DataProvider};
-use pallet_traits::PricesManager;
+use pallet_traits::{PoolOperationPauser, PriceLocker, PriceSource};
+use sp_runtime::{
+	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, UniqueSaturatedInto, Zero},
+	FixedPointNumber, RuntimeDebug,
+};
 use sp_std::vec::Vec;
 
 pub use module::*;
@@ -25,9 +68,76 @@ mod tests;
 #[cfg(test)]
 mod mock;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
+/// Storage version of the pallet.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum Releases {
+	/// The prices locked by the oracle guard, the circuit breaker and at genesis have no expiry.
+	V0,
+	/// Every locked price has an expiry.
+	V1,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0
+	}
+}
+
+/// The price returned when the sources agree.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum PriceAggregation {
+	/// The median of the primary and the secondary source prices.
+	Median,
+	/// The primary source price.
+	Primary,
+}
+
+/// The action taken when the sources disagree.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum DeviationAction {
+	/// Lock the price at the last value on which the sources agreed.
+	LockPrice,
+	/// Pause borrow and redeem operations with the pool.
+	PausePool,
+}
+
+/// Oracle guard settings of the currency.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub struct OracleGuard {
+	/// Maximum relative deviation of the secondary source price from the primary source price.
+	pub max_deviation: Rate,
+	/// The price returned when the sources agree.
+	pub aggregation: PriceAggregation,
+	/// The action taken when the sources disagree.
+	pub deviation_action: DeviationAction,
+}
+
+/// Cumulative price of the currency.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct PriceAccumulator<BlockNumber> {
+	/// Sum of the prices weighted by the number of blocks during which they were in effect.
+	pub cumulative_price: Price,
+	/// The price in effect since the last update.
+	pub last_price: Price,
+	/// Block number of the last update.
+	pub last_update_block: BlockNumber,
+}
+
+/// The price used by the protocol since the block.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq)]
+pub struct PriceRecord<BlockNumber> {
+	/// Block number since which the price is used.
+	pub block_number: BlockNumber,
+	/// The way the price is obtained.
+	pub source: PriceSourceKind,
+	/// The price used by the protocol.
+	pub price: Price,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -41,9 +151,37 @@ pub mod module {
 		/// The data source, such as Oracle.
 		type Source: DataProvider<CurrencyId, Price> + DataFeeder<CurrencyId, Price, Self::AccountId>;
 
+		/// The secondary data source used to cross-check the prices of the primary source.
+		type SecondarySource: DataProvider<CurrencyId, Price>;
+
+		/// Pauses operations with the pools when the sources disagree.
+		type PoolOperationPauser: PoolOperationPauser;
+
 		/// The origin which may lock and unlock prices feed to system.
 		type LockOrigin: EnsureOrigin<Self::Origin>;
 
+		/// Maximum number of blocks for which the price can be locked by `LockOrigin`.
+		#[pallet::constant]
+		type MaxLockDuration: Get<Self::BlockNumber>;
+
+		/// The origin which may update the oracle guard settings. Root or
+		/// Half Minterest Council can always do this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Number of blocks between the stored snapshots of the cumulative price. Must be greater
+		/// than zero.
+		#[pallet::constant]
+		type TwapObservationPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of the stored snapshots of the cumulative price per currency. Must be
+		/// greater than one.
+		#[pallet::constant]
+		type MaxTwapObservations: Get<u32>;
+
+		/// Maximum number of the stored price records per currency.
+		#[pallet::constant]
+		type MaxPriceHistoryLength: Get<u32>;
+
 		/// Weight information for the extrinsics.
 		type WeightInfo: WeightInfo;
 	}
@@ -52,6 +190,17 @@ pub mod module {
 	pub enum Error<T> {
 		/// The currency is not enabled in protocol.
 		NotValidUnderlyingAssetId,
+		/// Maximum deviation must be between 0 and 1.
+		NotValidMaxDeviationValue,
+		/// TWAP window must be between the observation period and the length of the stored
+		/// price history.
+		NotValidTwapWindow,
+		/// Circuit breaker threshold must be greater than zero.
+		NotValidCircuitBreakerThreshold,
+		/// There is no price jump awaiting the decision for the currency.
+		PriceJumpNotFound,
+		/// Lock expiry must be in the future and not later than `MaxLockDuration` blocks from now.
+		NotValidLockExpiry,
 	}
 
 	#[pallet::event]
@@ -61,8 +210,34 @@ pub mod module {
 		LockPrice(CurrencyId, Price),
 		/// Unlock price. \[currency_id\]
 		UnlockPrice(CurrencyId),
+		/// Oracle guard has been changed: \[currency_id, oracle_guard\]
+		OracleGuardChanged(CurrencyId, Option<OracleGuard>),
+		/// The primary and the secondary sources disagree:
+		/// \[currency_id, primary_price, secondary_price, deviation_action\]
+		PriceSourcesDeviated(CurrencyId, Option<Price>, Option<Price>, DeviationAction),
+		/// The primary and the secondary sources agree again: \[currency_id\]
+		PriceSourcesAgreed(CurrencyId),
+		/// TWAP window has been changed: \[currency_id, twap_window\]
+		TwapWindowChanged(CurrencyId, Option<T::BlockNumber>),
+		/// Circuit breaker threshold has been changed: \[currency_id, threshold\]
+		CircuitBreakerThresholdChanged(CurrencyId, Option<Rate>),
+		/// The price moved more than the circuit breaker threshold, the price is locked at the
+		/// previous value: \[currency_id, previous_price, new_price\]
+		CircuitBreakerTripped(CurrencyId, Price, Price),
+		/// The price jump has been confirmed, the price is unlocked: \[currency_id, new_price\]
+		PriceJumpConfirmed(CurrencyId, Price),
+		/// The price jump has been rejected, the price remains locked:
+		/// \[currency_id, new_price\]
+		PriceJumpRejected(CurrencyId, Price),
+		/// The lock of the price that tripped the circuit breaker has expired without the
+		/// governance decision, the price is unlocked: \[currency_id, new_price\]
+		PriceJumpExpired(CurrencyId, Price),
 	}
 
+	/// Storage version of the pallet.
+	#[pallet::storage]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
 	/// Mapping from currency id to it's locked(approved by Oracles pallet) price in USD.
 	///
 	/// Storage location:
@@ -73,6 +248,162 @@ pub mod module {
 	#[pallet::getter(fn locked_price_storage)]
 	pub type LockedPriceStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
 
+	/// The block at which the locked price is unlocked automatically. Every price is locked for at
+	/// most `MaxLockDuration` blocks.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::locked_price_expiry_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn locked_price_expiry_storage)]
+	pub type LockedPriceExpiryStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, T::BlockNumber, OptionQuery>;
+
+	/// Oracle guard settings. The prices of the currencies without a guard are provided by
+	/// the primary source.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::oracle_guard_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_guard_storage)]
+	pub type OracleGuardStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, OracleGuard, OptionQuery>;
+
+	/// The last price on which the primary and the secondary sources agreed.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::last_valid_price_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn last_valid_price_storage)]
+	pub type LastValidPriceStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
+	/// Currencies whose sources disagree and the block number at which the deviation was
+	/// detected.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::deviated_prices_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn deviated_prices_storage)]
+	pub type DeviatedPricesStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, T::BlockNumber, OptionQuery>;
+
+	/// The operations with the pool paused by the oracle guard. Resumed when the sources agree
+	/// again.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::deviation_paused_operations_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn deviation_paused_operations_storage)]
+	pub type DeviationPausedOperationsStorage<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, Vec<Operation>, ValueQuery>;
+
+	/// Cumulative prices of the currencies.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::price_accumulator_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn price_accumulator_storage)]
+	pub type PriceAccumulatorStorage<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, PriceAccumulator<T::BlockNumber>, OptionQuery>;
+
+	/// Snapshots of the cumulative price: (block_number, cumulative_price). Ordered by the
+	/// block number, at most `MaxTwapObservations` per currency.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::twap_observations_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn twap_observations_storage)]
+	pub type TwapObservationsStorage<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, Vec<(T::BlockNumber, Price)>, ValueQuery>;
+
+	/// Number of blocks over which the time-weighted average price used by the protocol is
+	/// calculated. The protocol uses the spot price of the currencies without a window.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::twap_window_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn twap_window_storage)]
+	pub type TwapWindowStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, T::BlockNumber, OptionQuery>;
+
+	/// Maximum relative change of the oracle price between two blocks. The prices of the
+	/// currencies without a threshold are not checked.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::circuit_breaker_threshold_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn circuit_breaker_threshold_storage)]
+	pub type CircuitBreakerThresholdStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, OptionQuery>;
+
+	/// The oracle price of the previous block of the currencies with the circuit breaker
+	/// threshold.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::previous_price_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn previous_price_storage)]
+	pub type PreviousPriceStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
+	/// The operations with the pool paused by the circuit breaker. Resumed by the governance
+	/// decision on the price jump.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::circuit_breaker_paused_operations_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn circuit_breaker_paused_operations_storage)]
+	pub type CircuitBreakerPausedOperationsStorage<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, Vec<Operation>, ValueQuery>;
+
+	/// The new prices that tripped the circuit breaker and await the governance decision.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::price_jump_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn price_jump_storage)]
+	pub type PriceJumpStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
+	/// History of the prices used by the protocol: currency_id -> record index -> price record.
+	/// The records are ordered by the block number.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::price_history_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn price_history_storage)]
+	pub type PriceHistoryStorage<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, u32, PriceRecord<T::BlockNumber>, OptionQuery>;
+
+	/// Indices of the stored price records: (the oldest record, the next record). At most
+	/// `MaxPriceHistoryLength` records are stored per currency.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=module_prices::module::Pallet::price_history_bounds_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT module_prices")]
+	#[pallet::storage]
+	#[pallet::getter(fn price_history_bounds_storage)]
+	pub type PriceHistoryBoundsStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, (u32, u32), ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		#[allow(clippy::type_complexity)]
@@ -93,9 +424,10 @@ pub mod module {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
-			self.locked_price
-				.iter()
-				.for_each(|(currency_id, price)| LockedPriceStorage::<T>::insert(currency_id, price));
+			self.locked_price.iter().for_each(|(currency_id, price)| {
+				Pallet::<T>::do_lock_price(*currency_id, *price, Pallet::<T>::max_lock_expiry())
+			});
+			StorageVersion::<T>::put(Releases::V1);
 		}
 	}
 
@@ -103,29 +435,150 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate_to_v1::<T>()
+		}
+
+		/// Unlocks the expired prices. Checks the price moves of the currencies with the circuit
+		/// breaker threshold.
+		/// Cross-checks the sources of the guarded currencies and takes the deviation action
+		/// when the sources disagree. Updates the cumulative prices and the price history of all
+		/// enabled currencies.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut expiring_locks: Weight = 0;
+			let expired_locks: Vec<CurrencyId> = LockedPriceExpiryStorage::<T>::iter()
+				.inspect(|_| expiring_locks += 1)
+				.filter_map(|(currency_id, expiry)| if expiry <= now { Some(currency_id) } else { None })
+				.collect();
+			let expired_locks_count = expired_locks.len() as Weight;
+			expired_locks.into_iter().for_each(Self::expire_lock);
+
+			let mut watched_currencies: Weight = 0;
+			CircuitBreakerThresholdStorage::<T>::iter().for_each(|(currency_id, threshold)| {
+				watched_currencies += 1;
+				Self::check_price_move(currency_id, threshold);
+			});
+
+			let mut checked_currencies: Weight = 0;
+			OracleGuardStorage::<T>::iter().for_each(|(currency_id, guard)| {
+				checked_currencies += 1;
+				let primary_price = T::Source::get(&currency_id);
+				let secondary_price = T::SecondarySource::get(&currency_id);
+				match Self::aggregate_prices(primary_price, secondary_price, &guard) {
+					Some(price) => {
+						if Self::last_valid_price_storage(currency_id) != Some(price) {
+							LastValidPriceStorage::<T>::insert(currency_id, price);
+						}
+						if DeviatedPricesStorage::<T>::take(currency_id).is_some() {
+							Self::resume_operations(
+								currency_id,
+								DeviationPausedOperationsStorage::<T>::take(currency_id),
+							);
+							Self::deposit_event(Event::PriceSourcesAgreed(currency_id));
+						}
+					}
+					None => {
+						if DeviatedPricesStorage::<T>::contains_key(currency_id) {
+							return;
+						}
+						DeviatedPricesStorage::<T>::insert(currency_id, now);
+						Self::take_deviation_action(currency_id, guard.deviation_action);
+						Self::deposit_event(Event::PriceSourcesDeviated(
+							currency_id,
+							primary_price,
+							secondary_price,
+							guard.deviation_action,
+						));
+					}
+				}
+			});
+
+			let enabled_currencies = CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset);
+			enabled_currencies.iter().for_each(|&currency_id| {
+				let price = Self::get_underlying_price_with_source(currency_id);
+				Self::update_price_accumulator(currency_id, price.map(|(_, price)| price), now);
+				Self::update_price_history(currency_id, price, now);
+			});
+
+			// Every lock with expiry:
+			// - reads the expiry.
+			// Every expired lock:
+			// - reads the price jump, the paused operations, the pause keeper and the guard;
+			// - removes the locked price and its expiry, the price jump, the paused operations
+			//   and the deviation flag, writes the pause keeper.
+			// Every watched currency:
+			// - reads the threshold, the price jump, the source, the previous price, the locked
+			//   price and the pause keeper;
+			// - writes the previous price, the locked price and its expiry, the price jump, the
+			//   pause keeper and the paused operations.
+			// Every guarded currency:
+			// - reads the guard, both sources, the last valid price, the deviation flag, the
+			//   paused operations and the pause keeper;
+			// - writes the last valid price, the deviation flag, the paused operations and the
+			//   pause keeper, or the deviation flag and the locked price and its expiry.
+			// Every enabled currency:
+			// - reads the locked price, the guard and both sources to get the price, the
+			//   accumulator, the snapshots, the history bounds and the last price record;
+			// - writes the accumulator, the snapshots, the new and the removed price records and
+			//   the history bounds.
+			let updated_currencies = enabled_currencies.len() as Weight;
+			T::DbWeight::get().reads_writes(
+				expiring_locks
+					.saturating_add(expired_locks_count.saturating_mul(4))
+					.saturating_add(watched_currencies.saturating_mul(6))
+					.saturating_add(checked_currencies.saturating_mul(7))
+					.saturating_add(updated_currencies.saturating_mul(8)),
+				expired_locks_count
+					.saturating_mul(6)
+					.saturating_add(watched_currencies.saturating_mul(6))
+					.saturating_add(checked_currencies.saturating_mul(4))
+					.saturating_add(updated_currencies.saturating_mul(5)),
+			)
+		}
+
+		fn integrity_test() {
+			assert!(!T::TwapObservationPeriod::get().is_zero());
+			// A window requires at least two snapshots of the cumulative price.
+			assert!(T::MaxTwapObservations::get() > 1);
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Lock the price and feed it to system.
+		/// Lock the price and feed it to system. The price is unlocked automatically at the
+		/// expiry block.
 		///
 		/// The dispatch origin of this call must be `LockOrigin`.
 		///
 		/// Parameters:
 		/// - `currency_id`: currency type.
+		/// - `expiry`: the block at which the price is unlocked. Must not be later than
+		/// `MaxLockDuration` blocks from now. `None` means the maximum lock duration.
 		#[doc(alias = "MNT Extrinsic")]
 		#[doc(alias = "MNT module_prices")]
 		#[pallet::weight((T::WeightInfo::lock_price(), DispatchClass::Operational))]
 		#[transactional]
-		pub fn lock_price(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+		pub fn lock_price(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
 			T::LockOrigin::ensure_origin(origin)?;
 
 			ensure!(
 				currency_id.is_supported_underlying_asset(),
 				Error::<T>::NotValidUnderlyingAssetId
 			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			let max_expiry = now.saturating_add(T::MaxLockDuration::get());
+			let expiry = expiry.unwrap_or(max_expiry);
+			ensure!(expiry > now && expiry <= max_expiry, Error::<T>::NotValidLockExpiry);
 
-			<Pallet<T> as PricesManager<CurrencyId>>::lock_price(currency_id);
+			// lock price when get valid price from source
+			if let Some(price) = T::Source::get(&currency_id) {
+				Self::do_lock_price(currency_id, price, expiry);
+			}
 			Ok(().into())
 		}
 
@@ -147,41 +600,527 @@ pub mod module {
 				Error::<T>::NotValidUnderlyingAssetId
 			);
 
-			<Pallet<T> as PricesManager<CurrencyId>>::unlock_price(currency_id);
+			<Pallet<T> as PriceLocker<CurrencyId>>::unlock_price(currency_id);
+			Ok(().into())
+		}
+
+		/// Set the oracle guard settings of the currency.
+		///
+		/// Parameters:
+		/// - `currency_id`: the CurrencyId for which the oracle guard is being set.
+		/// - `oracle_guard`: new oracle guard settings. `None` disables cross-checking of the
+		/// sources.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT module_prices")]
+		#[pallet::weight(T::WeightInfo::set_oracle_guard())]
+		#[transactional]
+		pub fn set_oracle_guard(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			oracle_guard: Option<OracleGuard>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				currency_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+
+			match oracle_guard {
+				Some(guard) => {
+					ensure!(
+						guard.max_deviation <= Rate::one(),
+						Error::<T>::NotValidMaxDeviationValue
+					);
+					OracleGuardStorage::<T>::insert(currency_id, guard);
+				}
+				None => {
+					OracleGuardStorage::<T>::remove(currency_id);
+					LastValidPriceStorage::<T>::remove(currency_id);
+					DeviatedPricesStorage::<T>::remove(currency_id);
+					Self::resume_operations(currency_id, DeviationPausedOperationsStorage::<T>::take(currency_id));
+				}
+			}
+
+			Self::deposit_event(Event::OracleGuardChanged(currency_id, oracle_guard));
+			Ok(().into())
+		}
+
+		/// Set the number of blocks over which the time-weighted average price used by the
+		/// protocol is calculated.
+		///
+		/// Parameters:
+		/// - `currency_id`: the CurrencyId for which the window is being set.
+		/// - `twap_window`: new window in blocks. `None` means that the spot price is used.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT module_prices")]
+		#[pallet::weight(T::WeightInfo::set_twap_window())]
+		#[transactional]
+		pub fn set_twap_window(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			twap_window: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				currency_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+
+			match twap_window {
+				Some(window) => {
+					let period = T::TwapObservationPeriod::get();
+					// The oldest stored snapshot is `MaxTwapObservations - 1` periods before the
+					// latest one, so a longer window would never be covered by the price history.
+					let max_window = period.saturating_mul(T::MaxTwapObservations::get().saturating_sub(1).into());
+					ensure!(
+						!period.is_zero() && window >= period && window <= max_window,
+						Error::<T>::NotValidTwapWindow
+					);
+					TwapWindowStorage::<T>::insert(currency_id, window);
+				}
+				None => TwapWindowStorage::<T>::remove(currency_id),
+			}
+
+			Self::deposit_event(Event::TwapWindowChanged(currency_id, twap_window));
+			Ok(().into())
+		}
+
+		/// Set the maximum relative change of the oracle price between two blocks.
+		///
+		/// Parameters:
+		/// - `currency_id`: the CurrencyId for which the threshold is being set.
+		/// - `threshold`: new circuit breaker threshold. `None` disables the circuit breaker.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT module_prices")]
+		#[pallet::weight(T::WeightInfo::set_circuit_breaker_threshold())]
+		#[transactional]
+		pub fn set_circuit_breaker_threshold(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			threshold: Option<Rate>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				currency_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+
+			match threshold {
+				Some(threshold) => {
+					ensure!(!threshold.is_zero(), Error::<T>::NotValidCircuitBreakerThreshold);
+					CircuitBreakerThresholdStorage::<T>::insert(currency_id, threshold);
+				}
+				None => {
+					CircuitBreakerThresholdStorage::<T>::remove(currency_id);
+					PreviousPriceStorage::<T>::remove(currency_id);
+				}
+			}
+
+			Self::deposit_event(Event::CircuitBreakerThresholdChanged(currency_id, threshold));
+			Ok(().into())
+		}
+
+		/// Confirm the price that tripped the circuit breaker. The price is unlocked and
+		/// the borrow and redeem operations with the pool are resumed.
+		///
+		/// Parameters:
+		/// - `currency_id`: the CurrencyId whose price jump is confirmed.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT module_prices")]
+		#[pallet::weight(T::WeightInfo::confirm_price_jump())]
+		#[transactional]
+		pub fn confirm_price_jump(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let new_price = PriceJumpStorage::<T>::take(currency_id).ok_or(Error::<T>::PriceJumpNotFound)?;
+
+			<Pallet<T> as PriceLocker<CurrencyId>>::unlock_price(currency_id);
+			Self::resume_pool(currency_id);
+
+			Self::deposit_event(Event::PriceJumpConfirmed(currency_id, new_price));
+			Ok(().into())
+		}
+
+		/// Reject the price that tripped the circuit breaker. The price remains locked at
+		/// the previous value for `MaxLockDuration` blocks and the borrow and redeem operations
+		/// with the pool are resumed.
+		///
+		/// Parameters:
+		/// - `currency_id`: the CurrencyId whose price jump is rejected.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[doc(alias = "MNT Extrinsic")]
+		#[doc(alias = "MNT module_prices")]
+		#[pallet::weight(T::WeightInfo::reject_price_jump())]
+		#[transactional]
+		pub fn reject_price_jump(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let new_price = PriceJumpStorage::<T>::take(currency_id).ok_or(Error::<T>::PriceJumpNotFound)?;
+
+			if LockedPriceStorage::<T>::contains_key(currency_id) {
+				LockedPriceExpiryStorage::<T>::insert(currency_id, Self::max_lock_expiry());
+			}
+			Self::resume_pool(currency_id);
+
+			Self::deposit_event(Event::PriceJumpRejected(currency_id, new_price));
 			Ok(().into())
 		}
 	}
 }
 
-impl<T: Config> PricesManager<CurrencyId> for Pallet<T> {
+impl<T: Config> Pallet<T> {
+	/// Returns the price of the guarded currency if the sources agree within the maximum
+	/// deviation, otherwise returns None.
+	fn aggregate_prices(
+		primary_price: Option<Price>,
+		secondary_price: Option<Price>,
+		guard: &OracleGuard,
+	) -> Option<Price> {
+		let (primary_price, secondary_price) = (primary_price?, secondary_price?);
+		let deviation = if primary_price > secondary_price {
+			primary_price.saturating_sub(secondary_price)
+		} else {
+			secondary_price.saturating_sub(primary_price)
+		}
+		.checked_div(&primary_price)?;
+		if deviation > guard.max_deviation {
+			return None;
+		}
+
+		match guard.aggregation {
+			PriceAggregation::Median => primary_price
+				.saturating_add(secondary_price)
+				.checked_div(&Price::saturating_from_integer(2)),
+			PriceAggregation::Primary => Some(primary_price),
+		}
+	}
+
+	/// Returns the price used by the protocol and the way it is obtained.
+	fn get_underlying_price_with_source(currency_id: CurrencyId) -> Option<(PriceSourceKind, Price)> {
+		// if locked price exists, return it, otherwise return latest price from oracle:
+		if let Some(price) = Self::locked_price_storage(currency_id) {
+			return Some((PriceSourceKind::Locked, price));
+		}
+		match Self::oracle_guard_storage(currency_id) {
+			Some(guard) => Self::aggregate_prices(
+				T::Source::get(&currency_id),
+				T::SecondarySource::get(&currency_id),
+				&guard,
+			)
+			.map(|price| (PriceSourceKind::Guarded, price)),
+			None => T::Source::get(&currency_id).map(|price| (PriceSourceKind::Oracle, price)),
+		}
+	}
+
+	/// Adds the price record if the price used by the protocol or the way it is obtained
+	/// has changed since the last record.
+	fn update_price_history(currency_id: CurrencyId, price: Option<(PriceSourceKind, Price)>, now: T::BlockNumber) {
+		let (source, price) = match price {
+			Some(price) => price,
+			None => return,
+		};
+		let (mut first_index, next_index) = Self::price_history_bounds_storage(currency_id);
+		if next_index > first_index
+			&& Self::price_history_storage(currency_id, next_index - 1)
+				.map_or(false, |record| record.source == source && record.price == price)
+		{
+			return;
+		}
+
+		PriceHistoryStorage::<T>::insert(
+			currency_id,
+			next_index,
+			PriceRecord {
+				block_number: now,
+				source,
+				price,
+			},
+		);
+		let next_index = next_index.saturating_add(1);
+		if next_index.saturating_sub(first_index) > T::MaxPriceHistoryLength::get() {
+			PriceHistoryStorage::<T>::remove(currency_id, first_index);
+			first_index = first_index.saturating_add(1);
+		}
+		PriceHistoryBoundsStorage::<T>::insert(currency_id, (first_index, next_index));
+	}
+
+	/// Returns the index of the price record in effect at the block. Returns None if the block
+	/// is older than the stored history.
+	fn find_price_record(currency_id: CurrencyId, block_number: T::BlockNumber) -> Option<u32> {
+		let (first_index, next_index) = Self::price_history_bounds_storage(currency_id);
+		let (mut low, mut high) = (first_index, next_index);
+		// Binary search of the first record after the block.
+		while low < high {
+			let middle = low + (high - low) / 2;
+			match Self::price_history_storage(currency_id, middle) {
+				Some(record) if record.block_number <= block_number => low = middle + 1,
+				_ => high = middle,
+			}
+		}
+		if low > first_index {
+			Some(low - 1)
+		} else {
+			None
+		}
+	}
+
+	/// Adds the price in effect since the last update to the cumulative price and stores the
+	/// snapshot of the cumulative price every `TwapObservationPeriod` blocks.
+	fn update_price_accumulator(currency_id: CurrencyId, price: Option<Price>, now: T::BlockNumber) {
+		let accumulator = match (Self::price_accumulator_storage(currency_id), price) {
+			(Some(accumulator), _) => Self::accumulate(&accumulator, now).map(|cumulative_price| PriceAccumulator {
+				cumulative_price,
+				last_price: price.unwrap_or(accumulator.last_price),
+				last_update_block: now,
+			}),
+			(None, Some(price)) => Some(PriceAccumulator {
+				cumulative_price: Price::zero(),
+				last_price: price,
+				last_update_block: now,
+			}),
+			(None, None) => None,
+		};
+		let accumulator = match accumulator {
+			Some(accumulator) => accumulator,
+			None => return,
+		};
+
+		let period = T::TwapObservationPeriod::get();
+		if !period.is_zero() && (now % period).is_zero() {
+			TwapObservationsStorage::<T>::mutate(currency_id, |observations| {
+				observations.push((now, accumulator.cumulative_price));
+				let excess_observations = observations
+					.len()
+					.saturating_sub(T::MaxTwapObservations::get() as usize);
+				observations.drain(..excess_observations);
+			});
+		}
+		PriceAccumulatorStorage::<T>::insert(currency_id, accumulator);
+	}
+
+	/// Returns the cumulative price at the block `now`.
+	fn accumulate(accumulator: &PriceAccumulator<T::BlockNumber>, now: T::BlockNumber) -> Option<Price> {
+		let elapsed: u128 = now
+			.saturating_sub(accumulator.last_update_block)
+			.unique_saturated_into();
+		accumulator
+			.last_price
+			.checked_mul(&Price::saturating_from_integer(elapsed))
+			.and_then(|weighted_price| accumulator.cumulative_price.checked_add(&weighted_price))
+	}
+
+	/// Returns the time-weighted average price of the currency over at least `window` blocks.
+	/// The actual window is extended to the closest stored snapshot of the cumulative price.
+	/// Returns None if the price history is shorter than the window.
+	///
+	/// - `currency_id`: the currency whose price is requested.
+	/// - `window`: number of blocks over which the price is averaged.
+	pub fn get_twap(currency_id: CurrencyId, window: T::BlockNumber) -> Option<Price> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let accumulator = Self::price_accumulator_storage(currency_id)?;
+		let cumulative_price = Self::accumulate(&accumulator, now)?;
+
+		let window_start = now.checked_sub(&window)?;
+		let (observation_block, observation_cumulative_price) = Self::twap_observations_storage(currency_id)
+			.into_iter()
+			.rev()
+			.find(|(block_number, _)| *block_number <= window_start)?;
+
+		let elapsed: u128 = now.saturating_sub(observation_block).unique_saturated_into();
+		cumulative_price
+			.checked_sub(&observation_cumulative_price)?
+			.checked_div(&Price::saturating_from_integer(elapsed))
+	}
+
+	/// Locks the price until the expiry block.
+	fn do_lock_price(currency_id: CurrencyId, price: Price, expiry: T::BlockNumber) {
+		LockedPriceStorage::<T>::insert(currency_id, price);
+		LockedPriceExpiryStorage::<T>::insert(currency_id, expiry);
+		<Pallet<T>>::deposit_event(Event::LockPrice(currency_id, price));
+	}
+
+	/// Returns the block at which the price locked now expires after `MaxLockDuration` blocks.
+	fn max_lock_expiry() -> T::BlockNumber {
+		<frame_system::Pallet<T>>::block_number().saturating_add(T::MaxLockDuration::get())
+	}
+
+	/// Unlocks the expired price. The price jump awaiting the governance decision goes live and
+	/// the operations paused by the circuit breaker are resumed. The price locked by the oracle
+	/// guard is locked again at the last valid value if the sources still disagree.
+	fn expire_lock(currency_id: CurrencyId) {
+		<Self as PriceLocker<CurrencyId>>::unlock_price(currency_id);
+		if let Some(new_price) = PriceJumpStorage::<T>::take(currency_id) {
+			Self::resume_pool(currency_id);
+			Self::deposit_event(Event::PriceJumpExpired(currency_id, new_price));
+		}
+		let locked_by_guard = Self::oracle_guard_storage(currency_id)
+			.map_or(false, |guard| guard.deviation_action == DeviationAction::LockPrice);
+		if locked_by_guard {
+			// The deviation is detected again by the following check of the sources.
+			DeviatedPricesStorage::<T>::remove(currency_id);
+		}
+	}
+
+	/// Locks the price at the previous value and pauses the pool if the oracle price moved
+	/// more than the threshold since the previous block. The locked prices and the prices
+	/// awaiting the governance decision are not checked.
+	fn check_price_move(currency_id: CurrencyId, threshold: Rate) {
+		if PriceJumpStorage::<T>::contains_key(currency_id) {
+			return;
+		}
+		let new_price = match T::Source::get(&currency_id) {
+			Some(price) => price,
+			None => return,
+		};
+		let previous_price = match PreviousPriceStorage::<T>::mutate(currency_id, |price| price.replace(new_price)) {
+			Some(price) => price,
+			None => return,
+		};
+		if Self::locked_price_storage(currency_id).is_some() {
+			return;
+		}
+
+		let price_move = if new_price > previous_price {
+			new_price.saturating_sub(previous_price)
+		} else {
+			previous_price.saturating_sub(new_price)
+		}
+		.checked_div(&previous_price);
+		if price_move.map_or(true, |price_move| price_move <= threshold) {
+			return;
+		}
+
+		Self::do_lock_price(currency_id, previous_price, Self::max_lock_expiry());
+		PriceJumpStorage::<T>::insert(currency_id, new_price);
+		CircuitBreakerPausedOperationsStorage::<T>::insert(currency_id, Self::pause_pool(currency_id));
+		Self::deposit_event(Event::CircuitBreakerTripped(currency_id, previous_price, new_price));
+	}
+
+	/// Pauses the borrow and redeem operations with the pool. Returns the paused operations:
+	/// the operations paused by other means are skipped and remain paused when the pool is
+	/// resumed.
+	fn pause_pool(currency_id: CurrencyId) -> Vec<Operation> {
+		let paused_operations: Vec<Operation> = [Operation::Borrow, Operation::Redeem]
+			.iter()
+			.copied()
+			.filter(|&operation| !T::PoolOperationPauser::is_operation_paused(currency_id, operation))
+			.collect();
+		paused_operations
+			.iter()
+			.for_each(|&operation| T::PoolOperationPauser::pause_operation(currency_id, operation));
+		paused_operations
+	}
+
+	/// Resumes the operations with the pool.
+	fn resume_operations(currency_id: CurrencyId, operations: Vec<Operation>) {
+		operations
+			.into_iter()
+			.for_each(|operation| T::PoolOperationPauser::resume_operation(currency_id, operation));
+	}
+
+	/// Resumes the operations with the pool paused by the circuit breaker.
+	fn resume_pool(currency_id: CurrencyId) {
+		Self::resume_operations(
+			currency_id,
+			CircuitBreakerPausedOperationsStorage::<T>::take(currency_id),
+		);
+	}
+
+	/// Locks the price at the last valid value or pauses the pool until the sources agree.
+	fn take_deviation_action(currency_id: CurrencyId, deviation_action: DeviationAction) {
+		match deviation_action {
+			DeviationAction::LockPrice => {
+				if Self::locked_price_storage(currency_id).is_some() {
+					return;
+				}
+				if let Some(price) = Self::last_valid_price_storage(currency_id) {
+					Self::do_lock_price(currency_id, price, Self::max_lock_expiry());
+				}
+			}
+			DeviationAction::PausePool => {
+				DeviationPausedOperationsStorage::<T>::insert(currency_id, Self::pause_pool(currency_id));
+			}
+		}
+	}
+}
+
+impl<T: Config> PriceSource<CurrencyId> for Pallet<T> {
 	/// Get price underlying token in USD.
 	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
-		// if locked price exists, return it, otherwise return latest price from oracle:
-		Self::locked_price_storage(currency_id).or_else(|| T::Source::get(&currency_id))
+		Self::get_underlying_price_with_source(currency_id).map(|(_, price)| price)
 	}
 
-	/// Locks price when get valid price from source.
+	/// Get time-weighted average price underlying token in USD over the window configured for
+	/// the currency.
+	fn get_underlying_twap_price(currency_id: CurrencyId) -> Option<Price> {
+		Self::get_twap(currency_id, Self::twap_window_storage(currency_id)?)
+	}
+}
+
+impl<T: Config> PriceLocker<CurrencyId> for Pallet<T> {
+	/// Locks price when get valid price from source for the maximum lock duration.
 	fn lock_price(currency_id: CurrencyId) {
-		// lock price when get valid price from source
-		if let Some(val) = T::Source::get(&currency_id) {
-			LockedPriceStorage::<T>::insert(currency_id, val);
-			<Pallet<T>>::deposit_event(Event::LockPrice(currency_id, val));
+		if let Some(price) = T::Source::get(&currency_id) {
+			Self::do_lock_price(currency_id, price, Self::max_lock_expiry());
 		}
 	}
 
 	/// Unlocks price when get valid price from source.
 	fn unlock_price(currency_id: CurrencyId) {
 		LockedPriceStorage::<T>::remove(currency_id);
+		LockedPriceExpiryStorage::<T>::remove(currency_id);
 		<Pallet<T>>::deposit_event(Event::UnlockPrice(currency_id));
 	}
 }
 
 /// RPC calls
 impl<T: Config> Pallet<T> {
+	pub fn get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>, Option<T::BlockNumber>)> {
+		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.map(|currency_id| {
+				(
+					currency_id,
+					Self::locked_price_storage(currency_id),
+					Self::locked_price_expiry_storage(currency_id),
+				)
+			})
+			.collect()
+	}
+
 	pub fn get_all_freshest_prices() -> Vec<(CurrencyId, Option<Price>)> {
 		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
 			.into_iter()
 			.map(|currency_id| (currency_id, T::Source::get(&currency_id)))
 			.collect()
 	}
+
+	/// Returns the price records of the currency in effect during the blocks from `from` to `to`
+	/// inclusive. The first record may start before `from`.
+	pub fn get_price_history(
+		currency_id: CurrencyId,
+		from: T::BlockNumber,
+		to: T::BlockNumber,
+	) -> Vec<PriceRecord<T::BlockNumber>> {
+		let (first_index, next_index) = Self::price_history_bounds_storage(currency_id);
+		let start_index = Self::find_price_record(currency_id, from).unwrap_or(first_index);
+		(start_index..next_index)
+			.filter_map(|index| Self::price_history_storage(currency_id, index))
+			.take_while(|record| record.block_number <= to)
+			.collect()
+	}
+
+	/// Returns the price record of the currency in effect at the block. Returns None if the
+	/// block is older than the stored history.
+	pub fn get_price_at(currency_id: CurrencyId, block_number: T::BlockNumber) -> Option<PriceRecord<T::BlockNumber>> {
+		Self::find_price_record(currency_id, block_number)
+			.and_then(|index| Self::price_history_storage(currency_id, index))
+	}
 }
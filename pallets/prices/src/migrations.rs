@@ -0,0 +1,35 @@
+//! Storage migrations for the prices pallet.
+
+use super::*;
+
+/// Sets the expiry of the locked prices without one, which were locked by the oracle guard, the
+/// circuit breaker or at genesis. The prices are locked for `MaxLockDuration` blocks from now.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if StorageVersion::<T>::get() != Releases::V0 {
+		return 0;
+	}
+
+	let locked_prices: Vec<CurrencyId> = LockedPriceStorage::<T>::iter()
+		.map(|(currency_id, _)| currency_id)
+		.collect();
+	let expiry = Pallet::<T>::max_lock_expiry();
+	let mut migrated_locks: Weight = 0;
+	locked_prices.iter().for_each(|currency_id| {
+		if !LockedPriceExpiryStorage::<T>::contains_key(currency_id) {
+			LockedPriceExpiryStorage::<T>::insert(currency_id, expiry);
+			migrated_locks += 1;
+		}
+	});
+	StorageVersion::<T>::put(Releases::V1);
+
+	log::info!(
+		target: "Prices",
+		"migrated to v1: {:?} locked prices got an expiry",
+		migrated_locks,
+	);
+
+	T::DbWeight::get().reads_writes(
+		(locked_prices.len() as Weight).saturating_mul(2).saturating_add(2),
+		migrated_locks.saturating_add(1),
+	)
+}
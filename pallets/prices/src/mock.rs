@@ -4,13 +4,14 @@ use super::*;
 use crate as module_prices;
 use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
 use frame_system::EnsureSignedBy;
-use minterest_primitives::{Balance, CurrencyId};
+use minterest_primitives::{Balance, CurrencyId, Operation};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup, Zero},
 	FixedPointNumber,
 };
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 pub use test_helper::*;
 
 // -----------------------------------------------------------------------------------------
@@ -44,8 +45,17 @@ mock_impl_prices_module_config!(TestRuntime, OneAlice);
 // 										DATA PROVIDER
 // -----------------------------------------------------------------------------------------
 pub struct MockDataProvider;
+impl MockDataProvider {
+	pub fn set_price(currency_id: CurrencyId, price: Price) {
+		PRIMARY_PRICES.with(|v| v.borrow_mut().insert(currency_id, price));
+	}
+}
+
 impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		if let Some(price) = PRIMARY_PRICES.with(|v| v.borrow().get(currency_id).copied()) {
+			return Some(price);
+		}
 		match currency_id {
 			&MNT => Some(Price::zero()),
 			&BTC => Some(Price::saturating_from_integer(48_000)),
@@ -63,6 +73,46 @@ impl DataFeeder<CurrencyId, Price, AccountId> for MockDataProvider {
 	}
 }
 
+thread_local! {
+	static PRIMARY_PRICES: RefCell<BTreeMap<CurrencyId, Price>> = RefCell::new(BTreeMap::new());
+	static SECONDARY_PRICE: RefCell<Option<Price>> = RefCell::new(None);
+	static PAUSED_OPERATIONS: RefCell<Vec<(CurrencyId, Operation)>> = RefCell::new(vec![]);
+}
+
+pub struct MockSecondaryDataProvider;
+impl MockSecondaryDataProvider {
+	pub fn set_price(price: Option<Price>) {
+		SECONDARY_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+
+impl DataProvider<CurrencyId, Price> for MockSecondaryDataProvider {
+	fn get(_currency_id: &CurrencyId) -> Option<Price> {
+		SECONDARY_PRICE.with(|v| *v.borrow())
+	}
+}
+
+pub struct MockPoolOperationPauser;
+impl MockPoolOperationPauser {
+	pub fn paused_operations() -> Vec<(CurrencyId, Operation)> {
+		PAUSED_OPERATIONS.with(|v| v.borrow().clone())
+	}
+}
+
+impl PoolOperationPauser for MockPoolOperationPauser {
+	fn pause_operation(pool_id: CurrencyId, operation: Operation) {
+		PAUSED_OPERATIONS.with(|v| v.borrow_mut().push((pool_id, operation)));
+	}
+
+	fn resume_operation(pool_id: CurrencyId, operation: Operation) {
+		PAUSED_OPERATIONS.with(|v| v.borrow_mut().retain(|paused| *paused != (pool_id, operation)));
+	}
+
+	fn is_operation_paused(pool_id: CurrencyId, operation: Operation) -> bool {
+		PAUSED_OPERATIONS.with(|v| v.borrow().contains(&(pool_id, operation)))
+	}
+}
+
 // -----------------------------------------------------------------------------------------
 // 									EXTBUILDER
 // -----------------------------------------------------------------------------------------
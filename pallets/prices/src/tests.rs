@@ -2,9 +2,9 @@
 
 use super::*;
 use crate::mock::{Event, *};
-use frame_support::{assert_noop, assert_ok};
-use minterest_primitives::Price;
-use pallet_traits::PricesManager;
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use minterest_primitives::{Operation, Price, PriceSourceKind, Rate};
+use pallet_traits::PriceSource;
 use sp_runtime::{
 	traits::{BadOrigin, Zero},
 	FixedPointNumber,
@@ -40,6 +40,8 @@ fn lock_price_should_work() {
 				TestPrices::get_underlying_price(BTC),
 				Some(Price::saturating_from_integer(80_000))
 			);
+			// The genesis lock expires after the maximum lock duration.
+			assert_eq!(TestPrices::locked_price_expiry_storage(BTC), Some(10));
 			assert_ok!(TestPrices::unlock_price(alice_origin(), BTC));
 			assert_eq!(
 				TestPrices::get_underlying_price(BTC),
@@ -52,7 +54,7 @@ fn lock_price_should_work() {
 fn lock_price_call_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		System::set_block_number(1);
-		assert_ok!(TestPrices::lock_price(alice_origin(), BTC));
+		assert_ok!(TestPrices::lock_price(alice_origin(), BTC, None));
 
 		let lock_price_event = Event::TestPrices(crate::Event::LockPrice(BTC, Price::saturating_from_integer(48000)));
 		assert!(System::events().iter().any(|record| record.event == lock_price_event));
@@ -60,11 +62,54 @@ fn lock_price_call_should_work() {
 			TestPrices::locked_price_storage(BTC),
 			Some(Price::saturating_from_integer(48000))
 		);
-		assert_noop!(TestPrices::lock_price(bob_origin(), BTC), BadOrigin);
+		// The price is locked for the maximum lock duration by default.
+		assert_eq!(TestPrices::locked_price_expiry_storage(BTC), Some(11));
+		assert_noop!(TestPrices::lock_price(bob_origin(), BTC, None), BadOrigin);
 		assert_noop!(
-			TestPrices::lock_price(alice_origin(), MDOT),
+			TestPrices::lock_price(alice_origin(), MDOT, None),
 			Error::<TestRuntime>::NotValidUnderlyingAssetId
 		);
+		assert_noop!(
+			TestPrices::lock_price(alice_origin(), DOT, Some(1)),
+			Error::<TestRuntime>::NotValidLockExpiry
+		);
+		assert_noop!(
+			TestPrices::lock_price(alice_origin(), DOT, Some(12)),
+			Error::<TestRuntime>::NotValidLockExpiry
+		);
+	});
+}
+
+#[test]
+fn locked_price_should_be_unlocked_at_expiry() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TestPrices::lock_price(alice_origin(), BTC, Some(5)));
+		MockDataProvider::set_price(BTC, Price::saturating_from_integer(50_000));
+
+		TestPrices::on_initialize(4);
+		assert_eq!(
+			TestPrices::get_underlying_price(BTC),
+			Some(Price::saturating_from_integer(48_000))
+		);
+		assert_eq!(
+			TestPrices::get_all_locked_prices(),
+			vec![
+				(DOT, None, None),
+				(KSM, None, None),
+				(BTC, Some(Price::saturating_from_integer(48_000)), Some(5)),
+				(ETH, None, None),
+			]
+		);
+
+		TestPrices::on_initialize(5);
+		assert_eq!(TestPrices::locked_price_storage(BTC), None);
+		assert_eq!(TestPrices::locked_price_expiry_storage(BTC), None);
+		assert_eq!(
+			TestPrices::get_underlying_price(BTC),
+			Some(Price::saturating_from_integer(50_000))
+		);
+		let unlock_price_event = Event::TestPrices(crate::Event::UnlockPrice(BTC));
+		assert!(System::events().iter().any(|record| record.event == unlock_price_event));
 	});
 }
 
@@ -84,8 +129,599 @@ fn unlock_price_call_should_work() {
 
 			assert_noop!(TestPrices::unlock_price(bob_origin(), BTC), BadOrigin);
 			assert_noop!(
-				TestPrices::lock_price(alice_origin(), MDOT),
+				TestPrices::lock_price(alice_origin(), MDOT, None),
 				Error::<TestRuntime>::NotValidUnderlyingAssetId
 			);
 		});
 }
+
+fn oracle_guard(aggregation: PriceAggregation, deviation_action: DeviationAction) -> OracleGuard {
+	OracleGuard {
+		max_deviation: Rate::saturating_from_rational(5, 100),
+		aggregation,
+		deviation_action,
+	}
+}
+
+#[test]
+fn set_oracle_guard_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let guard = oracle_guard(PriceAggregation::Median, DeviationAction::LockPrice);
+		assert_ok!(TestPrices::set_oracle_guard(alice_origin(), BTC, Some(guard)));
+		assert_eq!(TestPrices::oracle_guard_storage(BTC), Some(guard));
+		let expected_event = Event::TestPrices(crate::Event::OracleGuardChanged(BTC, Some(guard)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_ok!(TestPrices::set_oracle_guard(alice_origin(), BTC, None));
+		assert_eq!(TestPrices::oracle_guard_storage(BTC), None);
+
+		assert_noop!(TestPrices::set_oracle_guard(bob_origin(), BTC, Some(guard)), BadOrigin);
+		assert_noop!(
+			TestPrices::set_oracle_guard(alice_origin(), MDOT, Some(guard)),
+			Error::<TestRuntime>::NotValidUnderlyingAssetId
+		);
+		assert_noop!(
+			TestPrices::set_oracle_guard(
+				alice_origin(),
+				BTC,
+				Some(OracleGuard {
+					max_deviation: Rate::saturating_from_integer(2),
+					..guard
+				})
+			),
+			Error::<TestRuntime>::NotValidMaxDeviationValue
+		);
+	});
+}
+
+#[test]
+fn get_underlying_price_should_aggregate_sources() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Primary source price 1 DOT = 40 USD.
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(42)));
+		let guard = oracle_guard(PriceAggregation::Median, DeviationAction::LockPrice);
+		assert_ok!(TestPrices::set_oracle_guard(alice_origin(), DOT, Some(guard)));
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(41))
+		);
+
+		let guard = oracle_guard(PriceAggregation::Primary, DeviationAction::LockPrice);
+		assert_ok!(TestPrices::set_oracle_guard(alice_origin(), DOT, Some(guard)));
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(40))
+		);
+
+		// Deviation is 10% which is greater than the maximum deviation.
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(44)));
+		assert_eq!(TestPrices::get_underlying_price(DOT), None);
+
+		// Secondary source is unavailable.
+		MockSecondaryDataProvider::set_price(None);
+		assert_eq!(TestPrices::get_underlying_price(DOT), None);
+
+		// Prices of currencies without a guard are provided by the primary source.
+		assert_eq!(
+			TestPrices::get_underlying_price(BTC),
+			Some(Price::saturating_from_integer(48_000))
+		);
+	});
+}
+
+#[test]
+fn price_should_be_locked_when_sources_disagree() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(42)));
+		let guard = oracle_guard(PriceAggregation::Median, DeviationAction::LockPrice);
+		assert_ok!(TestPrices::set_oracle_guard(alice_origin(), DOT, Some(guard)));
+
+		TestPrices::on_initialize(1);
+		assert_eq!(
+			TestPrices::last_valid_price_storage(DOT),
+			Some(Price::saturating_from_integer(41))
+		);
+
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(60)));
+		TestPrices::on_initialize(2);
+		assert_eq!(TestPrices::deviated_prices_storage(DOT), Some(2));
+		assert_eq!(
+			TestPrices::locked_price_storage(DOT),
+			Some(Price::saturating_from_integer(41))
+		);
+		assert_eq!(TestPrices::locked_price_expiry_storage(DOT), Some(11));
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(41))
+		);
+		let expected_event = Event::TestPrices(crate::Event::PriceSourcesDeviated(
+			DOT,
+			Some(Price::saturating_from_integer(40)),
+			Some(Price::saturating_from_integer(60)),
+			DeviationAction::LockPrice,
+		));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The sources agree again, the price remains locked until it is unlocked by governance or
+		// the lock expires.
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(40)));
+		TestPrices::on_initialize(3);
+		assert_eq!(TestPrices::deviated_prices_storage(DOT), None);
+		assert_eq!(
+			TestPrices::locked_price_storage(DOT),
+			Some(Price::saturating_from_integer(41))
+		);
+		let expected_event = Event::TestPrices(crate::Event::PriceSourcesAgreed(DOT));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+#[test]
+fn price_should_be_locked_again_when_lock_expires_and_sources_disagree() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(42)));
+		let guard = oracle_guard(PriceAggregation::Median, DeviationAction::LockPrice);
+		assert_ok!(TestPrices::set_oracle_guard(alice_origin(), DOT, Some(guard)));
+		TestPrices::on_initialize(1);
+
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(60)));
+		TestPrices::on_initialize(2);
+		assert_eq!(TestPrices::locked_price_expiry_storage(DOT), Some(11));
+
+		System::set_block_number(11);
+		TestPrices::on_initialize(11);
+		assert_eq!(TestPrices::deviated_prices_storage(DOT), Some(11));
+		assert_eq!(
+			TestPrices::locked_price_storage(DOT),
+			Some(Price::saturating_from_integer(41))
+		);
+		assert_eq!(TestPrices::locked_price_expiry_storage(DOT), Some(21));
+	});
+}
+
+#[test]
+fn pool_should_be_paused_when_sources_disagree() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(60)));
+		let guard = oracle_guard(PriceAggregation::Median, DeviationAction::PausePool);
+		assert_ok!(TestPrices::set_oracle_guard(alice_origin(), DOT, Some(guard)));
+
+		TestPrices::on_initialize(1);
+		assert_eq!(
+			MockPoolOperationPauser::paused_operations(),
+			vec![(DOT, Operation::Borrow), (DOT, Operation::Redeem)]
+		);
+		assert_eq!(TestPrices::locked_price_storage(DOT), None);
+
+		// The action is taken only once while the sources disagree.
+		TestPrices::on_initialize(2);
+		assert_eq!(MockPoolOperationPauser::paused_operations().len(), 2);
+	});
+}
+
+#[test]
+fn pool_should_be_resumed_when_sources_agree() {
+	ExtBuilder::default().build().execute_with(|| {
+		// The redeem operation is paused before the deviation.
+		MockPoolOperationPauser::pause_operation(DOT, Operation::Redeem);
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(60)));
+		let guard = oracle_guard(PriceAggregation::Median, DeviationAction::PausePool);
+		assert_ok!(TestPrices::set_oracle_guard(alice_origin(), DOT, Some(guard)));
+
+		TestPrices::on_initialize(1);
+		assert_eq!(
+			TestPrices::deviation_paused_operations_storage(DOT),
+			vec![Operation::Borrow]
+		);
+		assert_eq!(
+			MockPoolOperationPauser::paused_operations(),
+			vec![(DOT, Operation::Redeem), (DOT, Operation::Borrow)]
+		);
+
+		// The sources agree again, only the operations paused by the oracle guard are resumed.
+		MockSecondaryDataProvider::set_price(Some(Price::saturating_from_integer(40)));
+		TestPrices::on_initialize(2);
+		assert_eq!(TestPrices::deviated_prices_storage(DOT), None);
+		assert!(TestPrices::deviation_paused_operations_storage(DOT).is_empty());
+		assert_eq!(
+			MockPoolOperationPauser::paused_operations(),
+			vec![(DOT, Operation::Redeem)]
+		);
+		let expected_event = Event::TestPrices(crate::Event::PriceSourcesAgreed(DOT));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+#[test]
+fn set_twap_window_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TestPrices::set_twap_window(alice_origin(), DOT, Some(4)));
+		assert_eq!(TestPrices::twap_window_storage(DOT), Some(4));
+		let expected_event = Event::TestPrices(crate::Event::TwapWindowChanged(DOT, Some(4)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_ok!(TestPrices::set_twap_window(alice_origin(), DOT, None));
+		assert_eq!(TestPrices::twap_window_storage(DOT), None);
+
+		assert_noop!(TestPrices::set_twap_window(bob_origin(), DOT, Some(4)), BadOrigin);
+		assert_noop!(
+			TestPrices::set_twap_window(alice_origin(), MDOT, Some(4)),
+			Error::<TestRuntime>::NotValidUnderlyingAssetId
+		);
+		// The window can't be shorter than the observation period.
+		assert_noop!(
+			TestPrices::set_twap_window(alice_origin(), DOT, Some(1)),
+			Error::<TestRuntime>::NotValidTwapWindow
+		);
+		// The window can't be longer than the stored history: 2 blocks * (5 observations - 1).
+		assert_noop!(
+			TestPrices::set_twap_window(alice_origin(), DOT, Some(9)),
+			Error::<TestRuntime>::NotValidTwapWindow
+		);
+		assert_ok!(TestPrices::set_twap_window(alice_origin(), DOT, Some(8)));
+	});
+}
+
+#[test]
+fn twap_should_be_calculated_over_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Price 1 DOT = 40 USD during blocks 1 - 3, 1 DOT = 80 USD since block 4.
+		for block_number in 1..=6 {
+			if block_number == 4 {
+				LockedPriceStorage::<TestRuntime>::insert(DOT, Price::saturating_from_integer(80));
+			}
+			System::set_block_number(block_number);
+			TestPrices::on_initialize(block_number);
+		}
+		assert_eq!(
+			TestPrices::twap_observations_storage(DOT),
+			vec![
+				(2, Price::saturating_from_integer(40)),
+				(4, Price::saturating_from_integer(120)),
+				(6, Price::saturating_from_integer(280)),
+			]
+		);
+
+		assert_eq!(TestPrices::get_twap(DOT, 2), Some(Price::saturating_from_integer(80)));
+		assert_eq!(TestPrices::get_twap(DOT, 4), Some(Price::saturating_from_integer(60)));
+		// There is not enough price history.
+		assert_eq!(TestPrices::get_twap(DOT, 6), None);
+
+		// The spot price is used if the window isn't configured.
+		assert_eq!(TestPrices::get_underlying_twap_price(DOT), None);
+		assert_ok!(TestPrices::set_twap_window(alice_origin(), DOT, Some(4)));
+		assert_eq!(
+			TestPrices::get_underlying_twap_price(DOT),
+			Some(Price::saturating_from_integer(60))
+		);
+
+		// Only the last `MaxTwapObservations` snapshots are stored.
+		for block_number in 7..=20 {
+			System::set_block_number(block_number);
+			TestPrices::on_initialize(block_number);
+		}
+		assert_eq!(TestPrices::twap_observations_storage(DOT).len(), 5);
+		assert_eq!(TestPrices::twap_observations_storage(DOT)[0].0, 12);
+	});
+}
+
+#[test]
+fn twap_should_be_available_for_max_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		// The maximum window: 2 blocks * (5 observations - 1) = 8 blocks.
+		assert_ok!(TestPrices::set_twap_window(alice_origin(), DOT, Some(8)));
+		for block_number in 1..=30 {
+			System::set_block_number(block_number);
+			TestPrices::on_initialize(block_number);
+			// The price history covers the maximum window at every block once it is long enough.
+			if block_number >= 10 {
+				assert_eq!(
+					TestPrices::get_underlying_twap_price(DOT),
+					Some(Price::saturating_from_integer(40))
+				);
+			}
+		}
+	});
+}
+
+#[test]
+fn set_circuit_breaker_threshold_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let threshold = Rate::saturating_from_rational(1, 10);
+		assert_ok!(TestPrices::set_circuit_breaker_threshold(
+			alice_origin(),
+			DOT,
+			Some(threshold)
+		));
+		assert_eq!(TestPrices::circuit_breaker_threshold_storage(DOT), Some(threshold));
+		let expected_event = Event::TestPrices(crate::Event::CircuitBreakerThresholdChanged(DOT, Some(threshold)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		TestPrices::on_initialize(1);
+		assert_eq!(
+			TestPrices::previous_price_storage(DOT),
+			Some(Price::saturating_from_integer(40))
+		);
+		assert_ok!(TestPrices::set_circuit_breaker_threshold(alice_origin(), DOT, None));
+		assert_eq!(TestPrices::circuit_breaker_threshold_storage(DOT), None);
+		assert_eq!(TestPrices::previous_price_storage(DOT), None);
+
+		assert_noop!(
+			TestPrices::set_circuit_breaker_threshold(bob_origin(), DOT, Some(threshold)),
+			BadOrigin
+		);
+		assert_noop!(
+			TestPrices::set_circuit_breaker_threshold(alice_origin(), MDOT, Some(threshold)),
+			Error::<TestRuntime>::NotValidUnderlyingAssetId
+		);
+		assert_noop!(
+			TestPrices::set_circuit_breaker_threshold(alice_origin(), DOT, Some(Rate::zero())),
+			Error::<TestRuntime>::NotValidCircuitBreakerThreshold
+		);
+	});
+}
+
+#[test]
+fn circuit_breaker_should_lock_price_until_jump_is_confirmed() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TestPrices::set_circuit_breaker_threshold(
+			alice_origin(),
+			DOT,
+			Some(Rate::saturating_from_rational(1, 10))
+		));
+		TestPrices::on_initialize(1);
+
+		// The price moves within the threshold: 40 -> 43 USD.
+		MockDataProvider::set_price(DOT, Price::saturating_from_integer(43));
+		TestPrices::on_initialize(2);
+		assert_eq!(TestPrices::price_jump_storage(DOT), None);
+		assert_eq!(TestPrices::locked_price_storage(DOT), None);
+
+		// The price jumps beyond the threshold: 43 -> 60 USD.
+		MockDataProvider::set_price(DOT, Price::saturating_from_integer(60));
+		TestPrices::on_initialize(3);
+		assert_eq!(
+			TestPrices::price_jump_storage(DOT),
+			Some(Price::saturating_from_integer(60))
+		);
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(43))
+		);
+		assert_eq!(TestPrices::locked_price_expiry_storage(DOT), Some(11));
+		assert_eq!(
+			MockPoolOperationPauser::paused_operations(),
+			vec![(DOT, Operation::Borrow), (DOT, Operation::Redeem)]
+		);
+		let expected_event = Event::TestPrices(crate::Event::CircuitBreakerTripped(
+			DOT,
+			Price::saturating_from_integer(43),
+			Price::saturating_from_integer(60),
+		));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_noop!(TestPrices::confirm_price_jump(bob_origin(), DOT), BadOrigin);
+		assert_noop!(
+			TestPrices::confirm_price_jump(alice_origin(), BTC),
+			Error::<TestRuntime>::PriceJumpNotFound
+		);
+
+		assert_ok!(TestPrices::confirm_price_jump(alice_origin(), DOT));
+		assert_eq!(TestPrices::price_jump_storage(DOT), None);
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(60))
+		);
+		assert!(MockPoolOperationPauser::paused_operations().is_empty());
+		let expected_event = Event::TestPrices(crate::Event::PriceJumpConfirmed(
+			DOT,
+			Price::saturating_from_integer(60),
+		));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The confirmed price becomes the reference for the next block.
+		TestPrices::on_initialize(4);
+		assert_eq!(TestPrices::price_jump_storage(DOT), None);
+	});
+}
+
+#[test]
+fn circuit_breaker_should_keep_price_locked_when_jump_is_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TestPrices::set_circuit_breaker_threshold(
+			alice_origin(),
+			DOT,
+			Some(Rate::saturating_from_rational(1, 10))
+		));
+		TestPrices::on_initialize(1);
+
+		MockDataProvider::set_price(DOT, Price::saturating_from_integer(20));
+		TestPrices::on_initialize(2);
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(40))
+		);
+
+		assert_ok!(TestPrices::reject_price_jump(alice_origin(), DOT));
+		assert_eq!(TestPrices::price_jump_storage(DOT), None);
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(40))
+		);
+		// The previous price remains locked for `MaxLockDuration` blocks.
+		assert_eq!(
+			TestPrices::locked_price_expiry_storage(DOT),
+			Some(System::block_number() + 10)
+		);
+		assert!(MockPoolOperationPauser::paused_operations().is_empty());
+		assert_noop!(
+			TestPrices::reject_price_jump(alice_origin(), DOT),
+			Error::<TestRuntime>::PriceJumpNotFound
+		);
+
+		// The locked price isn't checked, the pool isn't paused again.
+		TestPrices::on_initialize(3);
+		assert_eq!(TestPrices::price_jump_storage(DOT), None);
+		assert!(MockPoolOperationPauser::paused_operations().is_empty());
+	});
+}
+
+#[test]
+fn circuit_breaker_should_unlock_price_and_resume_pool_when_lock_expires() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TestPrices::set_circuit_breaker_threshold(
+			alice_origin(),
+			DOT,
+			Some(Rate::saturating_from_rational(1, 10))
+		));
+		TestPrices::on_initialize(1);
+
+		MockDataProvider::set_price(DOT, Price::saturating_from_integer(60));
+		TestPrices::on_initialize(2);
+		assert_eq!(TestPrices::locked_price_expiry_storage(DOT), Some(11));
+		assert_eq!(
+			MockPoolOperationPauser::paused_operations(),
+			vec![(DOT, Operation::Borrow), (DOT, Operation::Redeem)]
+		);
+
+		// No governance decision before `MaxLockDuration` blocks pass.
+		TestPrices::on_initialize(10);
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(40))
+		);
+
+		TestPrices::on_initialize(11);
+		assert_eq!(TestPrices::price_jump_storage(DOT), None);
+		assert_eq!(TestPrices::locked_price_storage(DOT), None);
+		assert_eq!(
+			TestPrices::get_underlying_price(DOT),
+			Some(Price::saturating_from_integer(60))
+		);
+		assert!(MockPoolOperationPauser::paused_operations().is_empty());
+		let expected_event = Event::TestPrices(crate::Event::PriceJumpExpired(DOT, Price::saturating_from_integer(60)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The circuit breaker watches the price moves again.
+		MockDataProvider::set_price(DOT, Price::saturating_from_integer(30));
+		TestPrices::on_initialize(12);
+		assert_eq!(
+			TestPrices::price_jump_storage(DOT),
+			Some(Price::saturating_from_integer(30))
+		);
+	});
+}
+
+#[test]
+fn circuit_breaker_should_resume_only_operations_paused_by_it() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TestPrices::set_circuit_breaker_threshold(
+			alice_origin(),
+			DOT,
+			Some(Rate::saturating_from_rational(1, 10))
+		));
+		TestPrices::on_initialize(1);
+		// The redeem operation is paused before the price jump.
+		MockPoolOperationPauser::pause_operation(DOT, Operation::Redeem);
+
+		MockDataProvider::set_price(DOT, Price::saturating_from_integer(60));
+		TestPrices::on_initialize(2);
+		assert_eq!(
+			TestPrices::circuit_breaker_paused_operations_storage(DOT),
+			vec![Operation::Borrow]
+		);
+		assert_eq!(
+			MockPoolOperationPauser::paused_operations(),
+			vec![(DOT, Operation::Redeem), (DOT, Operation::Borrow)]
+		);
+
+		assert_ok!(TestPrices::confirm_price_jump(alice_origin(), DOT));
+		assert!(TestPrices::circuit_breaker_paused_operations_storage(DOT).is_empty());
+		assert_eq!(
+			MockPoolOperationPauser::paused_operations(),
+			vec![(DOT, Operation::Redeem)]
+		);
+	});
+}
+
+#[test]
+fn migrate_to_v1_should_set_expiry_of_locked_prices() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::<TestRuntime>::put(Releases::V0);
+		LockedPriceStorage::<TestRuntime>::insert(DOT, Price::saturating_from_integer(41));
+		assert_ok!(TestPrices::lock_price(alice_origin(), BTC, Some(5)));
+
+		assert!(crate::migrations::migrate_to_v1::<TestRuntime>() > 0);
+		assert_eq!(TestPrices::locked_price_expiry_storage(DOT), Some(11));
+		// The expiry of the locks with one is kept.
+		assert_eq!(TestPrices::locked_price_expiry_storage(BTC), Some(5));
+		assert_eq!(StorageVersion::<TestRuntime>::get(), Releases::V1);
+
+		// The migration is applied only once.
+		assert_eq!(crate::migrations::migrate_to_v1::<TestRuntime>(), 0);
+	});
+}
+
+#[test]
+fn price_history_should_be_recorded_when_price_changes() {
+	ExtBuilder::default().build().execute_with(|| {
+		let record = |block_number, source, price| PriceRecord {
+			block_number,
+			source,
+			price: Price::saturating_from_integer(price),
+		};
+
+		TestPrices::on_initialize(1);
+		// The price hasn't changed, the record isn't added.
+		TestPrices::on_initialize(2);
+		MockDataProvider::set_price(DOT, Price::saturating_from_integer(42));
+		TestPrices::on_initialize(3);
+		LockedPriceStorage::<TestRuntime>::insert(DOT, Price::saturating_from_integer(50));
+		TestPrices::on_initialize(5);
+		assert_eq!(TestPrices::price_history_bounds_storage(DOT), (0, 3));
+
+		assert_eq!(TestPrices::get_price_at(DOT, 0), None);
+		assert_eq!(
+			TestPrices::get_price_at(DOT, 2),
+			Some(record(1, PriceSourceKind::Oracle, 40))
+		);
+		assert_eq!(
+			TestPrices::get_price_at(DOT, 4),
+			Some(record(3, PriceSourceKind::Oracle, 42))
+		);
+		assert_eq!(
+			TestPrices::get_price_at(DOT, 100),
+			Some(record(5, PriceSourceKind::Locked, 50))
+		);
+
+		assert_eq!(
+			TestPrices::get_price_history(DOT, 2, 4),
+			vec![
+				record(1, PriceSourceKind::Oracle, 40),
+				record(3, PriceSourceKind::Oracle, 42)
+			]
+		);
+		assert_eq!(
+			TestPrices::get_price_history(DOT, 3, 10),
+			vec![
+				record(3, PriceSourceKind::Oracle, 42),
+				record(5, PriceSourceKind::Locked, 50)
+			]
+		);
+
+		// Only the last `MaxPriceHistoryLength` records are stored.
+		LockedPriceStorage::<TestRuntime>::remove(DOT);
+		TestPrices::on_initialize(7);
+		assert_eq!(TestPrices::price_history_bounds_storage(DOT), (1, 4));
+		assert_eq!(TestPrices::price_history_storage(DOT, 0), None);
+		assert_eq!(TestPrices::get_price_at(DOT, 2), None);
+		assert_eq!(
+			TestPrices::get_price_history(DOT, 0, 10),
+			vec![
+				record(3, PriceSourceKind::Oracle, 42),
+				record(5, PriceSourceKind::Locked, 50),
+				record(7, PriceSourceKind::Oracle, 42)
+			]
+		);
+	});
+}
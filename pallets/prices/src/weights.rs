@@ -45,33 +45,82 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for module_prices.
 pub trait WeightInfo {
+	fn confirm_price_jump() -> Weight;
 	fn lock_price() -> Weight;
+	fn reject_price_jump() -> Weight;
+	fn set_circuit_breaker_threshold() -> Weight;
+	fn set_oracle_guard() -> Weight;
+	fn set_twap_window() -> Weight;
 	fn unlock_price() -> Weight;
 }
 
 /// Weights for module_prices using the Minterest node and recommended hardware.
 pub struct MinterestWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
+	fn confirm_price_jump() -> Weight {
+		(61_208_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
 	fn lock_price() -> Weight {
 		(98_455_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(11 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
-	fn unlock_price() -> Weight {
-		(26_548_000 as Weight)
+	fn reject_price_jump() -> Weight {
+		(52_847_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn set_circuit_breaker_threshold() -> Weight {
+		(25_301_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_oracle_guard() -> Weight {
+		(24_761_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_twap_window() -> Weight {
+		(23_914_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn unlock_price() -> Weight {
+		(28_113_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	fn confirm_price_jump() -> Weight {
+		(61_208_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
 	fn lock_price() -> Weight {
 		(98_455_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
-	fn unlock_price() -> Weight {
-		(26_548_000 as Weight)
+	fn reject_price_jump() -> Weight {
+		(52_847_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn set_circuit_breaker_threshold() -> Weight {
+		(25_301_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_oracle_guard() -> Weight {
+		(24_761_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_twap_window() -> Weight {
+		(23_914_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unlock_price() -> Weight {
+		(28_113_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
@@ -51,13 +51,14 @@ use frame_system::{
 pub use liquidation::*;
 use liquidity_pools::PoolData;
 use minterest_primitives::{
-	currency::CurrencyType::UnderlyingAsset, Balance, CurrencyId, OffchainErr, Operation, Rate,
+	currency::CurrencyType::UnderlyingAsset, Balance, CurrencyId, OffchainErr, Operation, Price, Rate,
 };
 pub use module::*;
 use orml_traits::MultiCurrency;
 use pallet_traits::{
-	ControllerManager, CurrencyConverter, LiquidityPoolStorageProvider, MinterestProtocolManager, PoolsManager,
-	PricesManager, RiskManagerStorageProvider, UserCollateral, UserLiquidationAttemptsManager,
+	Borrowing, ControllerManager, CurrencyConverter, LiquidationPoolsManager, LiquidityPoolStorageProvider,
+	MinterestProtocolManager, PoolsManager, PriceSource, RiskManagerStorageProvider, UserCollateral,
+	UserLiquidationAttemptsManager,
 };
 use sp_runtime::{
 	traits::{CheckedAdd, CheckedMul, One, StaticLookup, Zero},
@@ -89,7 +90,7 @@ pub mod module {
 		type UnsignedPriority: Get<TransactionPriority>;
 
 		/// The price source of currencies
-		type PriceSource: PricesManager<CurrencyId>;
+		type PriceSource: PriceSource<CurrencyId>;
 
 		/// Provides functionality for working with a user's collateral pools.
 		type UserCollateral: UserCollateral<Self::AccountId>;
@@ -119,10 +120,11 @@ pub mod module {
 		type LiquidityPoolsManager: LiquidityPoolStorageProvider<Self::AccountId, PoolData>
 			+ CurrencyConverter
 			+ UserCollateral<Self::AccountId>
-			+ PoolsManager<Self::AccountId>;
+			+ PoolsManager<Self::AccountId>
+			+ Borrowing<Self::AccountId>;
 
 		/// Provides the basic liquidation pools functionality.
-		type LiquidationPoolsManager: PoolsManager<Self::AccountId>;
+		type LiquidationPoolsManager: LiquidationPoolsManager<Self::AccountId>;
 
 		/// Provides the basic minterest protocol functionality.
 		type MinterestProtocolManager: MinterestProtocolManager<Self::AccountId>;
@@ -132,6 +134,10 @@ pub mod module {
 
 		/// The `MultiCurrency` implementation.
 		type MultiCurrency: MultiCurrency<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
+		#[pallet::constant]
+		/// The number of blocks during which the prices used by a liquidation are stored.
+		type LiquidationPricesRetention: Get<Self::BlockNumber>;
 	}
 
 	#[pallet::error]
@@ -204,6 +210,26 @@ pub mod module {
 	pub(crate) type UserLiquidationAttemptsStorage<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, u8, ValueQuery>;
 
+	/// The prices of the pools used by the liquidations: block number -> borrower -> prices.
+	/// A borrower is liquidated at most once per block. The prices are removed after
+	/// `LiquidationPricesRetention` blocks.
+	///
+	/// Storage location:
+	/// [`MNT Storage`](?search=risk_manager::module::Pallet::liquidation_prices_storage)
+	#[doc(alias = "MNT Storage")]
+	#[doc(alias = "MNT risk_manager")]
+	#[pallet::storage]
+	#[pallet::getter(fn liquidation_prices_storage)]
+	pub(crate) type LiquidationPricesStorage<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Twox64Concat,
+		T::AccountId,
+		Vec<(CurrencyId, Price)>,
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub liquidation_fee: Vec<(CurrencyId, Rate)>,
@@ -238,6 +264,17 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Removes the prices of the liquidations performed `LiquidationPricesRetention` blocks
+		/// ago.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let retention = T::LiquidationPricesRetention::get();
+			if now <= retention {
+				return 0;
+			}
+			let removed_records = LiquidationPricesStorage::<T>::drain_prefix(now - retention).count() as Weight;
+			T::DbWeight::get().reads_writes(removed_records.saturating_add(1), removed_records)
+		}
+
 		/// Runs after every block. Offchain worker checks insolvent loans and
 		/// submit unsigned tx to trigger liquidation.
 		fn offchain_worker(now: T::BlockNumber) {
@@ -333,6 +370,7 @@ pub mod module {
 			ensure_none(origin)?;
 			let borrower = T::Lookup::lookup(borrower)?;
 			Self::do_liquidate(&borrower, user_loan_state.clone())?;
+			Self::record_liquidation_prices(&borrower, &user_loan_state)?;
 			Self::deposit_event(Event::LiquidateUnsafeLoan(
 				borrower,
 				user_loan_state.get_user_borrows_to_repay_underlying(),
@@ -470,18 +508,59 @@ impl<T: Config> Pallet<T> {
 	/// in underlying assets.
 	fn do_liquidate(borrower: &T::AccountId, user_loan_state: UserLoanState<T>) -> DispatchResult {
 		let liquidation_pool_account_id = T::LiquidationPoolsManager::pools_account_id();
+		let is_forgivable_liquidation =
+			user_loan_state.get_user_liquidation_mode() == Some(LiquidationMode::ForgivableComplete);
+		let supplies_to_pay_underlying = user_loan_state
+			.get_user_supplies_to_pay_underlying()
+			.unwrap_or_default();
 		// perform repay
 		user_loan_state
 			.get_user_borrows_to_repay_underlying()
 			.into_iter()
 			.try_for_each(|(pool_id, repay_underlying)| -> DispatchResult {
-				T::MinterestProtocolManager::do_repay(
-					&liquidation_pool_account_id,
-					&borrower,
-					pool_id,
-					repay_underlying,
-					false,
-				)?;
+				// Protocol-owned supply is redeemed if the liquidation pool balance is not enough.
+				T::LiquidationPoolsManager::redeem_protocol_owned_liquidity(pool_id, repay_underlying)?;
+
+				// In case of the forgivable liquidation, the part of the borrow that cannot be
+				// covered by the liquidation pool is written off as bad debt. The balance paid from
+				// the liquidation pool at the end of the liquidation doesn't cover the borrow.
+				let (repay_underlying, bad_debt_underlying) = match is_forgivable_liquidation {
+					true => {
+						let pay_underlying: Balance = supplies_to_pay_underlying
+							.iter()
+							.filter(|(pay_pool_id, _)| *pay_pool_id == pool_id)
+							.map(|(_, pay_underlying)| *pay_underlying)
+							.sum();
+						let liquidation_pool_balance =
+							T::LiquidationPoolsManager::get_pool_available_liquidity(pool_id)
+								.saturating_sub(pay_underlying);
+						(
+							repay_underlying.min(liquidation_pool_balance),
+							repay_underlying.saturating_sub(liquidation_pool_balance),
+						)
+					}
+					false => (repay_underlying, Balance::zero()),
+				};
+
+				if !repay_underlying.is_zero() {
+					T::MinterestProtocolManager::do_repay(
+						&liquidation_pool_account_id,
+						&borrower,
+						pool_id,
+						repay_underlying,
+						false,
+					)?;
+				}
+				if !bad_debt_underlying.is_zero() {
+					let borrower_borrow_underlying =
+						T::ControllerManager::get_user_borrow_underlying_balance(&borrower, pool_id)?;
+					T::LiquidityPoolsManager::update_state_on_bad_debt(
+						&borrower,
+						pool_id,
+						bad_debt_underlying,
+						borrower_borrow_underlying,
+					)?;
+				}
 				Ok(())
 			})?;
 		// perform seize
@@ -494,18 +573,16 @@ impl<T: Config> Pallet<T> {
 			})?;
 
 		// perform pay in case of the forgivable liquidation
-		if let Some(supplies_to_pay_underlying) = user_loan_state.get_user_supplies_to_pay_underlying() {
-			supplies_to_pay_underlying
-				.into_iter()
-				.try_for_each(|(pool_id, pay_underlying)| -> DispatchResult {
-					T::MultiCurrency::transfer(
-						pool_id,
-						&liquidation_pool_account_id,
-						&T::LiquidityPoolsManager::pools_account_id(),
-						pay_underlying,
-					)
-				})?;
-		}
+		supplies_to_pay_underlying
+			.into_iter()
+			.try_for_each(|(pool_id, pay_underlying)| -> DispatchResult {
+				T::MultiCurrency::transfer(
+					pool_id,
+					&liquidation_pool_account_id,
+					&T::LiquidityPoolsManager::pools_account_id(),
+					pay_underlying,
+				)
+			})?;
 
 		<Self as UserLiquidationAttemptsManager<T::AccountId>>::try_mutate_attempts(
 			&borrower,
@@ -530,6 +607,50 @@ impl<T: Config> Pallet<T> {
 	fn user_liquidation_attempts_reset_to_zero(who: &T::AccountId) {
 		UserLiquidationAttemptsStorage::<T>::mutate(who, |p| *p = u8::zero())
 	}
+
+	/// Stores the prices of the pools whose borrows are repaid and whose supplies are seized or
+	/// paid by the liquidation.
+	///
+	/// - `borrower`: AccountId of the borrower whose loan is liquidated.
+	/// - `user_loan_state`: the liquidation amounts of the user's loan.
+	fn record_liquidation_prices(borrower: &T::AccountId, user_loan_state: &UserLoanState<T>) -> DispatchResult {
+		let mut pools: Vec<CurrencyId> = user_loan_state
+			.get_user_borrows_to_repay_underlying()
+			.into_iter()
+			.chain(user_loan_state.get_user_supplies_to_seize_underlying())
+			.chain(
+				user_loan_state
+					.get_user_supplies_to_pay_underlying()
+					.unwrap_or_default(),
+			)
+			.map(|(pool_id, _)| pool_id)
+			.collect();
+		pools.sort();
+		pools.dedup();
+
+		let prices = pools
+			.into_iter()
+			.map(|pool_id| {
+				let price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+				Ok((pool_id, price))
+			})
+			.collect::<Result<Vec<(CurrencyId, Price)>, DispatchError>>()?;
+		LiquidationPricesStorage::<T>::insert(<frame_system::Pallet<T>>::block_number(), borrower, prices);
+		Ok(())
+	}
+}
+
+/// RPC calls
+impl<T: Config> Pallet<T> {
+	/// Returns the prices of the pools used by the liquidation of the borrower's loan performed
+	/// at the block. Returns None if the borrower wasn't liquidated at the block or the prices
+	/// are already removed.
+	pub fn get_liquidation_prices(
+		block_number: T::BlockNumber,
+		borrower: &T::AccountId,
+	) -> Option<Vec<(CurrencyId, Price)>> {
+		Self::liquidation_prices_storage(block_number, borrower)
+	}
 }
 
 impl<T: Config> RiskManagerStorageProvider for Pallet<T> {
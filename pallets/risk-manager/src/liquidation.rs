@@ -182,6 +182,22 @@ impl<T: Config + Debug> UserLoanState<T> {
 		}
 	}
 
+	/// Sets the liquidation mode and the liquidation amounts of the user's loan. Used in tests
+	/// until the calculation of the liquidation amounts is implemented.
+	#[cfg(test)]
+	pub(crate) fn set_liquidation_amounts(
+		&mut self,
+		liquidation_mode: LiquidationMode,
+		borrows_to_repay_underlying: Vec<(CurrencyId, Balance)>,
+		supplies_to_seize_underlying: Vec<(CurrencyId, Balance)>,
+		supplies_to_pay_underlying: Option<Vec<(CurrencyId, Balance)>>,
+	) {
+		self.liquidation_mode = Some(liquidation_mode);
+		self.borrows_to_repay_underlying = borrows_to_repay_underlying;
+		self.supplies_to_seize_underlying = supplies_to_seize_underlying;
+		self.supplies_to_pay_underlying = supplies_to_pay_underlying;
+	}
+
 	/// Calculates the amount to be seized from user's supply (including liquidation fee).
 	/// Reads the liquidation fee value from storage.
 	///
@@ -1,7 +1,7 @@
 //! Tests for the risk-manager pallet.
 use super::*;
 use crate::LiquidationMode::{Complete, ForgivableComplete, Partial};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
 use minterest_primitives::Operation::{Deposit, Redeem, Repay};
 use mock::{Event, *};
 use sp_runtime::{traits::BadOrigin, FixedPointNumber};
@@ -445,3 +445,85 @@ fn partial_and_complete_liquidation() {
 			);
 		});
 }
+
+// Bob   supply: 1000 DOT - for liquidity in the DOT pool.
+// Alice supply: 100 ETH.
+// Alice borrow: 500 DOT.
+// Liquidation pool balance: 200 DOT.
+// 100 ETH are seized, 50 DOT are paid from the liquidation pool to the liquidity pool. The
+// liquidation pool covers the remaining 150 DOT of the repay, the other 350 DOT are written
+// off as bad debt.
+#[test]
+fn forgivable_liquidation_should_write_off_uncovered_borrow_as_bad_debt() {
+	ExtBuilder::default()
+		.deposit_underlying(BOB, DOT, dollars(1000))
+		.deposit_underlying(ALICE, ETH, dollars(100))
+		.borrow_underlying(ALICE, DOT, dollars(500))
+		.set_user_balance(LiquidationPoolAccountId::get(), DOT, dollars(200))
+		.merge_duplicates()
+		.build()
+		.execute_with(|| {
+			let mut alice_loan_state = UserLoanState::<TestRuntime>::new(&ALICE);
+			alice_loan_state.set_liquidation_amounts(
+				ForgivableComplete,
+				vec![(DOT, dollars(500))],
+				vec![(ETH, dollars(100))],
+				Some(vec![(DOT, dollars(50))]),
+			);
+
+			assert_ok!(TestRiskManager::do_liquidate(&ALICE, alice_loan_state));
+
+			assert_eq!(
+				Currencies::free_balance(DOT, &LiquidationPoolAccountId::get()),
+				Balance::zero()
+			);
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestPools::pools_account_id()),
+				dollars(700)
+			);
+			assert_eq!(
+				Currencies::free_balance(ETH, &LiquidationPoolAccountId::get()),
+				dollars(100)
+			);
+			assert_eq!(Currencies::free_balance(METH, &ALICE), Balance::zero());
+			assert_eq!(TestPools::pool_user_data_storage(DOT, ALICE).borrowed, Balance::zero());
+			assert_eq!(TestPools::pool_data_storage(DOT).borrowed, Balance::zero());
+			assert_eq!(TestPools::get_pool_bad_debt(DOT), dollars(350));
+			let expected_event = Event::TestPools(liquidity_pools::Event::BadDebtWrittenOff(
+				DOT,
+				dollars(350),
+				Balance::zero(),
+				dollars(350),
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+		});
+}
+
+#[test]
+fn liquidation_prices_should_be_recorded_and_removed_after_retention() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(10);
+		let mut alice_loan_state = UserLoanState::<TestRuntime>::new(&ALICE);
+		alice_loan_state.set_liquidation_amounts(
+			Partial,
+			vec![(DOT, dollars(100)), (ETH, dollars(50))],
+			vec![(ETH, dollars(160))],
+			None,
+		);
+
+		assert_ok!(TestRiskManager::record_liquidation_prices(&ALICE, &alice_loan_state));
+
+		assert_eq!(
+			TestRiskManager::get_liquidation_prices(10, &ALICE),
+			Some(vec![(DOT, Price::one()), (ETH, Price::one())])
+		);
+		assert_eq!(TestRiskManager::get_liquidation_prices(10, &BOB), None);
+		assert_eq!(TestRiskManager::get_liquidation_prices(11, &ALICE), None);
+
+		TestRiskManager::on_initialize(10 + LiquidationPricesRetention::get() - 1);
+		assert!(TestRiskManager::get_liquidation_prices(10, &ALICE).is_some());
+
+		TestRiskManager::on_initialize(10 + LiquidationPricesRetention::get());
+		assert_eq!(TestRiskManager::get_liquidation_prices(10, &ALICE), None);
+	});
+}
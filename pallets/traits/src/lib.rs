@@ -1,7 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::upper_case_acronyms)]
 
-use minterest_primitives::{Balance, CurrencyId, Interest, Operation, Price, Rate};
+use minterest_primitives::{Balance, CurrencyId, Interest, Operation, Price, Rate, TradingPair};
 use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::{collections::btree_set::BTreeSet, result::Result, vec::Vec};
 
@@ -22,6 +22,15 @@ pub trait Borrowing<AccountId> {
 		repay_amount: Balance,
 		account_borrows: Balance,
 	) -> DispatchResult;
+
+	/// Updates the state of the core as a consequence of writing off a part of the user loan
+	/// which cannot be covered during liquidation.
+	fn update_state_on_bad_debt(
+		who: &AccountId,
+		underlying_asset: CurrencyId,
+		bad_debt_amount: Balance,
+		account_borrows: Balance,
+	) -> DispatchResult;
 }
 
 /// An abstraction of pools basic functionalities.
@@ -50,6 +59,15 @@ pub trait LiquidityPoolStorageProvider<AccountId, PoolData> {
 	/// Get list of users with active loan positions for a particular pool.
 	fn get_pool_members_with_loan(underlying_asset: CurrencyId) -> Vec<AccountId>;
 
+	/// Get users with active loan positions for a particular pool among at most `limit` pool
+	/// members following the `start_after` member. Returns the users and the last visited member,
+	/// which is `None` when all pool members are visited.
+	fn get_pool_members_with_loan_page(
+		underlying_asset: CurrencyId,
+		start_after: Option<AccountId>,
+		limit: u32,
+	) -> (Vec<AccountId>, Option<AccountId>);
+
 	/// Gets total amount borrowed from the pool.
 	fn get_pool_borrow_underlying(pool_id: CurrencyId) -> Balance;
 
@@ -60,6 +78,9 @@ pub trait LiquidityPoolStorageProvider<AccountId, PoolData> {
 	/// Gets current total amount of protocol interest of the underlying held in this pool.
 	fn get_pool_protocol_interest(pool_id: CurrencyId) -> Balance;
 
+	/// Gets total amount of bad debt written off in the pool.
+	fn get_pool_bad_debt(pool_id: CurrencyId) -> Balance;
+
 	/// Check if pool exists.
 	fn pool_exists(underlying_asset: &CurrencyId) -> bool;
 
@@ -118,13 +139,39 @@ pub trait LiquidationPoolsManager<AccountId>: PoolsManager<AccountId> {
 	/// This is a part of a pool creation flow
 	/// Checks parameters validity and creates storage records for LiquidationPoolsData
 	fn create_pool(pool_id: CurrencyId, deviation_threshold: Rate, balance_ratio: Rate) -> DispatchResult;
+
+	/// Deposits a portion of the idle liquidation pool balance into the liquidity pool as
+	/// protocol-owned supply. Returns the deposited amount of the underlying asset.
+	///
+	/// - `pool_id`: the CurrencyId of the pool.
+	fn deposit_protocol_owned_liquidity(pool_id: CurrencyId) -> Result<Balance, DispatchError>;
+
+	/// Redeems protocol-owned supply back into the liquidation pool, so that the liquidation pool
+	/// balance covers `required_underlying` on top of the ideal balance defined by
+	/// `balance_ratio`. Returns the redeemed amount of the underlying asset.
+	///
+	/// - `pool_id`: the CurrencyId of the pool.
+	/// - `required_underlying`: the amount of the underlying asset required for liquidations.
+	fn redeem_protocol_owned_liquidity(
+		pool_id: CurrencyId,
+		required_underlying: Balance,
+	) -> Result<Balance, DispatchError>;
 }
 
-/// An abstraction of prices basic functionalities.
-pub trait PricesManager<CurrencyId> {
+/// An abstraction of reading prices.
+pub trait PriceSource<CurrencyId> {
 	/// Get price underlying token in USD.
 	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price>;
 
+	/// Get time-weighted average price underlying token in USD over the window configured for
+	/// the currency. Returns None if the window isn't configured or there isn't enough history.
+	fn get_underlying_twap_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+}
+
+/// An abstraction of locking prices.
+pub trait PriceLocker<CurrencyId> {
 	/// Locks price when get valid price from source.
 	fn lock_price(currency_id: CurrencyId);
 
@@ -132,18 +179,65 @@ pub trait PricesManager<CurrencyId> {
 	fn unlock_price(currency_id: CurrencyId);
 }
 
+/// An abstraction of pausing operations with the pools.
+pub trait PoolOperationPauser {
+	/// Pauses the operation with the pool.
+	fn pause_operation(pool_id: CurrencyId, operation: Operation);
+
+	/// Resumes the operation with the pool.
+	fn resume_operation(pool_id: CurrencyId, operation: Operation);
+
+	/// Checks if the operation with the pool is paused.
+	fn is_operation_paused(pool_id: CurrencyId, operation: Operation) -> bool;
+}
+
+impl PoolOperationPauser for () {
+	fn pause_operation(_pool_id: CurrencyId, _operation: Operation) {}
+
+	fn resume_operation(_pool_id: CurrencyId, _operation: Operation) {}
+
+	fn is_operation_paused(_pool_id: CurrencyId, _operation: Operation) -> bool {
+		false
+	}
+}
+
+/// Provides the liquidity shares of the DEX trading pairs.
+pub trait DexSharesProvider<AccountId> {
+	/// Total amount of liquidity shares issued for the trading pair.
+	fn get_total_shares(trading_pair: TradingPair) -> Balance;
+
+	/// Liquidity shares of the trading pair owned by the account.
+	fn get_shares(trading_pair: TradingPair, who: &AccountId) -> Balance;
+}
+
+impl<AccountId> DexSharesProvider<AccountId> for () {
+	fn get_total_shares(_trading_pair: TradingPair) -> Balance {
+		Balance::default()
+	}
+
+	fn get_shares(_trading_pair: TradingPair, _who: &AccountId) -> Balance {
+		Balance::default()
+	}
+}
+
 /// An abstraction of DEXs basic functionalities.
 pub trait DEXManager<AccountId, CurrencyId, Balance> {
-	//TODO: Add function description
+	/// Swaps the exact `supply_amount` of `supply_currency_id` for `target_currency_id`.
+	/// Fails if less than `min_target_amount` would be received.
+	///
+	/// Returns the received target amount.
 	fn swap_with_exact_supply(
 		who: &AccountId,
-		target_currency_id: CurrencyId,
 		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
 		supply_amount: Balance,
 		min_target_amount: Balance,
 	) -> Result<Balance, DispatchError>;
 
-	//TODO: Add function description
+	/// Swaps `supply_currency_id` for the exact `target_amount` of `target_currency_id`.
+	/// Fails if more than `max_supply_amount` would be spent.
+	///
+	/// Returns the spent supply amount.
 	fn swap_with_exact_target(
 		who: &AccountId,
 		supply_currency_id: CurrencyId,
@@ -151,6 +245,56 @@ pub trait DEXManager<AccountId, CurrencyId, Balance> {
 		max_supply_amount: Balance,
 		target_amount: Balance,
 	) -> Result<Balance, DispatchError>;
+
+	/// Swaps the exact `supply_amount` of the first currency of the `path` for the last one
+	/// through the intermediate currencies of the `path`.
+	/// Fails if less than `min_target_amount` would be received.
+	///
+	/// Returns the received target amount.
+	fn swap_with_exact_supply_by_path(
+		who: &AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		min_target_amount: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	/// Swaps the first currency of the `path` for the exact `target_amount` of the last one
+	/// through the intermediate currencies of the `path`.
+	/// Fails if more than `max_supply_amount` would be spent.
+	///
+	/// Returns the spent supply amount.
+	fn swap_with_exact_target_by_path(
+		who: &AccountId,
+		path: &[CurrencyId],
+		max_supply_amount: Balance,
+		target_amount: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	/// Finds the trading path with the greatest target amount for the exact supply amount.
+	/// The search goes through all the liquidity pools, so it is intended for off-chain use.
+	///
+	/// Returns: (path, target_amount)
+	fn get_best_path_for_exact_supply(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+	) -> Option<(Vec<CurrencyId>, Balance)>;
+
+	/// Finds the trading path with the least supply amount for the exact target amount.
+	/// The search goes through all the liquidity pools, so it is intended for off-chain use.
+	///
+	/// Returns: (path, supply_amount)
+	fn get_best_path_for_exact_target(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		target_amount: Balance,
+	) -> Option<(Vec<CurrencyId>, Balance)>;
+
+	/// Calculates the amount of the first currency of the `path` required to receive the exact
+	/// `target_amount` of the last one.
+	///
+	/// Returns `None` if the swap along the `path` isn't possible.
+	fn get_supply_amount_by_path(path: &[CurrencyId], target_amount: Balance) -> Option<Balance>;
 }
 
 /// An abstraction of controller basic functionalities.
@@ -234,6 +378,11 @@ pub trait ControllerManager<AccountId> {
 	///     `fresh_pool_protocol_interest_usd` - freshest value of protocol interest in the n pool;
 	fn get_protocol_total_values() -> Result<(Balance, Balance, Balance, Balance), DispatchError>;
 
+	/// Gets the data required to audit the pool solvency: (pool_supply_underlying,
+	/// pool_borrow_underlying, pool_protocol_interest, pool_bad_debt, exchange_rate).
+	/// All values are calculated for the current block.
+	fn get_pool_solvency_data(pool_id: CurrencyId) -> Option<(Balance, Balance, Balance, Balance, Rate)>;
+
 	/// Calculate user total collateral in usd based on collateral factor, fresh exchange rate and
 	/// latest oracle price. Collateral is calculated for the current block.
 	///
@@ -306,6 +455,24 @@ pub trait MntManager<AccountId> {
 		distribute_all: bool,
 	) -> Result<Balance, DispatchError>;
 
+	/// Update MNT distribution index for the liquidity providers of a DEX trading pair.
+	///
+	/// - `trading_pair`: The trading pair which index to update.
+	fn update_dex_pair_mnt_index(trading_pair: TradingPair) -> DispatchResult;
+
+	/// Distribute MNT token to liquidity provider. It should be called after
+	/// update_dex_pair_mnt_index and before the liquidity shares of the provider are changed.
+	///
+	/// - `trading_pair`: The trading pair in which the liquidity provider is acting;
+	/// - `provider`: The AccountId of the liquidity provider to distribute MNT to.
+	///
+	/// returns `provider_mnt_accrued`: - The MNT accrued but not yet transferred to each user
+	fn distribute_liquidity_provider_mnt(
+		trading_pair: TradingPair,
+		provider: &AccountId,
+		distribute_all: bool,
+	) -> Result<Balance, DispatchError>;
+
 	/// Return MNT Borrow Rate and MNT Supply Rate values per block for current pool.
 	/// - `pool_id` - the pool to calculate rates
 	///
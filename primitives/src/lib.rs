@@ -98,6 +98,46 @@ pub enum DataProviderId {
 	Minterest = 1,
 }
 
+/// The way the price used by the protocol is obtained.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PriceSourceKind {
+	/// The price is provided by the oracle.
+	Oracle,
+	/// The price is cross-checked with the secondary source by the oracle guard.
+	Guarded,
+	/// The price is locked.
+	Locked,
+}
+
+/// A pair of currencies traded on the DEX. The currencies are kept sorted, so that (A, B) and
+/// (B, A) refer to the same liquidity pool.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TradingPair(CurrencyId, CurrencyId);
+
+impl TradingPair {
+	/// Creates a trading pair from two different currencies. Returns `None` if the currencies
+	/// are the same.
+	pub fn from_currency_ids(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Option<Self> {
+		match currency_id_a.cmp(&currency_id_b) {
+			sp_std::cmp::Ordering::Less => Some(TradingPair(currency_id_a, currency_id_b)),
+			sp_std::cmp::Ordering::Greater => Some(TradingPair(currency_id_b, currency_id_a)),
+			sp_std::cmp::Ordering::Equal => None,
+		}
+	}
+
+	/// The lesser currency of the pair.
+	pub fn first(&self) -> CurrencyId {
+		self.0
+	}
+
+	/// The greater currency of the pair.
+	pub fn second(&self) -> CurrencyId {
+		self.1
+	}
+}
+
 /// Error which may occur while executing the off-chain code.
 #[derive(PartialEq, Eq)]
 pub enum OffchainErr {
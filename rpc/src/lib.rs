@@ -32,13 +32,17 @@ where
 		minterest_parachain_runtime::TimeStampedPrice,
 	>,
 	C::Api: controller_rpc::ControllerRuntimeApi<Block, AccountId>,
-	C::Api: prices_rpc::PricesRuntimeApi<Block>,
+	C::Api: dex_rpc::DexRuntimeApi<Block>,
+	C::Api: liquidation_pools_rpc::LiquidationPoolsRuntimeApi<Block>,
+	C::Api: prices_rpc::PricesRuntimeApi<Block, AccountId>,
 	C::Api: mnt_token_rpc::MntTokenRuntimeApi<Block, AccountId>,
 	C::Api: whitelist_rpc::WhitelistRuntimeApi<Block, AccountId>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
 	use controller_rpc::{ControllerRpcApi, ControllerRpcImpl};
+	use dex_rpc::{DexRpcApi, DexRpcImpl};
+	use liquidation_pools_rpc::{LiquidationPoolsRpcApi, LiquidationPoolsRpcImpl};
 	use mnt_token_rpc::{MntTokenRpcApi, MntTokenRpcImpl};
 	use orml_oracle_rpc::{Oracle, OracleApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
@@ -71,7 +75,13 @@ where
 
 	io.extend_with(PricesRpcApi::to_delegate(PricesRpcImpl::new(client.clone())));
 
-	io.extend_with(WhitelistRpcApi::to_delegate(WhitelistRpcImpl::new(client)));
+	io.extend_with(WhitelistRpcApi::to_delegate(WhitelistRpcImpl::new(client.clone())));
+
+	io.extend_with(DexRpcApi::to_delegate(DexRpcImpl::new(client.clone())));
+
+	io.extend_with(LiquidationPoolsRpcApi::to_delegate(LiquidationPoolsRpcImpl::new(
+		client,
+	)));
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
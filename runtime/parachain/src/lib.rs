@@ -12,9 +12,15 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 mod weights;
 
 pub use controller_rpc_runtime_api::{
-	BalanceInfo, HypotheticalLiquidityData, PoolState, ProtocolTotalValue, UserData, UserPoolBalanceData,
+	BalanceInfo, HypotheticalLiquidityData, PoolCheckpointData, PoolSolvencyData, PoolState, ProtocolTotalValue,
+	UserData, UserPoolBalanceData, WrappedBalanceData,
 };
+pub use dex_rpc_runtime_api::DexBalanceInfo;
 use frame_system::{EnsureOneOf, EnsureRoot};
+use liquidation_pools::SaleStatus;
+pub use liquidation_pools_rpc_runtime_api::{
+	BalancingPlan, BalancingSale, BalancingSaleStatus, LiquidationPoolBalancingInfo,
+};
 use minterest_primitives::constants::fee::WeightToFee;
 pub use minterest_primitives::{
 	constants::{
@@ -23,7 +29,7 @@ pub use minterest_primitives::{
 			MAX_LIQUIDATION_FEE, PARTIAL_LIQUIDATION_MAX_ATTEMPTS, PARTIAL_LIQUIDATION_MIN_SUM,
 			RISK_MANAGER_WORKER_MAX_DURATION_MS,
 		},
-		time::{BLOCKS_PER_YEAR, DAYS, SLOT_DURATION},
+		time::{BLOCKS_PER_YEAR, DAYS, HOURS, MINUTES, SLOT_DURATION},
 		INITIAL_EXCHANGE_RATE, MAX_BORROW_CAP, PROTOCOL_INTEREST_TRANSFER_THRESHOLD, TOTAL_ALLOCATION,
 	},
 	currency::{
@@ -31,12 +37,13 @@ pub use minterest_primitives::{
 		BTC, DOT, ETH, KSM, MBTC, MDOT, METH, MKSM, MNT,
 	},
 	AccountId, AccountIndex, Amount, Balance, BlockNumber, ChainlinkFeedId, ChainlinkPriceValue, CurrencyId,
-	DataProviderId, DigestItem, Hash, Index, Interest, Moment, Operation, Price, Rate, Signature, VestingBucket,
+	DataProviderId, DigestItem, Hash, Index, Interest, Moment, Operation, Price, PriceSourceKind, Rate, Signature,
+	VestingBucket,
 };
 pub use mnt_token_rpc_runtime_api::MntBalanceInfo;
 use orml_currencies::BasicCurrencyAdapter;
 use orml_traits::{create_median_value_data_provider, parameter_type_with_key, DataFeeder, DataProviderExtended};
-use pallet_traits::{ControllerManager, LiquidityPoolStorageProvider, MntManager, PricesManager, WhitelistManager};
+use pallet_traits::{ControllerManager, LiquidityPoolStorageProvider, MntManager, PriceSource, WhitelistManager};
 use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -400,6 +407,7 @@ parameter_types! {
 }
 
 impl liquidity_pools::Config for Runtime {
+	type Event = Event;
 	type MultiCurrency = Currencies;
 	type PriceSource = Prices;
 	type PalletId = LiquidityPoolsPalletId;
@@ -411,6 +419,9 @@ impl liquidity_pools::Config for Runtime {
 
 parameter_types! {
 	pub const MaxBorrowCap: Balance = MAX_BORROW_CAP;
+	// Hourly checkpoints for the last 90 days.
+	pub const PoolHistoryCheckpointInterval: BlockNumber = HOURS;
+	pub const PoolHistoryMaxLength: u32 = 90 * 24;
 }
 
 impl controller::Config for Runtime {
@@ -423,12 +434,28 @@ impl controller::Config for Runtime {
 	type UpdateOrigin = EnsureRootOrHalfMinterestCouncil;
 	type ControllerWeightInfo = weights::controller::WeightInfo<Runtime>;
 	type MntManager = MntToken;
+	type PoolHistoryCheckpointInterval = PoolHistoryCheckpointInterval;
+	type PoolHistoryMaxLength = PoolHistoryMaxLength;
+}
+
+parameter_types! {
+	pub const TwapObservationPeriod: BlockNumber = 10;
+	pub const MaxTwapObservations: u32 = 60;
+	pub const MaxPriceHistoryLength: u32 = 100_000;
+	pub const MaxLockDuration: BlockNumber = 7 * DAYS;
 }
 
 impl module_prices::Config for Runtime {
 	type Event = Event;
 	type Source = AggregatedDataProvider;
+	type SecondarySource = ChainlinkPriceManager;
+	type PoolOperationPauser = Controller;
 	type LockOrigin = EnsureRootOrTwoThirdsMinterestCouncil;
+	type UpdateOrigin = EnsureRootOrHalfMinterestCouncil;
+	type TwapObservationPeriod = TwapObservationPeriod;
+	type MaxTwapObservations = MaxTwapObservations;
+	type MaxPriceHistoryLength = MaxPriceHistoryLength;
+	type MaxLockDuration = MaxLockDuration;
 	type WeightInfo = weights::prices::WeightInfo<Runtime>;
 }
 
@@ -446,10 +473,18 @@ impl minterest_model::Config for Runtime {
 parameter_types! {
 	pub const RiskManagerPriority: TransactionPriority = TransactionPriority::max_value();
 	pub const LiquidityPoolsPriority: TransactionPriority = TransactionPriority::max_value() - 1;
+	pub const LiquidationPoolsBalancingBackoffPeriod: BlockNumber = 10 * MINUTES;
+	pub const LiquidationPoolsAuctionDuration: BlockNumber = HOURS;
+	pub const MaxTreasuryWithdrawalPerCall: Balance = 100_000 * DOLLARS;
+	pub const MaxTreasuryWithdrawalPerPeriod: Balance = 500_000 * DOLLARS;
+	pub const TreasuryWithdrawalPeriod: BlockNumber = 7 * DAYS;
+	pub const AtRiskDebtAccountsPerBlock: u32 = 100;
+	pub const LiquidationPoolsDefaultMaxSlippage: Rate = Rate::from_inner(50_000_000_000_000_000); // 5%
 	pub const PartialLiquidationMinSum: Balance = PARTIAL_LIQUIDATION_MIN_SUM;
 	pub const PartialLiquidationMaxAttempts: u8 = PARTIAL_LIQUIDATION_MAX_ATTEMPTS;
 	pub const MaxLiquidationFee: Rate = MAX_LIQUIDATION_FEE;
 	pub const RiskManagerWorkerMaxDurationMs: u64 = RISK_MANAGER_WORKER_MAX_DURATION_MS;
+	pub const LiquidationPricesRetention: BlockNumber = 30 * DAYS;
 }
 
 impl risk_manager::Config for Runtime {
@@ -467,6 +502,7 @@ impl risk_manager::Config for Runtime {
 	type MinterestProtocolManager = MinterestProtocol;
 	type OffchainWorkerMaxDurationMs = RiskManagerWorkerMaxDurationMs;
 	type MultiCurrency = Currencies;
+	type LiquidationPricesRetention = LiquidationPricesRetention;
 }
 
 parameter_types! {
@@ -480,6 +516,7 @@ impl mnt_token::Config for Runtime {
 	type LiquidityPoolsManager = LiquidityPools;
 	type MultiCurrency = Currencies;
 	type ControllerManager = Controller;
+	type DexSharesProvider = Dex;
 	type MntTokenAccountId = MntTokenAccountId;
 	type MntTokenWeightInfo = weights::mnt_token::WeightInfo<Runtime>;
 }
@@ -508,6 +545,15 @@ impl liquidation_pools::Config for Runtime {
 	type Dex = Dex;
 	type LiquidationPoolsWeightInfo = weights::liquidation_pools::WeightInfo<Runtime>;
 	type ControllerManager = Controller;
+	type MntManager = MntToken;
+	type BalancingBackoffPeriod = LiquidationPoolsBalancingBackoffPeriod;
+	type AuctionDuration = LiquidationPoolsAuctionDuration;
+	type MaxTreasuryWithdrawalPerCall = MaxTreasuryWithdrawalPerCall;
+	type MaxTreasuryWithdrawalPerPeriod = MaxTreasuryWithdrawalPerPeriod;
+	type TreasuryWithdrawalPeriod = TreasuryWithdrawalPeriod;
+	type AtRiskDebtAccountsPerBlock = AtRiskDebtAccountsPerBlock;
+	type DefaultMaxSlippage = LiquidationPoolsDefaultMaxSlippage;
+	type TradingPathLimit = DexTradingPathLimit;
 }
 
 parameter_types! {
@@ -548,6 +594,10 @@ impl DataFeeder<CurrencyId, Price, AccountId> for AggregatedDataProvider {
 
 parameter_types! {
 	pub DexAccountId: AccountId = DexPalletId::get().into_account();
+	pub const DexSwapFee: Rate = Rate::from_inner(3_000_000_000_000_000); // 0.3%
+	pub const DexTradingPathLimit: u32 = 3;
+	pub const DexMaxClaimTradingPairs: u32 = 10;
+	pub const DexPriceAveragingPeriod: BlockNumber = HOURS;
 }
 
 impl dex::Config for Runtime {
@@ -555,6 +605,12 @@ impl dex::Config for Runtime {
 	type MultiCurrency = Currencies;
 	type DexPalletId = DexPalletId;
 	type DexAccountId = DexAccountId;
+	type TradingPathLimit = DexTradingPathLimit;
+	type MaxClaimTradingPairs = DexMaxClaimTradingPairs;
+	type SwapFee = DexSwapFee;
+	type PriceAveragingPeriod = DexPriceAveragingPeriod;
+	type MntManager = MntToken;
+	type DexWeightInfo = weights::dex::WeightInfo<Runtime>;
 }
 
 parameter_types! {
@@ -586,6 +642,8 @@ impl whitelist_module::Config for Runtime {
 
 parameter_types! {
 	pub const ChainlinkManagerPriority: TransactionPriority = TransactionPriority::max_value() - 2;
+	pub const DefaultRoundInitiationPeriod: BlockNumber = 3;
+	pub const RoundAnswerTimeout: BlockNumber = MINUTES;
 	pub ChainlinkPriceManagerAccountId: AccountId = ChainlinkPriceManagerPalletId::get().into_account();
 }
 
@@ -593,6 +651,9 @@ impl chainlink_price_manager::Config for Runtime {
 	type Event = Event;
 	type PalletAccountId = ChainlinkPriceManagerAccountId;
 	type UnsignedPriority = ChainlinkManagerPriority;
+	type UpdateOrigin = EnsureRootOrHalfMinterestCouncil;
+	type DefaultRoundInitiationPeriod = DefaultRoundInitiationPeriod;
+	type RoundAnswerTimeout = RoundAnswerTimeout;
 	type ChainlinkPriceManagerWeightInfo = weights::chainlink_price_manager::WeightInfo<Runtime>;
 }
 
@@ -656,11 +717,11 @@ construct_runtime!(
 
 		// ChainLink
 		ChainlinkFeed: pallet_chainlink_feed::{Pallet, Call, Config<T>, Storage, Event<T>},
-		ChainlinkPriceManager: chainlink_price_manager::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
+		ChainlinkPriceManager: chainlink_price_manager::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
 
 		// Minterest pallets
 		MinterestProtocol: minterest_protocol::{Pallet, Call, Event<T>},
-		LiquidityPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+		LiquidityPools: liquidity_pools::{Pallet, Storage, Call, Event, Config<T>},
 		Controller: controller::{Pallet, Storage, Call, Event, Config<T>},
 		MinterestModel: minterest_model::{Pallet, Storage, Call, Event, Config<T>},
 		RiskManager: risk_manager::{Pallet, Storage, Call, Event<T>, Config<T>, ValidateUnsigned},
@@ -819,6 +880,28 @@ impl_runtime_apis! {
 			Some(PoolState { exchange_rate, borrow_rate, supply_rate })
 		}
 
+		fn get_pool_solvency_data(pool_id: CurrencyId) -> Option<PoolSolvencyData> {
+			let (pool_supply_underlying, pool_borrow_underlying, pool_protocol_interest, pool_bad_debt, exchange_rate) =
+				Controller::get_pool_solvency_data(pool_id)?;
+			Some(PoolSolvencyData { pool_supply_underlying, pool_borrow_underlying, pool_protocol_interest, pool_bad_debt, exchange_rate })
+		}
+
+		fn get_pool_history(pool_id: CurrencyId, from: BlockNumber, to: BlockNumber) -> Option<Vec<PoolCheckpointData>> {
+			if !LiquidityPools::pool_exists(&pool_id) {
+				return None;
+			}
+			Some(
+				Controller::get_pool_history(pool_id, from, to)
+					.into_iter()
+					.map(|checkpoint| PoolCheckpointData {
+						block_number: checkpoint.block_number,
+						exchange_rate: checkpoint.exchange_rate,
+						borrow_index: checkpoint.borrow_index,
+					})
+					.collect(),
+			)
+		}
+
 		fn get_pool_utilization_rate(pool_id: CurrencyId) -> Option<Rate> {
 			Controller::get_pool_utilization_rate(pool_id)
 		}
@@ -858,6 +941,20 @@ impl_runtime_apis! {
 				Some(BalanceInfo{amount: Controller::get_user_supply_underlying_balance(&account_id, pool_id).ok()?})
 		}
 
+		fn get_user_wrapped_balances(account_id: AccountId) -> Option<Vec<WrappedBalanceData>> {
+			Some(
+				liquidity_pools::RebasingBalanceView::<Runtime>::get_user_wrapped_balances(&account_id)
+					.ok()?
+					.into_iter()
+					.map(|(currency_id, wrapped_amount, underlying_amount)| WrappedBalanceData {
+						currency_id,
+						wrapped_amount,
+						underlying_amount,
+					})
+					.collect(),
+			)
+		}
+
 		fn pool_exists(underlying_asset_id: CurrencyId) -> bool {
 			LiquidityPools::pool_exists(&underlying_asset_id)
 		}
@@ -881,6 +978,52 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl dex_rpc_runtime_api::DexRuntimeApi<Block> for Runtime {
+		fn get_best_route(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+		) -> Option<(Vec<CurrencyId>, DexBalanceInfo)> {
+			let (path, target_amount) = Dex::get_best_path_for_exact_supply(supply_currency_id, target_currency_id, supply_amount)?;
+			Some((path, DexBalanceInfo{amount: target_amount}))
+		}
+	}
+
+	impl liquidation_pools_rpc_runtime_api::LiquidationPoolsRuntimeApi<Block> for Runtime {
+		fn get_balancing_plan() -> Option<BalancingPlan> {
+			let (pools, sales) = LiquidationPools::get_balancing_plan().ok()?;
+			Some(BalancingPlan {
+				pools: pools
+					.into_iter()
+					.map(|pool| LiquidationPoolBalancingInfo {
+						pool_id: pool.pool_id,
+						balance_usd: pool.balance_usd,
+						ideal_balance_usd: pool.ideal_balance_usd,
+						oversupply_usd: pool.oversupply_usd,
+						shortfall_usd: pool.shortfall_usd,
+						left_border_usd: pool.left_border_usd,
+						right_border_usd: pool.right_border_usd,
+					})
+					.collect(),
+				sales: sales
+					.into_iter()
+					.map(|(sale, status)| BalancingSale {
+						supply_pool_id: sale.supply_pool_id,
+						target_pool_id: sale.target_pool_id,
+						amount_usd: sale.amount_usd,
+						status: match status {
+							SaleStatus::Ready => BalancingSaleStatus::Ready,
+							SaleStatus::Postponed => BalancingSaleStatus::Postponed,
+							SaleStatus::AuctionInProgress => BalancingSaleStatus::AuctionInProgress,
+							SaleStatus::TradingPathNotFound => BalancingSaleStatus::TradingPathNotFound,
+							SaleStatus::ExcessiveSlippage => BalancingSaleStatus::ExcessiveSlippage,
+						},
+					})
+					.collect(),
+			})
+		}
+	}
+
 	impl whitelist_rpc_runtime_api::WhitelistRuntimeApi<Block, AccountId> for Runtime {
 		fn is_whitelist_member(who: AccountId) -> bool {
 				Whitelist::is_whitelist_member(&who)
@@ -908,21 +1051,33 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl prices_rpc_runtime_api::PricesRuntimeApi<Block> for Runtime {
+	impl prices_rpc_runtime_api::PricesRuntimeApi<Block, AccountId> for Runtime {
 		fn  get_current_price(currency_id: CurrencyId) -> Option<Price> {
 			Prices::get_underlying_price(currency_id)
 		}
 
-		fn  get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>)> {
-			CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
-				.into_iter()
-				.map(|currency_id| (currency_id, Prices::locked_price_storage(currency_id)))
-				.collect()
+		fn  get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>, Option<BlockNumber>)> {
+			Prices::get_all_locked_prices()
 		}
 
 		fn get_all_freshest_prices() -> Vec<(CurrencyId, Option<Price>)> {
 			Prices::get_all_freshest_prices()
 		}
+
+		fn get_price_history(
+			currency_id: CurrencyId,
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Vec<(BlockNumber, PriceSourceKind, Price)> {
+			Prices::get_price_history(currency_id, from, to)
+				.into_iter()
+				.map(|record| (record.block_number, record.source, record.price))
+				.collect()
+		}
+
+		fn get_liquidation_prices(block_number: BlockNumber, borrower: AccountId) -> Option<Vec<(CurrencyId, Price)>> {
+			RiskManager::get_liquidation_prices(block_number, &borrower)
+		}
 	}
 
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
@@ -60,4 +60,10 @@ impl<T: frame_system::Config> controller::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Estimated as the accrual of every enabled pool. Regenerate with the `on_initialize` benchmark.
+	fn on_initialize() -> Weight {
+		(190_048_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(40 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
 }
@@ -30,4 +30,10 @@ impl<T: frame_system::Config> mnt_token::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Estimated as `set_speed` of a pool. Regenerate with the `set_dex_pair_speed` benchmark.
+	fn set_dex_pair_speed() -> Weight {
+		(46_283_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
@@ -1,19 +1,66 @@
-use crate::Runtime;
+use crate::{AccountId, ChainlinkFeed, ChainlinkPriceManager, CurrencyId, Runtime, BTC, DOLLARS, DOT};
+use frame_benchmarking::account;
 use frame_system::RawOrigin;
+use minterest_primitives::currency::CurrencyType::UnderlyingAsset;
 use orml_benchmarking::runtime_benchmarks;
+use sp_std::vec;
+
+const SEED: u32 = 0;
+
+fn create_and_bind_feeds() -> Result<(), &'static str> {
+	let feed_creator: AccountId = account("feed_creator", 0, SEED);
+	let oracle: AccountId = account("oracle", 0, SEED);
+	pallet_chainlink_feed::FeedCreators::<Runtime>::insert(&feed_creator, ());
+	CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+		.into_iter()
+		.enumerate()
+		.try_for_each(|(feed_id, currency_id)| -> Result<(), &'static str> {
+			ChainlinkFeed::create_feed(
+				RawOrigin::Signed(feed_creator.clone()).into(),
+				20,
+				10,
+				(10, 1_000 * DOLLARS),
+				1,
+				18,
+				ChainlinkPriceManager::convert_to_description(currency_id).to_vec(),
+				0,
+				vec![(oracle.clone(), oracle.clone())],
+				None,
+				None,
+			)?;
+			ChainlinkPriceManager::bind_feed(RawOrigin::Root.into(), currency_id, feed_id as u32, None)?;
+			Ok(())
+		})
+}
 
 runtime_benchmarks! {
 	{ Runtime, chainlink_price_manager }
 
+	bind_feed {
+		create_and_bind_feeds()?;
+		ChainlinkPriceManager::unbind_feed(RawOrigin::Root.into(), BTC)?;
+	}: _(
+		RawOrigin::Root,
+		BTC,
+		2, // feed_id
+		Some(8)
+	)
+	verify { assert_eq!(ChainlinkPriceManager::feed_id_storage(BTC), Some(2)) }
+
 	disable_feeding {
+		create_and_bind_feeds()?;
+		ChainlinkPriceManager::enable_feeding(RawOrigin::Root.into())?;
 	}: _(
 		RawOrigin::Root
 	)
+	verify { assert!(!ChainlinkPriceManager::feeding_enabled_storage()) }
 
 	enable_feeding {
+		create_and_bind_feeds()?;
 	}: _(
 		RawOrigin::Root
 	)
+	verify { assert!(ChainlinkPriceManager::feeding_enabled_storage()) }
 
 	initiate_new_round {
 	}: _(
@@ -21,6 +68,32 @@ runtime_benchmarks! {
 		0, // feed_id
 		1 // round_id
 	)
+	verify { assert_eq!(ChainlinkPriceManager::last_initiated_round_storage(0), Some(1)) }
+
+	set_max_staleness {
+	}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(100)
+	)
+	verify { assert_eq!(ChainlinkPriceManager::max_staleness_storage(DOT), Some(100)) }
+
+	set_round_initiation_period {
+		create_and_bind_feeds()?;
+	}: _(
+		RawOrigin::Root,
+		0, // feed_id
+		Some(10)
+	)
+	verify { assert_eq!(ChainlinkPriceManager::round_initiation_period_storage(0), Some(10)) }
+
+	unbind_feed {
+		create_and_bind_feeds()?;
+	}: _(
+		RawOrigin::Root,
+		BTC
+	)
+	verify { assert_eq!(ChainlinkPriceManager::feed_id_storage(BTC), None) }
 }
 
 #[cfg(test)]
@@ -29,6 +102,13 @@ mod tests {
 	use crate::benchmarking::utils::tests::test_externalities;
 	use frame_support::assert_ok;
 
+	#[test]
+	fn test_bind_feed() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_bind_feed());
+		})
+	}
+
 	#[test]
 	fn test_disable_feeding() {
 		test_externalities().execute_with(|| {
@@ -49,4 +129,25 @@ mod tests {
 			assert_ok!(test_benchmark_initiate_new_round());
 		})
 	}
+
+	#[test]
+	fn test_set_max_staleness() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_staleness());
+		})
+	}
+
+	#[test]
+	fn test_set_round_initiation_period() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_round_initiation_period());
+		})
+	}
+
+	#[test]
+	fn test_unbind_feed() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_unbind_feed());
+		})
+	}
 }
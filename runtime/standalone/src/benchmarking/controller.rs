@@ -1,7 +1,10 @@
-use crate::{Balance, Operation, Rate, Runtime, DOT};
+use super::utils::{create_pools, prepare_for_mnt_distribution};
+use crate::{Balance, Controller, EnabledUnderlyingAssetsIds, Operation, Rate, Runtime, System, DOT};
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 use orml_benchmarking::{runtime_benchmarks, Zero};
 use sp_runtime::traits::One;
+use sp_std::prelude::*;
 
 runtime_benchmarks! {
 	{ Runtime, controller }
@@ -52,6 +55,19 @@ runtime_benchmarks! {
 		Balance::zero()
 	)
 
+	on_initialize {
+		// Deposits and borrows in all enabled pools, so that each of them is accrued.
+		let pools = EnabledUnderlyingAssetsIds::get();
+		create_pools(&pools);
+		prepare_for_mnt_distribution(pools)?;
+		System::set_block_number(10);
+	}: { Controller::on_initialize(10); }
+	verify {
+		EnabledUnderlyingAssetsIds::get().into_iter().for_each(|pool_id| {
+			assert_eq!(Controller::controller_data_storage(pool_id).last_interest_accrued_block, 10);
+			assert_eq!(Controller::pool_history_storage(pool_id, 0).map(|checkpoint| checkpoint.block_number), Some(10));
+		});
+	}
 }
 
 #[cfg(test)]
@@ -108,4 +124,11 @@ mod tests {
 			assert_ok!(test_benchmark_set_protocol_interest_threshold());
 		})
 	}
+
+	#[test]
+	fn test_on_initialize() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		})
+	}
 }
@@ -0,0 +1,109 @@
+use super::utils::{set_balance, SEED};
+use crate::{
+	AccountId, Currencies, CurrencyId, Dex, DexMaxClaimTradingPairs, MntToken, Runtime, System, BTC, DOLLARS, DOT, ETH,
+	KSM, MNT,
+};
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use minterest_primitives::TradingPair;
+use orml_benchmarking::runtime_benchmarks;
+use orml_traits::MultiCurrency;
+use sp_runtime::traits::Zero;
+use sp_std::prelude::*;
+
+/// Adds the liquidity of 100_000 DOT and 200_000 ETH to the DOT-ETH pool with the MNT speed set
+/// and advances the block number, so that the next liquidity change updates the cumulative prices
+/// and distributes MNT to the provider.
+fn prepare_dex_pair_for_mnt_distribution(provider: &AccountId) -> Result<(), &'static str> {
+	set_balance(MNT, &MntToken::get_account_id(), 1_000_000 * DOLLARS)?;
+	MntToken::set_dex_pair_speed(RawOrigin::Root.into(), DOT, ETH, DOLLARS)?;
+	set_balance(DOT, provider, 100_000 * DOLLARS)?;
+	set_balance(ETH, provider, 200_000 * DOLLARS)?;
+	Dex::add_liquidity(RawOrigin::Signed(provider.clone()).into(), DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS, 0)?;
+	System::set_block_number(10);
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, dex }
+
+	add_liquidity {
+		// The cumulative prices are updated and MNT is distributed to the provider.
+		let provider: AccountId = account("provider", 0, SEED);
+		prepare_dex_pair_for_mnt_distribution(&provider)?;
+		set_balance(DOT, &provider, 100_000 * DOLLARS)?;
+		set_balance(ETH, &provider, 200_000 * DOLLARS)?;
+	}: _(RawOrigin::Signed(provider.clone()), DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS, 0)
+	verify {
+		assert_eq!(
+			Dex::liquidity_pool_storage(TradingPair::from_currency_ids(DOT, ETH).unwrap()),
+			(200_000 * DOLLARS, 400_000 * DOLLARS)
+		);
+	}
+
+	remove_liquidity {
+		// Half of the shares are removed, so the cumulative prices are updated. MNT is distributed
+		// to the provider.
+		let provider: AccountId = account("provider", 0, SEED);
+		let trading_pair = TradingPair::from_currency_ids(DOT, ETH).unwrap();
+		prepare_dex_pair_for_mnt_distribution(&provider)?;
+		let shares = Dex::shares_storage(trading_pair, &provider);
+	}: _(RawOrigin::Signed(provider.clone()), DOT, ETH, shares / 2, 0, 0)
+	verify { assert_eq!(Dex::total_shares_storage(trading_pair), shares - shares / 2) }
+
+	claim_mnt {
+		let n in 1 .. DexMaxClaimTradingPairs::get();
+		let provider: AccountId = account("provider", 0, SEED);
+		let currencies = [MNT, DOT, ETH, KSM, BTC];
+		let trading_pairs: Vec<(CurrencyId, CurrencyId)> = currencies
+			.iter()
+			.enumerate()
+			.flat_map(|(i, &currency_id_a)| currencies[i + 1..].iter().map(move |&currency_id_b| (currency_id_a, currency_id_b)))
+			.take(n as usize)
+			.collect();
+		set_balance(MNT, &MntToken::get_account_id(), 1_000_000 * DOLLARS)?;
+		for &(currency_id_a, currency_id_b) in trading_pairs.iter() {
+			set_balance(currency_id_a, &provider, 100_000 * DOLLARS)?;
+			set_balance(currency_id_b, &provider, 200_000 * DOLLARS)?;
+			MntToken::set_dex_pair_speed(RawOrigin::Root.into(), currency_id_a, currency_id_b, DOLLARS)?;
+			Dex::add_liquidity(
+				RawOrigin::Signed(provider.clone()).into(),
+				currency_id_a,
+				currency_id_b,
+				100_000 * DOLLARS,
+				200_000 * DOLLARS,
+				0,
+			)?;
+		}
+		System::set_block_number(10);
+	}: _(RawOrigin::Signed(provider.clone()), trading_pairs)
+	verify { assert!(!Currencies::free_balance(MNT, &provider).is_zero()) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::test_externalities;
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_add_liquidity() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_add_liquidity());
+		})
+	}
+
+	#[test]
+	fn test_remove_liquidity() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_remove_liquidity());
+		})
+	}
+
+	#[test]
+	fn test_claim_mnt() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_claim_mnt());
+		})
+	}
+}
@@ -1,9 +1,55 @@
-use super::utils::set_balance;
-use crate::{AccountId, DexPalletId, LiquidationPools, LiquidationPoolsPalletId, Rate, Runtime, DOLLARS, DOT, ETH};
+use super::utils::{enable_is_collateral_mock, set_balance, SEED};
+use crate::{
+	AccountId, AtRiskDebtAccountsPerBlock, Balance, Currencies, CurrencyId, Dex, DexTradingPathLimit,
+	EnabledUnderlyingAssetsIds, LiquidationPools, LiquidationPoolsPalletId, LiquidityPools, Origin, Rate, Runtime, BTC,
+	DOLLARS, DOT, ETH, KSM, MDOT,
+};
 use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
+use liquidation_pools::{BalancingMode, IdealBalanceMode};
+use liquidity_pools::PoolData;
 use orml_benchmarking::runtime_benchmarks;
-use sp_runtime::traits::{AccountIdConversion, One};
+use orml_traits::MultiCurrency;
+use pallet_traits::{LiquidityPoolStorageProvider, UserStorageProvider};
+use sp_runtime::{
+	traits::{AccountIdConversion, One, Zero},
+	FixedPointNumber,
+};
+use sp_std::prelude::*;
+
+/// Sets up DOT liquidation pool oversupply of 42_000$ and ETH liquidation pool shortfall of 40_000$.
+fn prepare_liquidation_pools_for_balancing() -> Result<(), &'static str> {
+	set_balance(DOT, &LiquidationPoolsPalletId::get().into_account(), 21_000 * DOLLARS)?;
+	LiquidityPools::set_pool_data(
+		ETH,
+		PoolData {
+			borrowed: 100_000 * DOLLARS,
+			borrow_index: Rate::one(),
+			protocol_interest: Balance::zero(),
+		},
+	);
+	LiquidationPools::set_balance_ratio(RawOrigin::Root.into(), ETH, 2 * 10u128.pow(17))?;
+	Ok(())
+}
+
+/// Starts the auction of 21_000 DOT with the bid of 20_000 ETH.
+fn start_auction_with_bid() -> Result<(), &'static str> {
+	prepare_liquidation_pools_for_balancing()?;
+	LiquidationPools::set_balancing_mode(RawOrigin::Root.into(), DOT, BalancingMode::Auction)?;
+	LiquidationPools::balance_liquidation_pools(
+		RawOrigin::None.into(),
+		DOT,
+		ETH,
+		21_000 * DOLLARS,
+		20_000 * DOLLARS,
+		Vec::new(),
+	)?;
+	let bidder: AccountId = account("bidder", 0, 0);
+	set_balance(ETH, &bidder, 20_000 * DOLLARS)?;
+	LiquidationPools::bid(RawOrigin::Signed(bidder).into(), 0, 20_000 * DOLLARS)?;
+	Ok(())
+}
 
 runtime_benchmarks! {
 	{ Runtime, liquidation_pools }
@@ -23,17 +69,103 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Signed(who), DOT, 20_000)
 
 	balance_liquidation_pools {
-		set_balance(
-			ETH,
-			&DexPalletId::get().into_account(),
-			20_000 * DOLLARS,
-		)?;
+		// The sales list is collected over `p` pools, the worst case is every enabled pool.
+		let p in 2 .. EnabledUnderlyingAssetsIds::get().len() as u32;
+		// The swap goes along the trading path of `t` currencies: DOT -> KSM -> ... -> ETH, the
+		// worst case is the longest trading path.
+		let t in 2 .. DexTradingPathLimit::get();
+		EnabledUnderlyingAssetsIds::get()
+			.into_iter()
+			.filter(|&pool_id| pool_id != DOT && pool_id != ETH)
+			.skip(p as usize - 2)
+			.for_each(LiquidityPools::remove_pool_data);
+		let path: Vec<CurrencyId> = [DOT]
+			.iter()
+			.chain([KSM, BTC].iter().take(t as usize - 2))
+			.chain([ETH].iter())
+			.copied()
+			.collect();
+		// 1 DOT = 2 ETH along the trading path.
+		let provider: AccountId = account("provider", 0, 0);
+		path.windows(2).enumerate().try_for_each(|(index, pair)| -> Result<(), &'static str> {
+			let amount_a = if index == 0 { 1_000_000 * DOLLARS } else { 2_000_000 * DOLLARS };
+			set_balance(pair[0], &provider, amount_a)?;
+			set_balance(pair[1], &provider, 2_000_000 * DOLLARS)?;
+			Dex::add_liquidity(RawOrigin::Signed(provider.clone()).into(), pair[0], pair[1], amount_a, 2_000_000 * DOLLARS, 0)?;
+			Ok(())
+		})?;
+		prepare_liquidation_pools_for_balancing()?;
+	}: _(RawOrigin::None, DOT, ETH, 21_000 * DOLLARS, 20_000 * DOLLARS, path)
+	verify { assert_eq!(Currencies::free_balance(ETH, &LiquidationPoolsPalletId::get().into_account()), 20_000 * DOLLARS) }
+
+	set_protocol_owned_liquidity_factor {}: _(RawOrigin::Root, DOT, 10u128.pow(18))
+	verify { assert_eq!(LiquidationPools::protocol_owned_liquidity_factor_storage(DOT), Rate::one()) }
+
+	deposit_protocol_owned_liquidity {
 		set_balance(
 			DOT,
 			&LiquidationPoolsPalletId::get().into_account(),
 			20_000 * DOLLARS,
 		)?;
-	}: _(RawOrigin::None, DOT, ETH, 10_000 * DOLLARS, 10_000 * DOLLARS)
+		LiquidationPools::set_protocol_owned_liquidity_factor(RawOrigin::Root.into(), DOT, 10u128.pow(18))?;
+	}: _(RawOrigin::Root, DOT)
+	verify {
+		assert!(Currencies::free_balance(DOT, &LiquidationPoolsPalletId::get().into_account()).is_zero())
+	}
+
+	set_max_slippage {}: _(RawOrigin::Root, DOT, ETH, 10u128.pow(18))
+	verify { assert_eq!(LiquidationPools::max_slippage_storage(DOT, ETH), Rate::one()) }
+
+	set_balancing_mode {}: _(RawOrigin::Root, DOT, BalancingMode::Auction)
+	verify { assert_eq!(LiquidationPools::balancing_mode_storage(DOT), BalancingMode::Auction) }
+
+	bid {
+		// The previous highest bid is returned to its bidder.
+		start_auction_with_bid()?;
+		let who: AccountId = account("alice", 0, 0);
+		set_balance(ETH, &who, 21_000 * DOLLARS)?;
+	}: _(RawOrigin::Signed(who.clone()), 0, 21_000 * DOLLARS)
+	verify { assert_eq!(LiquidationPools::auctions_storage(0).and_then(|auction| auction.bid), Some((who, 21_000 * DOLLARS))) }
+
+	settle_auction {
+		start_auction_with_bid()?;
+		let end_block = LiquidationPools::auctions_storage(0).ok_or("auction is not started")?.end_block;
+	}: { LiquidationPools::on_initialize(end_block); }
+	verify { assert!(LiquidationPools::auctions_storage(0).is_none()) }
+
+	set_ideal_balance_mode {}: _(RawOrigin::Root, DOT, IdealBalanceMode::AtRiskDebt)
+	verify { assert_eq!(LiquidationPools::ideal_balance_mode_storage(DOT), IdealBalanceMode::AtRiskDebt) }
+
+	set_at_risk_health_threshold {}: _(RawOrigin::Root, 2 * 10u128.pow(18))
+	verify { assert_eq!(LiquidationPools::at_risk_health_threshold_storage(), Rate::saturating_from_integer(2)) }
+
+	withdraw_to_treasury {
+		// DOT liquidation pool surplus: 20_000 DOT * 2$ - 10_000$ = 30_000$.
+		set_balance(DOT, &LiquidationPoolsPalletId::get().into_account(), 20_000 * DOLLARS)?;
+		LiquidationPools::set_max_ideal_balance(RawOrigin::Root.into(), DOT, Some(10_000 * DOLLARS))?;
+		let treasury: AccountId = account("treasury", 0, 0);
+		LiquidationPools::set_treasury_account(RawOrigin::Root.into(), treasury.clone())?;
+	}: _(RawOrigin::Root, DOT, 10_000 * DOLLARS)
+	verify { assert_eq!(Currencies::free_balance(DOT, &treasury), 10_000 * DOLLARS) }
+
+	set_treasury_account {
+		let treasury: AccountId = account("treasury", 0, 0);
+	}: _(RawOrigin::Root, treasury.clone())
+	verify { assert_eq!(LiquidationPools::treasury_account_storage(), Some(treasury)) }
+
+	update_pool_at_risk_debt {
+		// All pool members are checked within a single block.
+		let n in 1 .. AtRiskDebtAccountsPerBlock::get() - 1;
+		(0..n).try_for_each(|index| -> Result<(), &'static str> {
+			let borrower: AccountId = account("borrower", index, SEED);
+			set_balance(MDOT, &borrower, 10_000 * DOLLARS)?;
+			enable_is_collateral_mock::<Runtime>(Origin::signed(borrower.clone()), DOT)?;
+			LiquidityPools::set_user_borrow_and_interest_index(&borrower, DOT, 10_000 * DOLLARS, Rate::one());
+			Ok(())
+		})?;
+		LiquidationPools::set_ideal_balance_mode(RawOrigin::Root.into(), DOT, IdealBalanceMode::AtRiskDebt)?;
+	}: { LiquidationPools::on_initialize(1); }
+	verify { assert!(LiquidationPools::at_risk_debt_storage(DOT).is_some()) }
 }
 
 #[cfg(test)]
@@ -76,4 +208,81 @@ mod tests {
 			assert_ok!(test_benchmark_transfer_to_liquidation_pool());
 		})
 	}
+
+	#[test]
+	fn test_set_protocol_owned_liquidity_factor() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_protocol_owned_liquidity_factor());
+		})
+	}
+
+	#[test]
+	fn test_deposit_protocol_owned_liquidity() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_deposit_protocol_owned_liquidity());
+		})
+	}
+
+	#[test]
+	fn test_set_max_slippage() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_slippage());
+		})
+	}
+
+	#[test]
+	fn test_set_balancing_mode() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_balancing_mode());
+		})
+	}
+
+	#[test]
+	fn test_bid() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_bid());
+		})
+	}
+
+	#[test]
+	fn test_settle_auction() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_settle_auction());
+		})
+	}
+
+	#[test]
+	fn test_set_ideal_balance_mode() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_ideal_balance_mode());
+		})
+	}
+
+	#[test]
+	fn test_set_at_risk_health_threshold() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_at_risk_health_threshold());
+		})
+	}
+
+	#[test]
+	fn test_withdraw_to_treasury() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_withdraw_to_treasury());
+		})
+	}
+
+	#[test]
+	fn test_set_treasury_account() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_treasury_account());
+		})
+	}
+
+	#[test]
+	fn test_update_pool_at_risk_debt() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_update_pool_at_risk_debt());
+		})
+	}
 }
@@ -1,5 +1,6 @@
-use super::utils::{create_pools, prepare_for_mnt_distribution};
-use crate::{EnabledUnderlyingAssetsIds, MntToken, Runtime, System, DOLLARS, DOT};
+use super::utils::{create_pools, prepare_for_mnt_distribution, set_balance, SEED};
+use crate::{AccountId, Dex, EnabledUnderlyingAssetsIds, MntToken, Runtime, System, DOLLARS, DOT, ETH};
+use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
 
@@ -14,6 +15,17 @@ runtime_benchmarks! {
 		MntToken::set_speed(RawOrigin::Root.into(), DOT, 1)?;
 		System::set_block_number(11);
 	}: _(RawOrigin::Root, DOT, 10 * DOLLARS)
+
+	set_dex_pair_speed {
+		// MNT distributed with the old speed is accrued to the shares of the pair.
+		let provider: AccountId = account("provider", 0, SEED);
+		set_balance(DOT, &provider, 100_000 * DOLLARS)?;
+		set_balance(ETH, &provider, 200_000 * DOLLARS)?;
+		System::set_block_number(10);
+		MntToken::set_dex_pair_speed(RawOrigin::Root.into(), DOT, ETH, 1)?;
+		Dex::add_liquidity(RawOrigin::Signed(provider).into(), DOT, ETH, 100_000 * DOLLARS, 200_000 * DOLLARS, 0)?;
+		System::set_block_number(11);
+	}: _(RawOrigin::Root, DOT, ETH, 10 * DOLLARS)
 }
 
 #[cfg(test)]
@@ -28,4 +40,11 @@ pub mod tests {
 			assert_ok!(test_benchmark_set_speed());
 		})
 	}
+
+	#[test]
+	fn test_set_dex_pair_speed() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_dex_pair_speed());
+		})
+	}
 }
@@ -2,6 +2,7 @@
 
 pub mod chainlink_price_manager;
 pub mod controller;
+pub mod dex;
 pub mod liquidation_pools;
 pub mod minterest_model;
 pub mod minterest_protocol;
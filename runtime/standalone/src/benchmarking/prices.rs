@@ -1,27 +1,81 @@
-use crate::{CurrencyId, EnabledUnderlyingAssetsIds, MinterestOracle, Origin, Price, Prices, Runtime, DOT};
+use crate::{
+	CurrencyId, EnabledUnderlyingAssetsIds, MinterestOracle, Origin, Price, Prices, Rate, Runtime,
+	TwapObservationPeriod, DOT,
+};
 
 use frame_system::RawOrigin;
+use module_prices::{DeviationAction, OracleGuard, PriceAggregation};
 use orml_benchmarking::runtime_benchmarks;
-use sp_runtime::traits::One;
+use sp_runtime::{traits::One, FixedPointNumber};
 use sp_std::vec;
 
 runtime_benchmarks! {
 	{ Runtime, module_prices }
 
+	confirm_price_jump {
+		MinterestOracle::feed_values(RawOrigin::Root.into(), vec![(DOT, Price::one())])?;
+		module_prices::LockedPriceStorage::<Runtime>::insert(DOT, Price::one());
+		module_prices::PriceJumpStorage::<Runtime>::insert(DOT, Price::saturating_from_integer(2));
+	}: _(
+		RawOrigin::Root,
+		DOT
+	)
+	verify { assert_eq!(Prices::locked_price_storage(DOT), None) }
+
 	lock_price {
 		let pool_id: CurrencyId = EnabledUnderlyingAssetsIds::get()[0];
 
 		MinterestOracle::feed_values(RawOrigin::Root.into(), vec![(pool_id, Price::one())])?;
+	}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(100)
+	)
+	verify { assert_eq!(Prices::locked_price_expiry_storage(DOT), Some(100)) }
+
+	reject_price_jump {
+		MinterestOracle::feed_values(RawOrigin::Root.into(), vec![(DOT, Price::one())])?;
+		module_prices::LockedPriceStorage::<Runtime>::insert(DOT, Price::one());
+		module_prices::PriceJumpStorage::<Runtime>::insert(DOT, Price::saturating_from_integer(2));
 	}: _(
 		RawOrigin::Root,
 		DOT
 	)
+	verify { assert_eq!(Prices::locked_price_storage(DOT), Some(Price::one())) }
+
+	set_circuit_breaker_threshold {
+	}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(Rate::saturating_from_rational(1, 10))
+	)
+	verify { assert!(Prices::circuit_breaker_threshold_storage(DOT).is_some()) }
+
+	set_oracle_guard {
+	}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(OracleGuard {
+			max_deviation: Rate::saturating_from_rational(5, 100),
+			aggregation: PriceAggregation::Median,
+			deviation_action: DeviationAction::LockPrice,
+		})
+	)
+	verify { assert!(Prices::oracle_guard_storage(DOT).is_some()) }
+
+	set_twap_window {
+	}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(TwapObservationPeriod::get())
+	)
+	verify { assert_eq!(Prices::twap_window_storage(DOT), Some(TwapObservationPeriod::get())) }
 
 	unlock_price {
 		let pool_id: CurrencyId = EnabledUnderlyingAssetsIds::get()[0];
 
 		MinterestOracle::feed_values(RawOrigin::Root.into(), vec![(pool_id, Price::one())])?;
-		Prices::lock_price(Origin::root(), DOT)?;
+		Prices::lock_price(Origin::root(), DOT, None)?;
 	}: _(
 		RawOrigin::Root,
 		DOT
@@ -34,6 +88,13 @@ pub mod tests {
 	use crate::benchmarking::utils::tests::test_externalities;
 	use frame_support::assert_ok;
 
+	#[test]
+	fn test_confirm_price_jump() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_confirm_price_jump());
+		})
+	}
+
 	#[test]
 	fn test_lock_price() {
 		test_externalities().execute_with(|| {
@@ -41,6 +102,34 @@ pub mod tests {
 		})
 	}
 
+	#[test]
+	fn test_reject_price_jump() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_reject_price_jump());
+		})
+	}
+
+	#[test]
+	fn test_set_circuit_breaker_threshold() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_circuit_breaker_threshold());
+		})
+	}
+
+	#[test]
+	fn test_set_oracle_guard() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_oracle_guard());
+		})
+	}
+
+	#[test]
+	fn test_set_twap_window() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_twap_window());
+		})
+	}
+
 	#[test]
 	fn test_unlock_price() {
 		test_externalities().execute_with(|| {
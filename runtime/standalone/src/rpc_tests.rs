@@ -1,28 +1,36 @@
 use crate::{
-	AccountId, Balance, Block, Controller, Currencies, EnabledUnderlyingAssetsIds, LiquidationPools, LiquidityPools,
-	MinterestCouncilMembership, MinterestOracle, MinterestProtocol, MntToken, Prices, Rate, Runtime, System, UserData,
-	Whitelist, DOLLARS, PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+	AccountId, Balance, Block, BlockNumber, Controller, Currencies, Dex, EnabledUnderlyingAssetsIds, LiquidationPools,
+	LiquidityPools, MinterestCouncilMembership, MinterestOracle, MinterestProtocol, MntToken, Prices, Rate, Runtime,
+	System, UserData, Whitelist, DOLLARS, HOURS, PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
 };
 use controller::{ControllerData, PauseKeeper};
 use controller_rpc_runtime_api::{
-	runtime_decl_for_ControllerRuntimeApi::ControllerRuntimeApi, BalanceInfo, HypotheticalLiquidityData, PoolState,
-	ProtocolTotalValue, UserPoolBalanceData,
+	runtime_decl_for_ControllerRuntimeApi::ControllerRuntimeApi, BalanceInfo, HypotheticalLiquidityData,
+	PoolCheckpointData, PoolSolvencyData, PoolState, ProtocolTotalValue, UserPoolBalanceData, WrappedBalanceData,
 };
+use dex_rpc_runtime_api::runtime_decl_for_DexRuntimeApi::DexRuntimeApi;
 use frame_support::pallet_prelude::{DispatchResultWithPostInfo, PhantomData};
 use frame_support::{assert_noop, assert_ok, pallet_prelude::GenesisBuild, parameter_types, traits::OnFinalize};
-use liquidation_pools::LiquidationPoolData;
+use liquidation_pools::{BalancingMode, LiquidationPoolData};
+use liquidation_pools_rpc_runtime_api::{
+	runtime_decl_for_LiquidationPoolsRuntimeApi::LiquidationPoolsRuntimeApi, BalancingPlan, BalancingSale,
+	BalancingSaleStatus, LiquidationPoolBalancingInfo,
+};
 use liquidity_pools::{PoolData, PoolUserData};
 use minterest_model::MinterestModelData;
 use minterest_primitives::{CurrencyId, Interest, Operation, Price};
+use mnt_token::RewardTarget;
 use mnt_token_rpc_runtime_api::runtime_decl_for_MntTokenRuntimeApi::MntTokenRuntimeApi;
 use orml_traits::MultiCurrency;
-use pallet_traits::{ControllerManager, LiquidityPoolStorageProvider, PoolsManager, PricesManager, UserCollateral};
+use pallet_traits::{
+	Borrowing, ControllerManager, LiquidityPoolStorageProvider, PoolsManager, PriceSource, UserCollateral,
+};
 use prices_rpc_runtime_api::runtime_decl_for_PricesRuntimeApi::PricesRuntimeApi;
 use sp_runtime::{
 	traits::{One, Zero},
 	DispatchResult, FixedPointNumber,
 };
-use test_helper::{BTC, DOT, ETH, KSM, MDOT, MNT};
+use test_helper::{BTC, DOT, ETH, KSM, MDOT, METH, MNT};
 use whitelist_rpc_runtime_api::runtime_decl_for_WhitelistRuntimeApi::WhitelistRuntimeApi;
 
 parameter_types! {
@@ -338,6 +346,14 @@ fn get_pool_utilization_rate_rpc(pool_id: CurrencyId) -> Option<Rate> {
 	<Runtime as ControllerRuntimeApi<Block, AccountId>>::get_pool_utilization_rate(pool_id)
 }
 
+fn get_pool_solvency_data_rpc(pool_id: CurrencyId) -> Option<PoolSolvencyData> {
+	<Runtime as ControllerRuntimeApi<Block, AccountId>>::get_pool_solvency_data(pool_id)
+}
+
+fn get_pool_history_rpc(pool_id: CurrencyId, from: BlockNumber, to: BlockNumber) -> Option<Vec<PoolCheckpointData>> {
+	<Runtime as ControllerRuntimeApi<Block, AccountId>>::get_pool_history(pool_id, from, to)
+}
+
 fn get_user_total_supply_and_borrow_balance_in_usd_rpc(account_id: AccountId) -> Option<UserPoolBalanceData> {
 	<Runtime as ControllerRuntimeApi<Block, AccountId>>::get_user_total_supply_and_borrow_balance_in_usd(account_id)
 }
@@ -368,12 +384,29 @@ fn get_user_underlying_balance_per_asset_rpc(account_id: AccountId, pool_id: Cur
 	<Runtime as ControllerRuntimeApi<Block, AccountId>>::get_user_underlying_balance_per_asset(account_id, pool_id)
 }
 
+fn get_user_wrapped_balances_rpc(account_id: AccountId) -> Option<Vec<WrappedBalanceData>> {
+	<Runtime as ControllerRuntimeApi<Block, AccountId>>::get_user_wrapped_balances(account_id)
+}
+
 fn get_user_total_unclaimed_mnt_balance_rpc(account_id: AccountId) -> Balance {
 	<Runtime as MntTokenRuntimeApi<Block, AccountId>>::get_user_total_unclaimed_mnt_balance(account_id)
 		.unwrap()
 		.amount
 }
 
+fn get_best_route_rpc(
+	supply_currency_id: CurrencyId,
+	target_currency_id: CurrencyId,
+	supply_amount: Balance,
+) -> Option<(Vec<CurrencyId>, Balance)> {
+	<Runtime as DexRuntimeApi<Block>>::get_best_route(supply_currency_id, target_currency_id, supply_amount)
+		.map(|(path, target_amount)| (path, target_amount.amount))
+}
+
+fn get_balancing_plan_rpc() -> Option<BalancingPlan> {
+	<Runtime as LiquidationPoolsRuntimeApi<Block>>::get_balancing_plan()
+}
+
 fn pool_exists_rpc(underlying_asset_id: CurrencyId) -> bool {
 	<Runtime as ControllerRuntimeApi<Block, AccountId>>::pool_exists(underlying_asset_id)
 }
@@ -418,16 +451,16 @@ fn set_oracle_prices(prices: Vec<(CurrencyId, Price)>) -> DispatchResult {
 	Ok(())
 }
 
-fn get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>)> {
-	<Runtime as PricesRuntimeApi<Block>>::get_all_locked_prices()
+fn get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>, Option<BlockNumber>)> {
+	<Runtime as PricesRuntimeApi<Block, AccountId>>::get_all_locked_prices()
 }
 
 fn get_all_freshest_prices() -> Vec<(CurrencyId, Option<Price>)> {
-	<Runtime as PricesRuntimeApi<Block>>::get_all_freshest_prices()
+	<Runtime as PricesRuntimeApi<Block, AccountId>>::get_all_freshest_prices()
 }
 
 fn lock_price(currency_id: CurrencyId) -> DispatchResultWithPostInfo {
-	Prices::lock_price(origin_root(), currency_id)
+	Prices::lock_price(origin_root(), currency_id, None)
 }
 
 fn unlock_price(currency_id: CurrencyId) -> DispatchResultWithPostInfo {
@@ -983,6 +1016,85 @@ fn test_get_pool_utilization_rate_rpc() {
 		});
 }
 
+#[test]
+fn test_get_pool_solvency_data_rpc() {
+	ExtBuilder::default().pool_initial(DOT).build().execute_with(|| {
+		// Set price = 2.00 USD for all pools.
+		assert_ok!(set_oracle_price_for_all_pools(2));
+
+		assert_ok!(MinterestProtocol::deposit_underlying(alice(), DOT, dollars(100_000)));
+		assert_ok!(MinterestProtocol::enable_is_collateral(alice(), DOT));
+		assert_ok!(MinterestProtocol::borrow(alice(), DOT, dollars(50_000)));
+
+		assert_eq!(
+			get_pool_solvency_data_rpc(DOT),
+			Some(PoolSolvencyData {
+				pool_supply_underlying: dollars(50_000),
+				pool_borrow_underlying: dollars(50_000),
+				pool_protocol_interest: Balance::zero(),
+				pool_bad_debt: Balance::zero(),
+				exchange_rate: Rate::one(),
+			})
+		);
+
+		// Write off 10_000 DOT of Alice's loan as bad debt.
+		assert_ok!(<LiquidityPools as Borrowing<AccountId>>::update_state_on_bad_debt(
+			&ALICE::get(),
+			DOT,
+			dollars(10_000),
+			dollars(50_000)
+		));
+
+		// exchange_rate = (50_000 + 40_000 - 0) / 100_000 = 0.9
+		assert_eq!(
+			get_pool_solvency_data_rpc(DOT),
+			Some(PoolSolvencyData {
+				pool_supply_underlying: dollars(50_000),
+				pool_borrow_underlying: dollars(40_000),
+				pool_protocol_interest: Balance::zero(),
+				pool_bad_debt: dollars(10_000),
+				exchange_rate: Rate::saturating_from_rational(9, 10),
+			})
+		);
+		assert_eq!(get_pool_solvency_data_rpc(ETH), None);
+	});
+}
+
+#[test]
+fn test_get_pool_history_rpc() {
+	ExtBuilder::default().pool_initial(DOT).build().execute_with(|| {
+		// Set price = 2.00 USD for all pools.
+		assert_ok!(set_oracle_price_for_all_pools(2));
+		assert_ok!(MinterestProtocol::deposit_underlying(alice(), DOT, dollars(100_000)));
+
+		// Checkpoints are written by `accrue_interest_rate` once per hour.
+		System::set_block_number(HOURS);
+		assert_ok!(MinterestProtocol::deposit_underlying(bob(), DOT, dollars(10_000)));
+		System::set_block_number(HOURS + 1);
+		assert_ok!(MinterestProtocol::deposit_underlying(bob(), DOT, dollars(10_000)));
+		System::set_block_number(2 * HOURS);
+		assert_ok!(MinterestProtocol::deposit_underlying(bob(), DOT, dollars(10_000)));
+
+		assert_eq!(
+			get_pool_history_rpc(DOT, 0, 2 * HOURS),
+			Some(vec![
+				PoolCheckpointData {
+					block_number: HOURS,
+					exchange_rate: Rate::one(),
+					borrow_index: Rate::one(),
+				},
+				PoolCheckpointData {
+					block_number: 2 * HOURS,
+					exchange_rate: Rate::one(),
+					borrow_index: Rate::one(),
+				},
+			])
+		);
+		assert_eq!(get_pool_history_rpc(DOT, HOURS + 1, 2 * HOURS - 1), Some(vec![]));
+		assert_eq!(get_pool_history_rpc(ETH, 0, 2 * HOURS), None);
+	});
+}
+
 /// Test that returned values are changed after some blocks passed
 #[test]
 fn test_user_balances_using_rpc() {
@@ -1441,6 +1553,45 @@ fn test_get_user_underlying_balance_per_asset_rpc() {
 	})
 }
 
+#[test]
+fn test_get_user_wrapped_balances_rpc() {
+	ExtBuilder::default()
+		.pool_initial(DOT)
+		.pool_initial(ETH)
+		.build()
+		.execute_with(|| {
+			// Set price = 2.00 USD for all pools.
+			assert_ok!(set_oracle_price_for_all_pools(2));
+
+			assert_ok!(MinterestProtocol::deposit_underlying(bob(), ETH, dollars(90_000)));
+			assert_ok!(MinterestProtocol::deposit_underlying(alice(), ETH, dollars(100_000)));
+			// Bob makes a borrow to update exchange rate
+			assert_ok!(MinterestProtocol::enable_is_collateral(bob(), ETH));
+			assert_ok!(MinterestProtocol::borrow(bob(), ETH, dollars(40_000)));
+
+			// Skip some blocks to accrue interest.
+			System::set_block_number(100);
+			assert_ok!(Controller::accrue_interest_rate(ETH));
+
+			// 3554127423600000 - this is interest that alice earn for depositing
+			assert_eq!(
+				get_user_wrapped_balances_rpc(ALICE::get()),
+				Some(vec![
+					WrappedBalanceData {
+						currency_id: MDOT,
+						wrapped_amount: Balance::zero(),
+						underlying_amount: Balance::zero(),
+					},
+					WrappedBalanceData {
+						currency_id: METH,
+						wrapped_amount: dollars(100_000),
+						underlying_amount: dollars(100_000) + 3554127423600000,
+					},
+				])
+			);
+		});
+}
+
 #[test]
 fn get_all_locked_prices_rpc_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1449,22 +1600,24 @@ fn get_all_locked_prices_rpc_should_work() {
 		CurrencyId::get_enabled_tokens_in_protocol(minterest_primitives::currency::CurrencyType::UnderlyingAsset)
 			.into_iter()
 			.for_each(|pool_id| {
-				assert_ok!(Prices::lock_price(origin_root(), pool_id));
+				assert_ok!(Prices::lock_price(origin_root(), pool_id, Some(100)));
 			});
 
 		// Check that locked prices are returned
 		// By default all price set to 10_000
 		let locked_prices = get_all_locked_prices();
-		for (_currency_id, price) in locked_prices {
+		for (_currency_id, price, expiry) in locked_prices {
 			assert_eq!(price, Some(Price::saturating_from_integer(10_000)));
+			assert_eq!(expiry, Some(100));
 		}
 		// Unlock price for DOT, check that None will be returned for this currency
 		assert_ok!(unlock_price(DOT));
 		let locked_prices = get_all_locked_prices();
-		for (currency_id, price) in locked_prices {
+		for (currency_id, price, expiry) in locked_prices {
 			match currency_id {
 				DOT => {
 					assert_eq!(price, None);
+					assert_eq!(expiry, None);
 				}
 				ETH | BTC | KSM => {
 					assert_eq!(price, Some(Price::saturating_from_integer(10_000)));
@@ -1594,9 +1747,9 @@ fn get_pool_mnt_borrow_and_supply_rates_should_work() {
 			assert_ok!(MinterestProtocol::borrow(bob(), ETH, 10_000 * DOLLARS));
 			assert_ok!(MinterestProtocol::borrow(alice(), BTC, 5_000 * DOLLARS));
 
-			assert_eq!(MntToken::mnt_speed_storage(DOT), 2_500_000_000_000_000_000);
-			assert_eq!(MntToken::mnt_speed_storage(ETH), 5_000_000_000_000_000_000);
-			assert_eq!(MntToken::mnt_speed_storage(BTC), 2_500_000_000_000_000_000);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(DOT)), 2_500_000_000_000_000_000);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(ETH)), 5_000_000_000_000_000_000);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(BTC)), 2_500_000_000_000_000_000);
 
 			// Borrow and Supply rates per block
 			// Prices: DOT[0] = 2 USD, ETH[1] = 2 USD, BTC[3] = 2 USD, MNT[4] = 4 USD
@@ -1789,8 +1942,8 @@ fn get_user_total_supply_borrow_and_net_apy_should_work() {
 			// Sum = 2360 + 6048 = 8408 $
 			// sum_borrow_apy = 8408/260_000 = 3.23 %
 
-			assert_eq!(MntToken::mnt_speed_storage(DOT), 5 * DOLLARS);
-			assert_eq!(MntToken::mnt_speed_storage(ETH), 5 * DOLLARS);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(DOT)), 5 * DOLLARS);
+			assert_eq!(MntToken::mnt_speed_storage(RewardTarget::Pool(ETH)), 5 * DOLLARS);
 
 			// MNT rates for the pool:
 			// mnt_borrow_rate = mnt_speed * mnt_price / (pool_borrow * currency_price)
@@ -1963,3 +2116,99 @@ fn get_user_total_borrow_usd_rpc_without_initialized_pools_should_return_zero()
 		assert_eq!(get_user_total_borrow_usd_rpc(ALICE::get()), 0);
 	})
 }
+
+#[test]
+fn get_best_route_rpc_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(get_best_route_rpc(DOT, ETH, dollars(1_000)), None);
+
+		assert_ok!(Dex::add_liquidity(
+			alice(),
+			DOT,
+			ETH,
+			dollars(10_000),
+			dollars(20_000),
+			0
+		));
+
+		// 1_000 DOT with 0.3% fee: 20_000 * 997 / (10_000 + 997)
+		assert_eq!(
+			get_best_route_rpc(DOT, ETH, dollars(1_000)),
+			Some((vec![DOT, ETH], 1_813_221_787_760_298_263_162))
+		);
+		assert_eq!(get_best_route_rpc(DOT, KSM, dollars(1_000)), None);
+	})
+}
+
+#[test]
+fn get_balancing_plan_rpc_should_work() {
+	ExtBuilder::default()
+		.pool_initial(DOT)
+		.pool_initial(ETH)
+		.build()
+		.execute_with(|| {
+			assert_ok!(set_oracle_price_for_all_pools(2));
+			assert_ok!(Currencies::deposit(
+				DOT,
+				&LiquidationPools::pools_account_id(),
+				dollars(20_000)
+			));
+			LiquidityPools::set_pool_borrow_underlying(ETH, dollars(100_000));
+			assert_ok!(LiquidationPools::set_max_ideal_balance(
+				origin_root(),
+				ETH,
+				Some(dollars(40_000))
+			));
+
+			// DOT liquidation pool: balance 20_000 DOT * 2$, ideal balance 0$.
+			// ETH liquidation pool: balance 0$, ideal balance min(100_000 ETH * 0.2 * 2$, 40_000$).
+			assert_eq!(
+				get_balancing_plan_rpc(),
+				Some(BalancingPlan {
+					pools: vec![
+						LiquidationPoolBalancingInfo {
+							pool_id: DOT,
+							balance_usd: dollars(40_000),
+							ideal_balance_usd: Balance::zero(),
+							oversupply_usd: dollars(40_000),
+							shortfall_usd: Balance::zero(),
+							left_border_usd: Balance::zero(),
+							right_border_usd: Balance::zero(),
+						},
+						LiquidationPoolBalancingInfo {
+							pool_id: ETH,
+							balance_usd: Balance::zero(),
+							ideal_balance_usd: dollars(40_000),
+							oversupply_usd: Balance::zero(),
+							shortfall_usd: dollars(40_000),
+							left_border_usd: dollars(36_000),
+							right_border_usd: dollars(44_000),
+						},
+					],
+					sales: vec![BalancingSale {
+						supply_pool_id: DOT,
+						target_pool_id: ETH,
+						amount_usd: dollars(40_000),
+						// There are no liquidity pools on DEX.
+						status: BalancingSaleStatus::TradingPathNotFound,
+					}],
+				})
+			);
+
+			// The sale is offered in the auction instead.
+			assert_ok!(LiquidationPools::set_balancing_mode(
+				origin_root(),
+				DOT,
+				BalancingMode::Auction
+			));
+			assert_eq!(
+				get_balancing_plan_rpc().map(|plan| plan.sales),
+				Some(vec![BalancingSale {
+					supply_pool_id: DOT,
+					target_pool_id: ETH,
+					amount_usd: dollars(40_000),
+					status: BalancingSaleStatus::Ready,
+				}])
+			);
+		})
+}
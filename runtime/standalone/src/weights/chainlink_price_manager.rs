@@ -25,13 +25,36 @@ use sp_std::marker::PhantomData;
 /// Weight functions for chainlink_price_manager.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> chainlink_price_manager::WeightInfo for WeightInfo<T> {
+	fn bind_feed() -> Weight {
+		(41_307_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
 	fn disable_feeding() -> Weight {
-		(1_092_000 as Weight)
+		(14_753_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn enable_feeding() -> Weight {
-		(1_082_000 as Weight)
+		(38_204_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn initiate_new_round() -> Weight {
-		(15_379_000 as Weight)
+		(16_745_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_staleness() -> Weight {
+		(20_364_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_round_initiation_period() -> Weight {
+		(23_918_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn unbind_feed() -> Weight {
+		(27_518_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 }
@@ -0,0 +1,51 @@
+//! Weights for dex
+//!
+//! The weights are estimated and aren't generated by the benchmark CLI yet. Regenerate them
+//! on the reference hardware with the following command.
+
+// Command:
+// ./target/release/minterest
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=dex
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=./runtime/standalone/src/weights/dex.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for dex.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> dex::WeightInfo for WeightInfo<T> {
+	// Estimated as the liquidity change with the cumulative price update and the MNT distribution
+	// to the provider. Regenerate with the `add_liquidity` benchmark.
+	fn add_liquidity() -> Weight {
+		(97_812_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Estimated as the liquidity change with the cumulative price update and the MNT distribution
+	// to the provider. Regenerate with the `remove_liquidity` benchmark.
+	fn remove_liquidity() -> Weight {
+		(93_946_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Estimated as the MNT distribution to the provider in each of `n` trading pairs and the
+	// transfer of the accrued MNT. Regenerate with the `claim_mnt` benchmark.
+	fn claim_mnt(n: u32) -> Weight {
+		(24_903_000 as Weight)
+			.saturating_add((46_012_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+}
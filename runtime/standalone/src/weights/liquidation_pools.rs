@@ -45,9 +45,73 @@ impl<T: frame_system::Config> liquidation_pools::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
-	fn balance_liquidation_pools() -> Weight {
-		(124_551_000 as Weight)
+	// Estimated as the sales list collected over `p` pools and the swap along the trading path of
+	// `t` currencies. Regenerate with the `balance_liquidation_pools` benchmark.
+	fn balance_liquidation_pools(p: u32, t: u32) -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add((18_500_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add((12_500_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(t as Weight)))
+	}
+	fn set_protocol_owned_liquidity_factor() -> Weight {
+		(28_370_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn deposit_protocol_owned_liquidity() -> Weight {
+		(196_804_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(22 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	fn set_max_slippage() -> Weight {
+		(29_874_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_balancing_mode() -> Weight {
+		(27_946_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn bid() -> Weight {
+		(96_431_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn settle_auction() -> Weight {
+		(91_208_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn set_ideal_balance_mode() -> Weight {
+		(28_105_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_at_risk_health_threshold() -> Weight {
+		(19_838_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn withdraw_to_treasury() -> Weight {
+		(71_574_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(8 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_treasury_account() -> Weight {
+		(19_206_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn update_pool_at_risk_debt(n: u32) -> Weight {
+		(8_937_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((57_481_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((20 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 }
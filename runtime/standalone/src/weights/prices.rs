@@ -25,12 +25,31 @@ use sp_std::marker::PhantomData;
 /// Weight functions for module_prices.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> module_prices::WeightInfo for WeightInfo<T> {
+	fn confirm_price_jump() -> Weight {
+		(39_514_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
 	fn lock_price() -> Weight {
-		(48_370_000 as Weight)
+		(50_826_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn reject_price_jump() -> Weight {
+		(34_172_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn set_circuit_breaker_threshold() -> Weight {
+		(20_947_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_oracle_guard() -> Weight {
+		(21_036_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_twap_window() -> Weight {
+		(20_582_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn unlock_price() -> Weight {
-		(18_992_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+		(20_417_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 }
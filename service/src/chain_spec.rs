@@ -495,6 +495,10 @@ fn minterest_genesis(
 			pallet_admin: Some(root_key.clone()),
 			feed_creators: vec![root_key],
 		},
+		chainlink_price_manager: parachain_runtime::ChainlinkPriceManagerConfig {
+			feeding_enabled: false,
+			_phantom: Default::default(),
+		},
 		aura_ext: Default::default(),
 		parachain_system: Default::default(),
 	}
@@ -766,6 +770,10 @@ fn standalone_dev_genesis(
 			pallet_admin: Some(root_key.clone()),
 			feed_creators: vec![root_key],
 		},
+		chainlink_price_manager: standalone_runtime::ChainlinkPriceManagerConfig {
+			feeding_enabled: false,
+			_phantom: Default::default(),
+		},
 		aura_ext: Default::default(),
 		parachain_system: Default::default(),
 		parachain_info: Default::default(),
@@ -147,6 +147,7 @@ macro_rules! mock_impl_orml_currencies_config {
 macro_rules! mock_impl_liquidity_pools_config {
 	($target:ty) => {
 		impl liquidity_pools::Config for $target {
+			type Event = Event;
 			type MultiCurrency = orml_currencies::Pallet<$target>;
 			type PriceSource = MockPriceSource;
 			type PalletId = LiquidityPoolsPalletId;
@@ -163,6 +164,13 @@ macro_rules! mock_impl_liquidation_pools_config {
 	($target:ty) => {
 		parameter_types! {
 			pub const MockLiquidityPoolsPriority: TransactionPriority = TransactionPriority::max_value() - 1;
+			pub const MockBalancingBackoffPeriod: u64 = 10;
+			pub const MockAuctionDuration: u64 = 20;
+			pub const MockMaxTreasuryWithdrawalPerCall: Balance = 10_000 * DOLLARS;
+			pub const MockMaxTreasuryWithdrawalPerPeriod: Balance = 15_000 * DOLLARS;
+			pub const MockTreasuryWithdrawalPeriod: u64 = 100;
+			pub const MockAtRiskDebtAccountsPerBlock: u32 = 10;
+			pub const MockDefaultMaxSlippage: Rate = Rate::from_inner(50_000_000_000_000_000); // 5%
 		}
 
 		impl liquidation_pools::Config for $target {
@@ -177,6 +185,15 @@ macro_rules! mock_impl_liquidation_pools_config {
 			type Dex = dex::Pallet<$target>;
 			type LiquidationPoolsWeightInfo = ();
 			type ControllerManager = controller::Pallet<$target>;
+			type MntManager = mnt_token::Pallet<$target>;
+			type BalancingBackoffPeriod = MockBalancingBackoffPeriod;
+			type AuctionDuration = MockAuctionDuration;
+			type MaxTreasuryWithdrawalPerCall = MockMaxTreasuryWithdrawalPerCall;
+			type MaxTreasuryWithdrawalPerPeriod = MockMaxTreasuryWithdrawalPerPeriod;
+			type TreasuryWithdrawalPeriod = MockTreasuryWithdrawalPeriod;
+			type AtRiskDebtAccountsPerBlock = MockAtRiskDebtAccountsPerBlock;
+			type DefaultMaxSlippage = MockDefaultMaxSlippage;
+			type TradingPathLimit = DexTradingPathLimit;
 		}
 
 		/// An extrinsic type used for tests.
@@ -197,6 +214,8 @@ macro_rules! mock_impl_controller_config {
 	($target:ty, $acc:ident) => {
 		parameter_types! {
 			pub const MaxBorrowCap: Balance = 1_000_000_000_000_000_000_000_000;
+			pub const PoolHistoryCheckpointInterval: u64 = 10;
+			pub const PoolHistoryMaxLength: u32 = 5;
 		}
 
 		impl controller::Config for $target {
@@ -209,6 +228,8 @@ macro_rules! mock_impl_controller_config {
 			type UpdateOrigin = EnsureSignedBy<$acc, AccountId>;
 			type ControllerWeightInfo = ();
 			type MntManager = mnt_token::Pallet<$target>;
+			type PoolHistoryCheckpointInterval = PoolHistoryCheckpointInterval;
+			type PoolHistoryMaxLength = PoolHistoryMaxLength;
 		}
 	};
 }
@@ -235,6 +256,10 @@ macro_rules! mock_impl_dex_config {
 		parameter_types! {
 			pub const DexPalletId: PalletId = PalletId(*b"min/dexs");
 			pub DexAccountId: AccountId = DexPalletId::get().into_account();
+			pub const DexSwapFee: Rate = Rate::from_inner(3_000_000_000_000_000); // 0.3%
+			pub const DexTradingPathLimit: u32 = 3;
+			pub const DexMaxClaimTradingPairs: u32 = 3;
+			pub const DexPriceAveragingPeriod: u64 = 10;
 		}
 
 		impl dex::Config for $target {
@@ -242,6 +267,12 @@ macro_rules! mock_impl_dex_config {
 			type MultiCurrency = orml_currencies::Pallet<$target>;
 			type DexPalletId = DexPalletId;
 			type DexAccountId = DexAccountId;
+			type TradingPathLimit = DexTradingPathLimit;
+			type MaxClaimTradingPairs = DexMaxClaimTradingPairs;
+			type SwapFee = DexSwapFee;
+			type PriceAveragingPeriod = DexPriceAveragingPeriod;
+			type MntManager = mnt_token::Pallet<$target>;
+			type DexWeightInfo = ();
 		}
 	};
 }
@@ -274,6 +305,7 @@ macro_rules! mock_impl_risk_manager_config {
 			pub const PartialLiquidationMaxAttempts: u8 = 3_u8;
 			pub const MaxLiquidationFee: Rate = Rate::from_inner(500_000_000_000_000_000);
 			pub const RiskManagerWorkerMaxDurationMs: u64 = 2000_u64;
+			pub const LiquidationPricesRetention: u64 = 100_u64;
 		}
 
 		impl risk_manager::Config for $target {
@@ -291,6 +323,7 @@ macro_rules! mock_impl_risk_manager_config {
 			type MinterestProtocolManager = minterest_protocol::Pallet<$target>;
 			type OffchainWorkerMaxDurationMs = RiskManagerWorkerMaxDurationMs;
 			type MultiCurrency = orml_currencies::Pallet<$target>;
+			type LiquidationPricesRetention = LiquidationPricesRetention;
 		}
 	};
 
@@ -301,6 +334,7 @@ macro_rules! mock_impl_risk_manager_config {
 			pub const PartialLiquidationMaxAttempts: u8 = 3_u8;
 			pub const MaxLiquidationFee: Rate = Rate::from_inner(500_000_000_000_000_000);
 			pub const RiskManagerWorkerMaxDurationMs: u64 = 2000_u64;
+			pub const LiquidationPricesRetention: u64 = 100_u64;
 		}
 
 		impl risk_manager::Config for $target {
@@ -318,6 +352,7 @@ macro_rules! mock_impl_risk_manager_config {
 			type MinterestProtocolManager = minterest_protocol::Pallet<$target>;
 			type OffchainWorkerMaxDurationMs = RiskManagerWorkerMaxDurationMs;
 			type MultiCurrency = orml_currencies::Pallet<$target>;
+			type LiquidationPricesRetention = LiquidationPricesRetention;
 		}
 	};
 }
@@ -325,6 +360,9 @@ macro_rules! mock_impl_risk_manager_config {
 #[macro_export]
 macro_rules! mock_impl_mnt_token_config {
 	($target:ty, $acc:ident) => {
+		$crate::mock_impl_mnt_token_config!($target, $acc, ());
+	};
+	($target:ty, $acc:ident, $dex_shares_provider:ty) => {
 		impl mnt_token::Config for $target {
 			type Event = Event;
 			type PriceSource = MockPriceSource;
@@ -332,6 +370,7 @@ macro_rules! mock_impl_mnt_token_config {
 			type LiquidityPoolsManager = liquidity_pools::Pallet<$target>;
 			type MultiCurrency = orml_currencies::Pallet<$target>;
 			type ControllerManager = controller::Pallet<$target>;
+			type DexSharesProvider = $dex_shares_provider;
 			type MntTokenAccountId = MntTokenAccountId;
 			type MntTokenWeightInfo = ();
 		}
@@ -380,10 +419,24 @@ macro_rules! mock_impl_whitelist_module_config {
 #[macro_export]
 macro_rules! mock_impl_prices_module_config {
 	($target:ty, $acc:ident) => {
+		parameter_types! {
+			pub const TwapObservationPeriod: u64 = 2;
+			pub const MaxTwapObservations: u32 = 5;
+			pub const MaxPriceHistoryLength: u32 = 3;
+			pub const MaxLockDuration: u64 = 10;
+		}
+
 		impl module_prices::Config for $target {
 			type Event = Event;
 			type Source = MockDataProvider;
+			type SecondarySource = MockSecondaryDataProvider;
+			type PoolOperationPauser = MockPoolOperationPauser;
 			type LockOrigin = EnsureSignedBy<$acc, AccountId>;
+			type UpdateOrigin = EnsureSignedBy<$acc, AccountId>;
+			type TwapObservationPeriod = TwapObservationPeriod;
+			type MaxTwapObservations = MaxTwapObservations;
+			type MaxPriceHistoryLength = MaxPriceHistoryLength;
+			type MaxLockDuration = MaxLockDuration;
 			type WeightInfo = ();
 		}
 	};